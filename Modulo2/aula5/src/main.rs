@@ -185,58 +185,380 @@ mod generics {
     }
 
     mod phantom_types {
+        use self::driver::HeaterDriver;
         use std::marker::PhantomData;
 
+        /// Nível mínimo de água (fração do tanque) para considerar o aquecedor em `Medium`.
+        pub const MEDIUM_LEVEL: f32 = 0.4;
+        /// Nível mínimo de água (fração do tanque) para considerar o aquecedor em `High`.
+        pub const HIGH_LEVEL: f32 = 0.8;
+        /// Período do laço de controle, em segundos.
+        const DT: f32 = 1.0;
+        /// Tempo máximo para encher ou esvaziar até o próximo nível, em segundos.
+        const VALVE_TIMEOUT: f32 = 120.0;
+        /// Ganho proporcional do laço de temperatura (potência por °C de erro).
+        const KP: f32 = 0.5;
+
         struct Low;
 
         struct Medium;
 
         struct High;
 
-        struct Heater<S> {
+        /// Somente os níveis em que a resistência fica submersa podem aquecer.
+        trait CanHeat {}
+
+        impl CanHeat for Medium {}
+
+        impl CanHeat for High {}
+
+        struct Heater<S, D: HeaterDriver> {
+            driver: D,
             _marker: PhantomData<S>,
         }
 
-        impl Heater<Low> {
-            pub fn add_water(self) -> Heater<Medium> {
+        #[derive(Debug, PartialEq)]
+        enum HeaterError {
+            /// O nível não chegou ao esperado dentro de `VALVE_TIMEOUT`.
+            Timeout,
+            /// O sensor de nível indicou pouca água com a resistência ligada.
+            DryFire,
+        }
+
+        impl<S, D: HeaterDriver> Heater<S, D> {
+            fn into_state<T>(self) -> Heater<T, D> {
                 Heater {
+                    driver: self.driver,
                     _marker: PhantomData,
                 }
             }
+
+            pub fn driver(&self) -> &D {
+                &self.driver
+            }
+
+            /// Abre `inlet` ou `outlet` até que `reached` seja verdadeiro para o nível atual.
+            fn move_water(&mut self, inlet: bool, reached: impl Fn(f32) -> bool) -> bool {
+                let mut elapsed = 0.0;
+
+                self.driver.set_power(0.0);
+                self.driver.set_inlet(inlet);
+                self.driver.set_outlet(!inlet);
+
+                while !reached(self.driver.level()) && elapsed < VALVE_TIMEOUT {
+                    self.driver.wait(DT);
+                    elapsed += DT;
+                }
+
+                self.driver.set_inlet(false);
+                self.driver.set_outlet(false);
+
+                reached(self.driver.level())
+            }
+
+            /// Muda de estado se a água chegou ao nível, senão devolve o aquecedor no estado atual.
+            fn transition<T>(
+                mut self,
+                inlet: bool,
+                reached: impl Fn(f32) -> bool,
+            ) -> Result<Heater<T, D>, Self> {
+                if self.move_water(inlet, reached) {
+                    Ok(self.into_state())
+                } else {
+                    Err(self)
+                }
+            }
         }
 
-        impl Heater<Medium> {
-            pub fn drain(self) -> Heater<Low> {
+        impl<D: HeaterDriver> Heater<Low, D> {
+            /// O tanque deve começar vazio, com a resistência desligada.
+            pub fn new(mut driver: D) -> Self {
+                driver.set_power(0.0);
+                driver.set_inlet(false);
+                driver.set_outlet(false);
+
                 Heater {
+                    driver,
                     _marker: PhantomData,
                 }
             }
 
-            pub fn fill(self) -> Heater<High> {
-                Heater {
-                    _marker: PhantomData,
+            pub fn add_water(self) -> Result<Heater<Medium, D>, Heater<Low, D>> {
+                self.transition(true, |level| level >= MEDIUM_LEVEL)
+            }
+        }
+
+        impl<D: HeaterDriver> Heater<Medium, D> {
+            pub fn drain(self) -> Result<Heater<Low, D>, Heater<Medium, D>> {
+                // Esvazia totalmente, assim como o aquecedor é entregue
+                self.transition(false, |level| level <= 0.0)
+            }
+
+            pub fn fill(self) -> Result<Heater<High, D>, Heater<Medium, D>> {
+                self.transition(true, |level| level >= HIGH_LEVEL)
+            }
+        }
+
+        impl<D: HeaterDriver> Heater<High, D> {
+            pub fn remove_water(self) -> Result<Heater<Medium, D>, Heater<High, D>> {
+                self.transition(false, |level| level < HIGH_LEVEL)
+            }
+        }
+
+        impl<S: CanHeat, D: HeaterDriver> Heater<S, D> {
+            /// Um passo do laço de controle proporcional. Retorna a potência aplicada.
+            pub fn control_step(&mut self, setpoint: f32) -> Result<f32, HeaterError> {
+                // O typestate garante o nível na transição, mas um vazamento ainda pode ocorrer
+                if self.driver.level() < MEDIUM_LEVEL {
+                    self.driver.set_power(0.0);
+                    return Err(HeaterError::DryFire);
                 }
+
+                let power = (KP * (setpoint - self.driver.temperature())).clamp(0.0, 1.0);
+                self.driver.set_power(power);
+                self.driver.wait(DT);
+
+                Ok(power)
             }
         }
 
-        impl Heater<High> {
-            pub fn remove_water(self) -> Heater<Medium> {
-                Heater {
-                    _marker: PhantomData,
+        /// Laço de controle: mantém a água em `setpoint` (°C) durante `duration` segundos.
+        ///
+        /// Não existe `run_heater` para `Heater<Low, _>`, pois `Low` não implementa `CanHeat`:
+        /// ligar a resistência sem água é um erro de compilação.
+        fn run_heater<S: CanHeat, D: HeaterDriver>(
+            h: &mut Heater<S, D>,
+            setpoint: f32,
+            duration: f32,
+        ) -> Result<(), HeaterError> {
+            let mut elapsed = 0.0;
+
+            while elapsed < duration {
+                if let Err(err) = h.control_step(setpoint) {
+                    h.driver.set_power(0.0);
+                    return Err(err);
                 }
+                elapsed += DT;
             }
+
+            h.driver.set_power(0.0);
+            Ok(())
         }
 
-        fn run_heater<S>(h: &mut Heater<S>) {
-            todo!()
+        /// Ciclo de enchimento completo: `Low` -> `Medium` -> `High`.
+        fn refill_heater<D: HeaterDriver>(
+            h: Heater<Low, D>,
+        ) -> Result<Heater<High, D>, HeaterError> {
+            h.add_water()
+                .map_err(|_| HeaterError::Timeout)?
+                .fill()
+                .map_err(|_| HeaterError::Timeout)
         }
 
-        fn refill_heater(h: &mut Heater<Low>) {
-            todo!()
+        mod driver {
+            /// Interface entre o controle do aquecedor e o hardware (ou a simulação).
+            pub trait HeaterDriver {
+                /// Nível de água como fração do tanque (0.0 a 1.0).
+                fn level(&self) -> f32;
+                /// Temperatura da água em °C.
+                fn temperature(&self) -> f32;
+                fn set_inlet(&mut self, open: bool);
+                fn set_outlet(&mut self, open: bool);
+                /// Potência da resistência como fração da potência máxima (0.0 a 1.0).
+                fn set_power(&mut self, power: f32);
+                /// Aguarda `dt` segundos. Na simulação é aqui que o tempo avança.
+                fn wait(&mut self, dt: f32);
+            }
+
+            const WATER_HEAT_CAPACITY: f32 = 4186.0; // J / (kg °C)
+
+            /// Tanque simulado com válvulas de entrada e saída e uma resistência.
+            pub struct SimulatedPlant {
+                pub capacity: f32,  // L
+                pub flow: f32,      // L/s
+                pub max_power: f32, // W
+                pub ambient: f32,   // °C
+                // Coeficiente de perda de calor para o ambiente (1/s)
+                pub loss: f32,
+                // Vazamento (L/s), usado para testar a proteção contra aquecimento a seco
+                pub leak: f32,
+                // Nível abaixo do qual a resistência fica descoberta
+                pub element_level: f32,
+                volume: f32,
+                temperature: f32,
+                inlet: bool,
+                outlet: bool,
+                power: f32,
+                dry_fired: bool,
+            }
+
+            impl Default for SimulatedPlant {
+                fn default() -> Self {
+                    Self {
+                        capacity: 10.0,
+                        flow: 0.25,
+                        max_power: 2000.0,
+                        ambient: 25.0,
+                        loss: 0.001,
+                        leak: 0.0,
+                        element_level: 0.3,
+                        volume: 0.0,
+                        temperature: 25.0,
+                        inlet: false,
+                        outlet: false,
+                        power: 0.0,
+                        dry_fired: false,
+                    }
+                }
+            }
+
+            impl SimulatedPlant {
+                pub fn power(&self) -> f32 {
+                    self.power
+                }
+
+                /// Indica se em algum momento a resistência foi ligada descoberta.
+                pub fn dry_fired(&self) -> bool {
+                    self.dry_fired
+                }
+
+                fn step(&mut self, dt: f32) {
+                    if self.inlet {
+                        let added = (self.flow * dt).min(self.capacity - self.volume);
+                        // A água que entra está na temperatura ambiente
+                        if self.volume + added > 0.0 {
+                            self.temperature = (self.temperature * self.volume
+                                + self.ambient * added)
+                                / (self.volume + added);
+                        }
+                        self.volume += added;
+                    }
+
+                    let outflow = if self.outlet { self.flow } else { 0.0 } + self.leak;
+                    self.volume = (self.volume - outflow * dt).max(0.0);
+
+                    if self.power > 0.0 {
+                        if self.level() < self.element_level {
+                            self.dry_fired = true;
+                        } else {
+                            let energy = self.power * self.max_power * dt;
+                            self.temperature += energy / (self.volume * WATER_HEAT_CAPACITY);
+                        }
+                    }
+
+                    self.temperature -= self.loss * (self.temperature - self.ambient) * dt;
+                }
+            }
+
+            impl HeaterDriver for SimulatedPlant {
+                fn level(&self) -> f32 {
+                    self.volume / self.capacity
+                }
+
+                fn temperature(&self) -> f32 {
+                    self.temperature
+                }
+
+                fn set_inlet(&mut self, open: bool) {
+                    self.inlet = open;
+                }
+
+                fn set_outlet(&mut self, open: bool) {
+                    self.outlet = open;
+                }
+
+                fn set_power(&mut self, power: f32) {
+                    self.power = power.clamp(0.0, 1.0);
+                }
+
+                fn wait(&mut self, dt: f32) {
+                    self.step(dt);
+                }
+            }
         }
 
-        /// TODO: Após Traits
-        mod driver {}
+        mod tests {
+            use super::driver::{HeaterDriver, SimulatedPlant};
+            use super::*;
+
+            #[test]
+            fn test_fill_and_drain_cycle() {
+                let heater = Heater::new(SimulatedPlant::default());
+                assert_eq!(heater.driver().level(), 0.0);
+
+                let heater = heater.add_water().ok().unwrap();
+                assert!(heater.driver().level() >= MEDIUM_LEVEL);
+
+                let heater = heater.fill().ok().unwrap();
+                assert!(heater.driver().level() >= HIGH_LEVEL);
+
+                let heater = heater.remove_water().ok().unwrap();
+                let level = heater.driver().level();
+                assert!((MEDIUM_LEVEL..HIGH_LEVEL).contains(&level));
+
+                let heater = heater.drain().ok().unwrap();
+                assert_eq!(heater.driver().level(), 0.0);
+                assert!(!heater.driver().dry_fired());
+            }
+
+            #[test]
+            fn test_refill_heater() {
+                let heater = refill_heater(Heater::new(SimulatedPlant::default())).unwrap();
+
+                assert!(heater.driver().level() >= HIGH_LEVEL);
+            }
+
+            #[test]
+            fn test_fill_timeout_keeps_state() {
+                // Sem vazão a água nunca chega ao nível, o aquecedor volta em `Low`
+                let mut plant = SimulatedPlant::default();
+                plant.flow = 0.0;
+
+                let heater: Heater<Low, _> = Heater::new(plant).add_water().err().unwrap();
+                assert_eq!(heater.driver().level(), 0.0);
+            }
+
+            #[test]
+            fn test_run_heater_reaches_setpoint() {
+                let mut heater = Heater::new(SimulatedPlant::default())
+                    .add_water()
+                    .ok()
+                    .unwrap();
+
+                assert_eq!(run_heater(&mut heater, 60.0, 1200.0), Ok(()));
+
+                let temperature = heater.driver().temperature();
+                assert!((temperature - 60.0).abs() < 2.0, "{temperature}");
+                assert_eq!(heater.driver().power(), 0.0);
+                assert!(!heater.driver().dry_fired());
+            }
+
+            #[test]
+            fn test_dry_fire_protection_on_leak() {
+                let mut heater = Heater::new(SimulatedPlant::default())
+                    .add_water()
+                    .ok()
+                    .unwrap();
+                heater.driver.leak = 0.01;
+
+                assert_eq!(
+                    run_heater(&mut heater, 90.0, 3600.0),
+                    Err(HeaterError::DryFire)
+                );
+                assert_eq!(heater.driver().power(), 0.0);
+                assert!(!heater.driver().dry_fired());
+            }
+
+            #[test]
+            fn test_plant_detects_dry_fire() {
+                let mut plant = SimulatedPlant::default();
+
+                plant.set_power(1.0);
+                plant.wait(1.0);
+
+                assert!(plant.dry_fired());
+            }
+        }
     }
 
     /// TODO: Após Traits
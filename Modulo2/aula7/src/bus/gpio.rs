@@ -0,0 +1,132 @@
+use super::mock::Script;
+use super::BusError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PinState {
+    Low,
+    High,
+}
+
+impl From<bool> for PinState {
+    fn from(value: bool) -> Self {
+        if value {
+            PinState::High
+        } else {
+            PinState::Low
+        }
+    }
+}
+
+pub trait OutputPin {
+    fn set_state(&mut self, state: PinState) -> Result<(), BusError>;
+
+    fn set_high(&mut self) -> Result<(), BusError> {
+        self.set_state(PinState::High)
+    }
+
+    fn set_low(&mut self) -> Result<(), BusError> {
+        self.set_state(PinState::Low)
+    }
+}
+
+pub trait InputPin {
+    fn state(&mut self) -> Result<PinState, BusError>;
+
+    fn is_high(&mut self) -> Result<bool, BusError> {
+        Ok(self.state()? == PinState::High)
+    }
+
+    fn is_low(&mut self) -> Result<bool, BusError> {
+        Ok(self.state()? == PinState::Low)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PinTransaction {
+    Set(PinState),
+    /// Estado que o mock devolve na próxima leitura.
+    Get(PinState),
+}
+
+/// Mock de um pino, que pode ser usado tanto como entrada quanto como saída.
+#[derive(Debug, Clone)]
+pub struct PinMock {
+    script: Script<PinTransaction>,
+}
+
+impl PinMock {
+    pub fn new(expected: &[PinTransaction]) -> Self {
+        Self {
+            script: Script::new("GPIO", expected),
+        }
+    }
+
+    pub fn done(&self) {
+        self.script.done();
+    }
+}
+
+impl OutputPin for PinMock {
+    fn set_state(&mut self, state: PinState) -> Result<(), BusError> {
+        match self.script.next("set") {
+            PinTransaction::Set(expected) if expected == state => Ok(()),
+            other => self.script.mismatch(&other, PinTransaction::Set(state)),
+        }
+    }
+}
+
+impl InputPin for PinMock {
+    fn state(&mut self) -> Result<PinState, BusError> {
+        match self.script.next("get") {
+            PinTransaction::Get(state) => Ok(state),
+            other => self.script.mismatch(&other, "Get"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Acende o LED enquanto o botão (ativo em nível baixo) estiver pressionado.
+    fn follow_button(button: &mut impl InputPin, led: &mut impl OutputPin) -> Result<(), BusError> {
+        let pressed = button.is_low()?;
+        led.set_state(pressed.into())
+    }
+
+    #[test]
+    fn test_button_and_led() {
+        let mut button = PinMock::new(&[
+            PinTransaction::Get(PinState::Low),
+            PinTransaction::Get(PinState::High),
+        ]);
+        let mut led = PinMock::new(&[
+            PinTransaction::Set(PinState::High),
+            PinTransaction::Set(PinState::Low),
+        ]);
+
+        follow_button(&mut button, &mut led).unwrap();
+        follow_button(&mut button, &mut led).unwrap();
+
+        button.done();
+        led.done();
+    }
+
+    #[test]
+    fn test_clone_shares_script() {
+        let led = PinMock::new(&[PinTransaction::Set(PinState::High)]);
+        let mut driver_led = led.clone();
+
+        driver_led.set_high().unwrap();
+
+        led.done();
+    }
+
+    #[test]
+    #[should_panic(expected = "GPIO: expected Set(High), got Set(Low)")]
+    fn test_wrong_state_panics() {
+        let mut led = PinMock::new(&[PinTransaction::Set(PinState::High)]);
+
+        let _ = led.set_low();
+    }
+}
@@ -0,0 +1,406 @@
+//! Implementações dos traits do `embedded-hal` 1.0 (e do `embedded-hal-async`) para os mocks.
+//!
+//! Os drivers de verdade, como o `Lis2dh`, são escritos sobre o `embedded-hal`. Com estas
+//! implementações o mesmo `SpiMock` que roda os exemplos da aula também pode ser passado para
+//! esses drivers nos testes no host. Cada operação do `embedded-hal` é traduzida para as
+//! transações do roteiro do mock:
+//!
+//! | `embedded-hal`                   | Roteiro                                          |
+//! |----------------------------------|--------------------------------------------------|
+//! | `SpiDevice::transaction`         | `Select`, uma transação por operação, `Deselect` |
+//! | `spi::Operation::Transfer*`      | `SpiTransaction::Transfer`                       |
+//! | `spi::Operation::DelayNs`        | nada, o mock não modela tempo                    |
+//! | `i2c::Operation::Write` + `Read` | `I2cTransaction::WriteRead`                      |
+//! | `InputPin::is_high`/`is_low`     | `PinTransaction::Get`                            |
+
+use super::gpio::{InputPin, OutputPin, PinMock, PinState};
+use super::i2c::{I2c, I2cMock};
+use super::spi::{Spi, SpiMock};
+use super::BusError;
+use embedded_hal::{digital, i2c, spi};
+
+impl spi::Error for BusError {
+    fn kind(&self) -> spi::ErrorKind {
+        match self {
+            BusError::Framing => spi::ErrorKind::FrameFormat,
+            _ => spi::ErrorKind::Other,
+        }
+    }
+}
+
+impl i2c::Error for BusError {
+    fn kind(&self) -> i2c::ErrorKind {
+        match self {
+            BusError::Nack { .. } => {
+                i2c::ErrorKind::NoAcknowledge(i2c::NoAcknowledgeSource::Address)
+            }
+            _ => i2c::ErrorKind::Other,
+        }
+    }
+}
+
+impl digital::Error for BusError {
+    fn kind(&self) -> digital::ErrorKind {
+        digital::ErrorKind::Other
+    }
+}
+
+impl From<digital::PinState> for PinState {
+    fn from(value: digital::PinState) -> Self {
+        match value {
+            digital::PinState::Low => PinState::Low,
+            digital::PinState::High => PinState::High,
+        }
+    }
+}
+
+impl From<PinState> for digital::PinState {
+    fn from(value: PinState) -> Self {
+        match value {
+            PinState::Low => digital::PinState::Low,
+            PinState::High => digital::PinState::High,
+        }
+    }
+}
+
+mod blocking {
+    use super::*;
+
+    impl spi::ErrorType for SpiMock {
+        type Error = BusError;
+    }
+
+    impl spi::SpiDevice for SpiMock {
+        fn transaction(
+            &mut self,
+            operations: &mut [spi::Operation<'_, u8>],
+        ) -> Result<(), BusError> {
+            Spi::transaction(self, |spi| {
+                for operation in operations.iter_mut() {
+                    match operation {
+                        spi::Operation::Read(buf) => Spi::read(spi, buf)?,
+                        spi::Operation::Write(data) => Spi::write(spi, data)?,
+                        spi::Operation::TransferInPlace(data) => Spi::transfer(spi, data)?,
+                        spi::Operation::Transfer(read, write) => {
+                            // O barramento transfere o maior dos dois tamanhos: o que falta de
+                            // `write` é completado com zeros e o excesso lido é descartado.
+                            let mut buf = vec![0; read.len().max(write.len())];
+                            buf[..write.len()].copy_from_slice(write);
+                            Spi::transfer(spi, &mut buf)?;
+                            read.copy_from_slice(&buf[..read.len()]);
+                        }
+                        spi::Operation::DelayNs(_) => {}
+                    }
+                }
+                Ok(())
+            })
+        }
+    }
+
+    impl i2c::ErrorType for I2cMock {
+        type Error = BusError;
+    }
+
+    impl i2c::I2c for I2cMock {
+        fn read(&mut self, address: u8, read: &mut [u8]) -> Result<(), BusError> {
+            I2c::read(self, address, read)
+        }
+
+        fn write(&mut self, address: u8, write: &[u8]) -> Result<(), BusError> {
+            I2c::write(self, address, write)
+        }
+
+        fn write_read(
+            &mut self,
+            address: u8,
+            write: &[u8],
+            read: &mut [u8],
+        ) -> Result<(), BusError> {
+            I2c::write_read(self, address, write, read)
+        }
+
+        /// Uma escrita seguida de uma leitura vira um `WriteRead`, que é como os drivers leem
+        /// registradores. As demais operações são conferidas uma a uma.
+        fn transaction(
+            &mut self,
+            address: u8,
+            operations: &mut [i2c::Operation<'_>],
+        ) -> Result<(), BusError> {
+            let mut operations = operations.iter_mut().peekable();
+
+            while let Some(operation) = operations.next() {
+                match operation {
+                    i2c::Operation::Write(write) => match operations.peek_mut() {
+                        Some(i2c::Operation::Read(read)) => {
+                            I2c::write_read(self, address, write, read)?;
+                            operations.next();
+                        }
+                        _ => I2c::write(self, address, write)?,
+                    },
+                    i2c::Operation::Read(read) => I2c::read(self, address, read)?,
+                }
+            }
+
+            Ok(())
+        }
+    }
+
+    impl digital::ErrorType for PinMock {
+        type Error = BusError;
+    }
+
+    impl digital::OutputPin for PinMock {
+        fn set_low(&mut self) -> Result<(), BusError> {
+            OutputPin::set_low(self)
+        }
+
+        fn set_high(&mut self) -> Result<(), BusError> {
+            OutputPin::set_high(self)
+        }
+    }
+
+    impl digital::InputPin for PinMock {
+        fn is_high(&mut self) -> Result<bool, BusError> {
+            InputPin::is_high(self)
+        }
+
+        fn is_low(&mut self) -> Result<bool, BusError> {
+            InputPin::is_low(self)
+        }
+    }
+}
+
+/// Os mocks respondem na hora, então as versões assíncronas apenas chamam as bloqueantes e os
+/// futures ficam prontos no primeiro `poll`.
+mod asynch {
+    use super::*;
+    use embedded_hal_async as hal_async;
+
+    impl hal_async::spi::SpiDevice for SpiMock {
+        async fn transaction(
+            &mut self,
+            operations: &mut [spi::Operation<'_, u8>],
+        ) -> Result<(), BusError> {
+            spi::SpiDevice::transaction(self, operations)
+        }
+    }
+
+    impl hal_async::i2c::I2c for I2cMock {
+        async fn read(&mut self, address: u8, read: &mut [u8]) -> Result<(), BusError> {
+            I2c::read(self, address, read)
+        }
+
+        async fn write(&mut self, address: u8, write: &[u8]) -> Result<(), BusError> {
+            I2c::write(self, address, write)
+        }
+
+        async fn write_read(
+            &mut self,
+            address: u8,
+            write: &[u8],
+            read: &mut [u8],
+        ) -> Result<(), BusError> {
+            I2c::write_read(self, address, write, read)
+        }
+
+        async fn transaction(
+            &mut self,
+            address: u8,
+            operations: &mut [i2c::Operation<'_>],
+        ) -> Result<(), BusError> {
+            i2c::I2c::transaction(self, address, operations)
+        }
+    }
+
+    /// Cada verificação do nível consome um `PinTransaction::Get`, então o roteiro descreve
+    /// quantas leituras acontecem até a condição ser satisfeita.
+    impl hal_async::digital::Wait for PinMock {
+        async fn wait_for_high(&mut self) -> Result<(), BusError> {
+            while InputPin::state(self)? != PinState::High {}
+            Ok(())
+        }
+
+        async fn wait_for_low(&mut self) -> Result<(), BusError> {
+            while InputPin::state(self)? != PinState::Low {}
+            Ok(())
+        }
+
+        async fn wait_for_rising_edge(&mut self) -> Result<(), BusError> {
+            self.wait_for_low().await?;
+            self.wait_for_high().await
+        }
+
+        async fn wait_for_falling_edge(&mut self) -> Result<(), BusError> {
+            self.wait_for_high().await?;
+            self.wait_for_low().await
+        }
+
+        async fn wait_for_any_edge(&mut self) -> Result<(), BusError> {
+            match InputPin::state(self)? {
+                PinState::Low => self.wait_for_high().await,
+                PinState::High => self.wait_for_low().await,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bus::mock::{I2cTransaction, PinTransaction, SpiTransaction};
+    use std::future::Future;
+    use std::pin::pin;
+    use std::task::{Context, Poll, Waker};
+
+    /// Executor mínimo: os futures dos mocks ficam prontos no primeiro `poll`.
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut cx = Context::from_waker(Waker::noop());
+        match pin!(future).poll(&mut cx) {
+            Poll::Ready(output) => output,
+            Poll::Pending => panic!("mock future should be ready"),
+        }
+    }
+
+    /// Leitura de registradores como a feita por um driver genérico sobre o `embedded-hal`.
+    fn read_registers<S: spi::SpiDevice>(
+        spi: &mut S,
+        register: u8,
+        buf: &mut [u8],
+    ) -> Result<(), S::Error> {
+        spi.transaction(&mut [
+            spi::Operation::Write(&[register | 0xC0]),
+            spi::Operation::Read(buf),
+        ])
+    }
+
+    async fn who_am_i<I: embedded_hal_async::i2c::I2c>(i2c: &mut I) -> Result<u8, I::Error> {
+        let mut buf = [0];
+        i2c.write_read(0x19, &[0x0F], &mut buf).await?;
+        Ok(buf[0])
+    }
+
+    #[test]
+    fn test_spi_device() {
+        let mut spi = SpiMock::new(&[
+            SpiTransaction::Select,
+            SpiTransaction::Write(vec![0xE8]),
+            SpiTransaction::Read(vec![0x10, 0x20]),
+            SpiTransaction::Deselect,
+            SpiTransaction::Select,
+            SpiTransaction::Transfer {
+                write: vec![0x8F, 0x00, 0x00],
+                read: vec![0x00, 0x33, 0xFF],
+            },
+            SpiTransaction::Deselect,
+        ]);
+        let mut buf = [0; 2];
+
+        read_registers(&mut spi, 0x28, &mut buf).unwrap();
+        assert_eq!(buf, [0x10, 0x20]);
+
+        let mut read = [0; 2];
+        spi::SpiDevice::transaction(
+            &mut spi,
+            &mut [
+                spi::Operation::Transfer(&mut read, &[0x8F, 0x00, 0x00]),
+                spi::Operation::DelayNs(100),
+            ],
+        )
+        .unwrap();
+        assert_eq!(read, [0x00, 0x33]);
+
+        spi.done();
+    }
+
+    #[test]
+    fn test_i2c_transaction_groups_write_read() {
+        let mut i2c = I2cMock::new(&[
+            I2cTransaction::WriteRead {
+                address: 0x19,
+                write: vec![0xA8],
+                read: vec![1, 2, 3],
+            },
+            I2cTransaction::Write {
+                address: 0x19,
+                data: vec![0x20, 0x57],
+            },
+            I2cTransaction::Nack { address: 0x19 },
+        ]);
+        let mut buf = [0; 3];
+
+        i2c::I2c::transaction(
+            &mut i2c,
+            0x19,
+            &mut [
+                i2c::Operation::Write(&[0xA8]),
+                i2c::Operation::Read(&mut buf),
+            ],
+        )
+        .unwrap();
+        assert_eq!(buf, [1, 2, 3]);
+
+        i2c::I2c::write(&mut i2c, 0x19, &[0x20, 0x57]).unwrap();
+
+        let err = i2c::I2c::read(&mut i2c, 0x19, &mut buf).unwrap_err();
+        assert_eq!(
+            i2c::Error::kind(&err),
+            i2c::ErrorKind::NoAcknowledge(i2c::NoAcknowledgeSource::Address)
+        );
+
+        i2c.done();
+    }
+
+    #[test]
+    fn test_pins() {
+        let mut pin = PinMock::new(&[
+            PinTransaction::Set(PinState::High),
+            PinTransaction::Get(PinState::Low),
+        ]);
+
+        digital::OutputPin::set_state(&mut pin, digital::PinState::High).unwrap();
+        assert!(digital::InputPin::is_low(&mut pin).unwrap());
+
+        pin.done();
+    }
+
+    #[test]
+    fn test_async_spi_and_i2c() {
+        let mut spi = SpiMock::new(&[
+            SpiTransaction::Select,
+            SpiTransaction::Write(vec![0x20, 0x57]),
+            SpiTransaction::Deselect,
+        ]);
+        let mut i2c = I2cMock::new(&[I2cTransaction::WriteRead {
+            address: 0x19,
+            write: vec![0x0F],
+            read: vec![0x33],
+        }]);
+
+        block_on(embedded_hal_async::spi::SpiDevice::write(
+            &mut spi,
+            &[0x20, 0x57],
+        ))
+        .unwrap();
+        assert_eq!(block_on(who_am_i(&mut i2c)), Ok(0x33));
+
+        spi.done();
+        i2c.done();
+    }
+
+    #[test]
+    fn test_async_wait_for_edge() {
+        use embedded_hal_async::digital::Wait;
+
+        let mut pin = PinMock::new(&[
+            PinTransaction::Get(PinState::High),
+            PinTransaction::Get(PinState::High),
+            PinTransaction::Get(PinState::Low),
+            PinTransaction::Get(PinState::Low),
+            PinTransaction::Get(PinState::High),
+        ]);
+
+        block_on(pin.wait_for_rising_edge()).unwrap();
+
+        pin.done();
+    }
+}
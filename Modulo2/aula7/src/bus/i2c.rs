@@ -0,0 +1,162 @@
+use super::mock::Script;
+use super::BusError;
+
+/// Barramento I2C, com endereços de 7 bits.
+pub trait I2c {
+    fn write(&mut self, address: u8, data: &[u8]) -> Result<(), BusError>;
+    fn read(&mut self, address: u8, buf: &mut [u8]) -> Result<(), BusError>;
+    /// Escrita seguida de leitura com repeated start, usada para ler registradores.
+    fn write_read(&mut self, address: u8, data: &[u8], buf: &mut [u8]) -> Result<(), BusError>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum I2cTransaction {
+    Write {
+        address: u8,
+        data: Vec<u8>,
+    },
+    /// `data` são os bytes que o mock devolve.
+    Read {
+        address: u8,
+        data: Vec<u8>,
+    },
+    WriteRead {
+        address: u8,
+        write: Vec<u8>,
+        read: Vec<u8>,
+    },
+    /// A próxima operação no endereço falha com [`BusError::Nack`].
+    Nack {
+        address: u8,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct I2cMock {
+    script: Script<I2cTransaction>,
+}
+
+impl I2cMock {
+    pub fn new(expected: &[I2cTransaction]) -> Self {
+        Self {
+            script: Script::new("I2C", expected),
+        }
+    }
+
+    pub fn done(&self) {
+        self.script.done();
+    }
+}
+
+impl I2c for I2cMock {
+    fn write(&mut self, address: u8, data: &[u8]) -> Result<(), BusError> {
+        match self.script.next("write") {
+            I2cTransaction::Write {
+                address: a,
+                data: d,
+            } if a == address && d == data => Ok(()),
+            I2cTransaction::Nack { address: a } if a == address => Err(BusError::Nack { address }),
+            other => self.script.mismatch(
+                &other,
+                I2cTransaction::Write {
+                    address,
+                    data: data.to_vec(),
+                },
+            ),
+        }
+    }
+
+    fn read(&mut self, address: u8, buf: &mut [u8]) -> Result<(), BusError> {
+        match self.script.next("read") {
+            I2cTransaction::Read { address: a, data }
+                if a == address && data.len() == buf.len() =>
+            {
+                buf.copy_from_slice(&data);
+                Ok(())
+            }
+            I2cTransaction::Nack { address: a } if a == address => Err(BusError::Nack { address }),
+            other => self.script.mismatch(
+                &other,
+                format!("Read {{ address: {}, {} bytes }}", address, buf.len()),
+            ),
+        }
+    }
+
+    fn write_read(&mut self, address: u8, data: &[u8], buf: &mut [u8]) -> Result<(), BusError> {
+        match self.script.next("write_read") {
+            I2cTransaction::WriteRead {
+                address: a,
+                write,
+                read,
+            } if a == address && write == data && read.len() == buf.len() => {
+                buf.copy_from_slice(&read);
+                Ok(())
+            }
+            I2cTransaction::Nack { address: a } if a == address => Err(BusError::Nack { address }),
+            other => self.script.mismatch(
+                &other,
+                format!(
+                    "WriteRead {{ address: {}, write: {:?}, {} bytes }}",
+                    address,
+                    data,
+                    buf.len()
+                ),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADDRESS: u8 = 0x19;
+
+    #[test]
+    fn test_register_read() {
+        let mut i2c = I2cMock::new(&[
+            I2cTransaction::Write {
+                address: ADDRESS,
+                data: vec![0x20, 0x57],
+            },
+            I2cTransaction::WriteRead {
+                address: ADDRESS,
+                write: vec![0x0F],
+                read: vec![0x33],
+            },
+            I2cTransaction::Read {
+                address: ADDRESS,
+                data: vec![1, 2],
+            },
+        ]);
+        let mut who_am_i = [0];
+        let mut buf = [0; 2];
+
+        i2c.write(ADDRESS, &[0x20, 0x57]).unwrap();
+        i2c.write_read(ADDRESS, &[0x0F], &mut who_am_i).unwrap();
+        i2c.read(ADDRESS, &mut buf).unwrap();
+
+        assert_eq!(who_am_i, [0x33]);
+        assert_eq!(buf, [1, 2]);
+        i2c.done();
+    }
+
+    #[test]
+    fn test_nack() {
+        let mut i2c = I2cMock::new(&[I2cTransaction::Nack { address: ADDRESS }]);
+
+        assert_eq!(
+            i2c.write(ADDRESS, &[0x00]),
+            Err(BusError::Nack { address: ADDRESS })
+        );
+        i2c.done();
+    }
+
+    #[test]
+    #[should_panic(expected = "I2C: unexpected read, no transaction left")]
+    fn test_no_transaction_left() {
+        let mut i2c = I2cMock::new(&[]);
+
+        let _ = i2c.read(ADDRESS, &mut [0]);
+    }
+}
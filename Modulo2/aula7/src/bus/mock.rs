@@ -0,0 +1,73 @@
+//! Mocks programáveis dos barramentos.
+//!
+//! Cada mock recebe a lista de transações esperadas, na ordem. Qualquer operação diferente da
+//! próxima esperada gera um `panic!` com a diferença, e ao final do teste `done()` confere se
+//! todas foram consumidas. O mock pode ser clonado: os clones compartilham o mesmo roteiro, então
+//! o teste mantém uma cópia para chamar `done()` depois de mover a outra para o driver.
+
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+
+pub use super::gpio::{PinMock, PinTransaction};
+pub use super::i2c::{I2cMock, I2cTransaction};
+pub use super::spi::{SpiMock, SpiTransaction};
+pub use super::uart::{UartMock, UartTransaction};
+
+#[derive(Debug)]
+pub(crate) struct Script<T: Debug> {
+    name: &'static str,
+    expected: Arc<Mutex<VecDeque<T>>>,
+}
+
+impl<T: Debug> Clone for Script<T> {
+    fn clone(&self) -> Self {
+        Self {
+            name: self.name,
+            expected: self.expected.clone(),
+        }
+    }
+}
+
+impl<T: Debug> Script<T> {
+    pub(crate) fn new(name: &'static str, expected: &[T]) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            name,
+            expected: Arc::new(Mutex::new(expected.iter().cloned().collect())),
+        }
+    }
+
+    /// Retira a próxima transação esperada, falhando se o roteiro já acabou.
+    pub(crate) fn next(&self, operation: &str) -> T {
+        self.expected
+            .lock()
+            .unwrap()
+            .pop_front()
+            .unwrap_or_else(|| {
+                panic!(
+                    "{}: unexpected {}, no transaction left",
+                    self.name, operation
+                )
+            })
+    }
+
+    pub(crate) fn mismatch(&self, expected: &T, operation: impl Debug) -> ! {
+        panic!(
+            "{}: expected {:?}, got {:?}",
+            self.name, expected, operation
+        )
+    }
+
+    pub(crate) fn done(&self) {
+        let expected = self.expected.lock().unwrap();
+        assert!(
+            expected.is_empty(),
+            "{}: transactions not performed: {:?}",
+            self.name,
+            expected
+        );
+    }
+}
@@ -0,0 +1,41 @@
+//! Abstração de barramentos de comunicação (SPI, I2C, UART e GPIO).
+//!
+//! É a evolução do par `Interface`/`Driver` de `struct_context_with_traits`: cada barramento tem
+//! o seu trait, com as operações que fazem sentido para ele, e um mock programável (módulo
+//! [`mock`]) que confere as transações esperadas. Assim drivers como o `Lis2dh` podem ser
+//! escritos genéricos sobre o trait e testados no Linux.
+
+pub mod gpio;
+pub mod hal;
+pub mod i2c;
+pub mod mock;
+pub mod spi;
+pub mod uart;
+
+use std::fmt::{Display, Formatter};
+
+/// Erros comuns a todos os barramentos.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BusError {
+    /// O dispositivo não respondeu ao endereço (I2C).
+    Nack { address: u8 },
+    /// Quadro recebido com erro de paridade, stop bit etc. (UART).
+    Framing,
+    /// Tempo de espera esgotado.
+    Timeout,
+    /// Operação incompatível com a configuração atual do barramento.
+    InvalidConfig,
+}
+
+impl Display for BusError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BusError::Nack { address } => write!(f, "No acknowledge from 0x{:02X}", address),
+            BusError::Framing => write!(f, "Framing error"),
+            BusError::Timeout => write!(f, "Timeout"),
+            BusError::InvalidConfig => write!(f, "Invalid bus configuration"),
+        }
+    }
+}
+
+impl std::error::Error for BusError {}
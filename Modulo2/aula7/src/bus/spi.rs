@@ -0,0 +1,169 @@
+use super::mock::Script;
+use super::BusError;
+
+/// Barramento SPI visto por um único dispositivo, com o seu chip select.
+pub trait Spi {
+    /// Ativa o chip select do dispositivo.
+    fn select(&mut self) -> Result<(), BusError>;
+    fn deselect(&mut self) -> Result<(), BusError>;
+    fn write(&mut self, data: &[u8]) -> Result<(), BusError>;
+    fn read(&mut self, buf: &mut [u8]) -> Result<(), BusError>;
+    /// Transferência full-duplex: envia `data` e o substitui pelos bytes recebidos.
+    fn transfer(&mut self, data: &mut [u8]) -> Result<(), BusError>;
+
+    /// Executa `f` com o chip select ativo, liberando-o mesmo se `f` falhar.
+    fn transaction<R>(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> Result<R, BusError>,
+    ) -> Result<R, BusError>
+    where
+        Self: Sized,
+    {
+        self.select()?;
+        let result = f(self);
+        self.deselect()?;
+        result
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpiTransaction {
+    Select,
+    Deselect,
+    Write(Vec<u8>),
+    /// Bytes que o mock devolve em um `read`.
+    Read(Vec<u8>),
+    Transfer {
+        write: Vec<u8>,
+        read: Vec<u8>,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct SpiMock {
+    script: Script<SpiTransaction>,
+}
+
+impl SpiMock {
+    pub fn new(expected: &[SpiTransaction]) -> Self {
+        Self {
+            script: Script::new("SPI", expected),
+        }
+    }
+
+    pub fn done(&self) {
+        self.script.done();
+    }
+}
+
+impl Spi for SpiMock {
+    fn select(&mut self) -> Result<(), BusError> {
+        match self.script.next("select") {
+            SpiTransaction::Select => Ok(()),
+            other => self.script.mismatch(&other, SpiTransaction::Select),
+        }
+    }
+
+    fn deselect(&mut self) -> Result<(), BusError> {
+        match self.script.next("deselect") {
+            SpiTransaction::Deselect => Ok(()),
+            other => self.script.mismatch(&other, SpiTransaction::Deselect),
+        }
+    }
+
+    fn write(&mut self, data: &[u8]) -> Result<(), BusError> {
+        match self.script.next("write") {
+            SpiTransaction::Write(expected) if expected == data => Ok(()),
+            other => self
+                .script
+                .mismatch(&other, SpiTransaction::Write(data.to_vec())),
+        }
+    }
+
+    fn read(&mut self, buf: &mut [u8]) -> Result<(), BusError> {
+        match self.script.next("read") {
+            SpiTransaction::Read(response) if response.len() == buf.len() => {
+                buf.copy_from_slice(&response);
+                Ok(())
+            }
+            other => self
+                .script
+                .mismatch(&other, format!("Read({} bytes)", buf.len())),
+        }
+    }
+
+    fn transfer(&mut self, data: &mut [u8]) -> Result<(), BusError> {
+        match self.script.next("transfer") {
+            SpiTransaction::Transfer { write, read }
+                if write == data && read.len() == data.len() =>
+            {
+                data.copy_from_slice(&read);
+                Ok(())
+            }
+            other => self
+                .script
+                .mismatch(&other, format!("Transfer {{ write: {:?}, .. }}", data)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Leitura de registrador no formato do LIS2DH: bit 7 do endereço indica leitura.
+    fn read_register(spi: &mut impl Spi, register: u8) -> Result<u8, BusError> {
+        spi.transaction(|spi| {
+            let mut buf = [register | 0x80, 0];
+            spi.transfer(&mut buf)?;
+            Ok(buf[1])
+        })
+    }
+
+    #[test]
+    fn test_transfer_with_chip_select() {
+        let mut spi = SpiMock::new(&[
+            SpiTransaction::Select,
+            SpiTransaction::Transfer {
+                write: vec![0x8F, 0x00],
+                read: vec![0x00, 0x33],
+            },
+            SpiTransaction::Deselect,
+        ]);
+
+        assert_eq!(read_register(&mut spi, 0x0F), Ok(0x33));
+        spi.done();
+    }
+
+    #[test]
+    fn test_write_then_read() {
+        let mut spi = SpiMock::new(&[
+            SpiTransaction::Write(vec![0x20, 0x57]),
+            SpiTransaction::Read(vec![1, 2, 3]),
+        ]);
+        let mut buf = [0; 3];
+
+        spi.write(&[0x20, 0x57]).unwrap();
+        spi.read(&mut buf).unwrap();
+
+        assert_eq!(buf, [1, 2, 3]);
+        spi.done();
+    }
+
+    #[test]
+    #[should_panic(expected = "SPI: expected Write([32, 87]), got Write([32, 0])")]
+    fn test_unexpected_write_panics() {
+        let mut spi = SpiMock::new(&[SpiTransaction::Write(vec![0x20, 0x57])]);
+
+        let _ = spi.write(&[0x20, 0x00]);
+    }
+
+    #[test]
+    #[should_panic(expected = "SPI: transactions not performed: [Deselect]")]
+    fn test_done_checks_pending() {
+        let mut spi = SpiMock::new(&[SpiTransaction::Select, SpiTransaction::Deselect]);
+
+        spi.select().unwrap();
+        spi.done();
+    }
+}
@@ -0,0 +1,190 @@
+use super::mock::Script;
+use super::BusError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Parity {
+    None,
+    Even,
+    Odd,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopBits {
+    One,
+    Two,
+}
+
+/// Enquadramento da UART. O padrão é 115200 8N1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UartConfig {
+    pub baud: u32,
+    pub data_bits: u8,
+    pub parity: Parity,
+    pub stop_bits: StopBits,
+}
+
+impl Default for UartConfig {
+    fn default() -> Self {
+        Self {
+            baud: 115_200,
+            data_bits: 8,
+            parity: Parity::None,
+            stop_bits: StopBits::One,
+        }
+    }
+}
+
+impl UartConfig {
+    pub fn validate(&self) -> Result<(), BusError> {
+        if self.baud == 0 || !(5..=9).contains(&self.data_bits) {
+            return Err(BusError::InvalidConfig);
+        }
+        Ok(())
+    }
+
+    /// Bits transmitidos por caractere: start + dados + paridade + stop.
+    pub fn frame_bits(&self) -> u32 {
+        let parity = if self.parity == Parity::None { 0 } else { 1 };
+        let stop = match self.stop_bits {
+            StopBits::One => 1,
+            StopBits::Two => 2,
+        };
+
+        1 + self.data_bits as u32 + parity + stop
+    }
+
+    /// Tempo para transmitir `len` bytes, em microssegundos. `None` se `baud` for zero.
+    pub fn transmit_time_us(&self, len: usize) -> Option<u64> {
+        (len as u64 * self.frame_bits() as u64 * 1_000_000).checked_div(self.baud as u64)
+    }
+}
+
+pub trait Uart {
+    fn configure(&mut self, config: UartConfig) -> Result<(), BusError>;
+    fn write(&mut self, data: &[u8]) -> Result<(), BusError>;
+    /// Lê o que estiver disponível, até `buf.len()` bytes. Retorna quantos foram lidos.
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, BusError>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UartTransaction {
+    Configure(UartConfig),
+    Write(Vec<u8>),
+    /// Bytes que o mock devolve no próximo `read`.
+    Read(Vec<u8>),
+    /// O próximo `read` falha com [`BusError::Framing`].
+    FramingError,
+}
+
+#[derive(Debug, Clone)]
+pub struct UartMock {
+    script: Script<UartTransaction>,
+}
+
+impl UartMock {
+    pub fn new(expected: &[UartTransaction]) -> Self {
+        Self {
+            script: Script::new("UART", expected),
+        }
+    }
+
+    pub fn done(&self) {
+        self.script.done();
+    }
+}
+
+impl Uart for UartMock {
+    fn configure(&mut self, config: UartConfig) -> Result<(), BusError> {
+        config.validate()?;
+
+        match self.script.next("configure") {
+            UartTransaction::Configure(expected) if expected == config => Ok(()),
+            other => self
+                .script
+                .mismatch(&other, UartTransaction::Configure(config)),
+        }
+    }
+
+    fn write(&mut self, data: &[u8]) -> Result<(), BusError> {
+        match self.script.next("write") {
+            UartTransaction::Write(expected) if expected == data => Ok(()),
+            other => self
+                .script
+                .mismatch(&other, UartTransaction::Write(data.to_vec())),
+        }
+    }
+
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, BusError> {
+        match self.script.next("read") {
+            UartTransaction::Read(data) if data.len() <= buf.len() => {
+                buf[..data.len()].copy_from_slice(&data);
+                Ok(data.len())
+            }
+            UartTransaction::FramingError => Err(BusError::Framing),
+            other => self
+                .script
+                .mismatch(&other, format!("Read({} bytes)", buf.len())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_timing() {
+        let config = UartConfig::default();
+        assert_eq!(config.frame_bits(), 10);
+        assert_eq!(config.transmit_time_us(1152), Some(100_000));
+
+        let config = UartConfig {
+            baud: 9600,
+            data_bits: 7,
+            parity: Parity::Even,
+            stop_bits: StopBits::Two,
+        };
+        assert_eq!(config.frame_bits(), 11);
+
+        let stopped = UartConfig {
+            baud: 0,
+            ..Default::default()
+        };
+        assert_eq!(stopped.transmit_time_us(1), None);
+    }
+
+    #[test]
+    fn test_configure_write_read() {
+        let config = UartConfig {
+            baud: 9600,
+            ..Default::default()
+        };
+        let mut uart = UartMock::new(&[
+            UartTransaction::Configure(config),
+            UartTransaction::Write(b"AT\r\n".to_vec()),
+            UartTransaction::Read(b"OK\r\n".to_vec()),
+            UartTransaction::FramingError,
+        ]);
+        let mut buf = [0; 16];
+
+        uart.configure(config).unwrap();
+        uart.write(b"AT\r\n").unwrap();
+        let len = uart.read(&mut buf).unwrap();
+
+        assert_eq!(&buf[..len], b"OK\r\n");
+        assert_eq!(uart.read(&mut buf), Err(BusError::Framing));
+        uart.done();
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        let mut uart = UartMock::new(&[]);
+        let config = UartConfig {
+            data_bits: 4,
+            ..Default::default()
+        };
+
+        assert_eq!(uart.configure(config), Err(BusError::InvalidConfig));
+        uart.done();
+    }
+}
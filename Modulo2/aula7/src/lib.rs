@@ -0,0 +1,22 @@
+//! Partes da aula 7 que são reaproveitadas fora dela:
+//!
+//! - [`bus`]: abstrações de barramento usadas para testar os drivers no host;
+//! - [`ring_buffer`]: buffer circular, por exemplo para as amostras do `Lis2dh`;
+//! - [`spsc`]: fila lock-free usada pelo app RTIC da aula 8 do Módulo 3;
+//! - [`state_machine`]: máquina de estados genérica (player, aquecedor, Wi-Fi, debounce).
+//!
+//! !!! Sem a feature `std` (ligada por padrão) a biblioteca é `no_std` e só exporta o que usa
+//! apenas `core`, para ser usada no firmware:
+//!
+//! ```toml
+//! aula7 = { path = "../../Modulo2/aula7", default-features = false }
+//! ```
+
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
+
+#[cfg(feature = "std")]
+pub mod bus;
+pub mod ring_buffer;
+pub mod spsc;
+#[cfg(feature = "std")]
+pub mod state_machine;
@@ -314,7 +314,6 @@ mod associate_types_and_generics {
             }
 
             let el = Some(&self.coll[self.index]);
-            let el = dbg!(el);
 
             self.index += 1;
             if self.index == self.coll.len() {
@@ -570,6 +569,73 @@ mod struct_state_with_traits {
     }
 }
 
+/// Mesmo player de `struct_state_with_traits`, mas com a tabela de transições declarada uma única
+/// vez usando o `state_machine::StateMachine`.
+#[allow(unused)]
+mod struct_state_with_framework {
+    use aula7::state_machine::{StateMachine, TransitionError};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum PlayerState {
+        Stopped,
+        Paused,
+        Playing,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum PlayerEvent {
+        Play,
+        Stop,
+    }
+
+    pub struct Player {
+        music: String,
+        plays: u32,
+    }
+
+    fn player(music: &str) -> StateMachine<PlayerState, PlayerEvent, Player> {
+        use PlayerEvent::*;
+        use PlayerState::*;
+
+        StateMachine::builder(Stopped)
+            .transition(Stopped, Play, Playing)
+            .transition(Paused, Play, Playing)
+            .transition(Paused, Stop, Stopped)
+            .transition(Playing, Play, Paused)
+            .transition(Playing, Stop, Stopped)
+            .on_entry(Playing, |player: &mut Player| {
+                player.plays += 1;
+                println!("{} | Playing", player.music);
+            })
+            .build(Player {
+                music: music.to_string(),
+                plays: 0,
+            })
+    }
+
+    #[test]
+    fn test_player_with_framework() {
+        let mut player = player("Track1.mp3");
+
+        assert_eq!(player.handle(PlayerEvent::Play), Ok(PlayerState::Playing));
+        assert_eq!(player.handle(PlayerEvent::Play), Ok(PlayerState::Paused));
+        assert_eq!(player.handle(PlayerEvent::Play), Ok(PlayerState::Playing));
+        assert_eq!(player.context().plays, 2);
+        assert_eq!(player.handle(PlayerEvent::Stop), Ok(PlayerState::Stopped));
+
+        // Antes era só um "Already stopped" no terminal, agora é um erro
+        assert_eq!(
+            player.handle(PlayerEvent::Stop),
+            Err(TransitionError::NoTransition {
+                state: PlayerState::Stopped,
+                event: PlayerEvent::Stop
+            })
+        );
+
+        println!("{}", player.to_mermaid());
+    }
+}
+
 mod struct_context_with_traits {
     pub trait Interface {
         type D: Driver;
@@ -622,6 +688,47 @@ mod struct_context_with_traits {
 
         assert_eq!(result, vec![1, 2, 3]);
     }
+
+    // !!! A versão completa desta ideia está em `aula7::bus`: um trait por barramento e mocks
+    //     que conferem as transações, permitindo testar o driver sem o hardware.
+    #[cfg(test)]
+    mod with_bus {
+        use aula7::bus::mock::{SpiMock, SpiTransaction};
+        use aula7::bus::spi::Spi;
+        use aula7::bus::BusError;
+
+        const WHO_AM_I: u8 = 0x0F;
+        const READ: u8 = 0x80;
+
+        struct Accelerometer<S: Spi> {
+            spi: S,
+        }
+
+        impl<S: Spi> Accelerometer<S> {
+            fn who_am_i(&mut self) -> Result<u8, BusError> {
+                self.spi.transaction(|spi| {
+                    let mut buf = [0];
+                    spi.write(&[READ | WHO_AM_I])?;
+                    spi.read(&mut buf)?;
+                    Ok(buf[0])
+                })
+            }
+        }
+
+        #[test]
+        fn test_driver_with_mock() {
+            let spi = SpiMock::new(&[
+                SpiTransaction::Select,
+                SpiTransaction::Write(vec![0x8F]),
+                SpiTransaction::Read(vec![0x33]),
+                SpiTransaction::Deselect,
+            ]);
+            let mut acc = Accelerometer { spi: spi.clone() };
+
+            assert_eq!(acc.who_am_i(), Ok(0x33));
+            spi.done();
+        }
+    }
 }
 
 fn main() {
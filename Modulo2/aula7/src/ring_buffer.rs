@@ -0,0 +1,307 @@
+//! Buffer circular de capacidade fixa.
+//!
+//! Diferente do `RingPointer`, que apenas percorre um `&Vec` emprestado, o `RingBuffer` é dono
+//! dos seus dados. Como usa apenas `core` (sem alocação), pode ser levado para um alvo `no_std`,
+//! por exemplo para armazenar as amostras do `Lis2dh`.
+
+use core::fmt::{Debug, Formatter};
+
+/// O que fazer quando um `push` é feito com o buffer cheio.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverflowPolicy {
+    /// Descarta o elemento mais antigo para abrir espaço para o novo.
+    #[default]
+    Overwrite,
+    /// Recusa o novo elemento, devolvendo-o para quem chamou.
+    Reject,
+}
+
+pub struct RingBuffer<T, const N: usize> {
+    buffer: [Option<T>; N],
+    // Índice do elemento mais antigo
+    head: usize,
+    len: usize,
+    policy: OverflowPolicy,
+}
+
+impl<T, const N: usize> RingBuffer<T, N> {
+    pub fn new(policy: OverflowPolicy) -> Self {
+        Self {
+            buffer: core::array::from_fn(|_| None),
+            head: 0,
+            len: 0,
+            policy,
+        }
+    }
+
+    pub fn policy(&self) -> OverflowPolicy {
+        self.policy
+    }
+
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    /// Insere `value` no final do buffer.
+    ///
+    /// Com o buffer cheio, o comportamento depende da [`OverflowPolicy`]: `Overwrite` descarta o
+    /// elemento mais antigo e `Reject` devolve `value` como erro.
+    pub fn push(&mut self, value: T) -> Result<(), T> {
+        if N == 0 {
+            return Err(value);
+        }
+
+        if self.is_full() {
+            match self.policy {
+                OverflowPolicy::Reject => return Err(value),
+                OverflowPolicy::Overwrite => {
+                    self.pop();
+                }
+            }
+        }
+
+        let tail = (self.head + self.len) % N;
+        self.buffer[tail] = Some(value);
+        self.len += 1;
+
+        Ok(())
+    }
+
+    /// Remove e retorna o elemento mais antigo.
+    pub fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let el = self.buffer[self.head].take();
+        self.head = (self.head + 1) % N;
+        self.len -= 1;
+
+        el
+    }
+
+    /// Retorna o elemento mais antigo sem removê-lo.
+    pub fn peek(&self) -> Option<&T> {
+        if self.is_empty() {
+            return None;
+        }
+
+        self.buffer[self.head].as_ref()
+    }
+
+    pub fn clear(&mut self) {
+        while self.pop().is_some() {}
+    }
+
+    /// Percorre os elementos do mais antigo para o mais novo.
+    pub fn iter(&self) -> Iter<'_, T, N> {
+        Iter {
+            ring: self,
+            index: 0,
+        }
+    }
+}
+
+impl<T, const N: usize> Default for RingBuffer<T, N> {
+    fn default() -> Self {
+        Self::new(OverflowPolicy::default())
+    }
+}
+
+impl<T: Debug, const N: usize> Debug for RingBuffer<T, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+mod iterator {
+    use super::*;
+
+    pub struct Iter<'a, T, const N: usize> {
+        pub(super) ring: &'a RingBuffer<T, N>,
+        pub(super) index: usize,
+    }
+
+    impl<'a, T, const N: usize> Iterator for Iter<'a, T, N> {
+        type Item = &'a T;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            if self.index == self.ring.len {
+                return None;
+            }
+
+            let el = self.ring.buffer[(self.ring.head + self.index) % N].as_ref();
+            self.index += 1;
+
+            el
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            let remaining = self.ring.len - self.index;
+            (remaining, Some(remaining))
+        }
+    }
+
+    impl<T, const N: usize> ExactSizeIterator for Iter<'_, T, N> {}
+
+    pub struct IntoIter<T, const N: usize> {
+        ring: RingBuffer<T, N>,
+    }
+
+    impl<T, const N: usize> Iterator for IntoIter<T, N> {
+        type Item = T;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            self.ring.pop()
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (self.ring.len, Some(self.ring.len))
+        }
+    }
+
+    impl<T, const N: usize> ExactSizeIterator for IntoIter<T, N> {}
+
+    impl<T, const N: usize> IntoIterator for RingBuffer<T, N> {
+        type Item = T;
+        type IntoIter = IntoIter<T, N>;
+
+        fn into_iter(self) -> Self::IntoIter {
+            IntoIter { ring: self }
+        }
+    }
+
+    impl<'a, T, const N: usize> IntoIterator for &'a RingBuffer<T, N> {
+        type Item = &'a T;
+        type IntoIter = Iter<'a, T, N>;
+
+        fn into_iter(self) -> Self::IntoIter {
+            self.iter()
+        }
+    }
+
+    /// Os elementos são inseridos um a um seguindo a [`OverflowPolicy`] do buffer: com `Reject`,
+    /// o que não couber é descartado.
+    impl<T, const N: usize> Extend<T> for RingBuffer<T, N> {
+        fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+            for el in iter {
+                let _ = self.push(el);
+            }
+        }
+    }
+}
+
+pub use iterator::{IntoIter, Iter};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty() {
+        let mut ring = RingBuffer::<u32, 3>::default();
+
+        assert!(ring.is_empty());
+        assert_eq!(ring.capacity(), 3);
+        assert_eq!(ring.peek(), None);
+        assert_eq!(ring.pop(), None);
+        assert_eq!(ring.iter().next(), None);
+    }
+
+    #[test]
+    fn test_push_pop_fifo() {
+        let mut ring = RingBuffer::<u32, 3>::new(OverflowPolicy::Reject);
+
+        assert_eq!(ring.push(1), Ok(()));
+        assert_eq!(ring.push(2), Ok(()));
+        assert_eq!(ring.peek(), Some(&1));
+        assert_eq!(ring.len(), 2);
+
+        assert_eq!(ring.pop(), Some(1));
+        assert_eq!(ring.pop(), Some(2));
+        assert_eq!(ring.pop(), None);
+    }
+
+    #[test]
+    fn test_reject_when_full() {
+        let mut ring = RingBuffer::<u32, 2>::new(OverflowPolicy::Reject);
+
+        ring.push(1).unwrap();
+        ring.push(2).unwrap();
+
+        assert!(ring.is_full());
+        assert_eq!(ring.push(3), Err(3));
+        assert_eq!(ring.iter().copied().collect::<Vec<_>>(), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_overwrite_oldest_when_full() {
+        let mut ring = RingBuffer::<u32, 3>::new(OverflowPolicy::Overwrite);
+
+        for i in 1..=5 {
+            assert_eq!(ring.push(i), Ok(()));
+        }
+
+        assert!(ring.is_full());
+        assert_eq!(ring.peek(), Some(&3));
+        assert_eq!(ring.iter().copied().collect::<Vec<_>>(), vec![3, 4, 5]);
+    }
+
+    #[test]
+    fn test_iter_logical_order_after_wrap() {
+        let mut ring = RingBuffer::<u32, 4>::new(OverflowPolicy::Reject);
+
+        ring.extend([1, 2, 3, 4]);
+        ring.pop();
+        ring.pop();
+        ring.extend([5, 6]);
+
+        assert_eq!(ring.iter().len(), 4);
+        assert_eq!(
+            (&ring).into_iter().copied().collect::<Vec<_>>(),
+            vec![3, 4, 5, 6]
+        );
+        assert_eq!(ring.into_iter().collect::<Vec<_>>(), vec![3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn test_extend_with_reject_drops_excess() {
+        let mut ring = RingBuffer::<u32, 2>::new(OverflowPolicy::Reject);
+
+        ring.extend(0..10);
+
+        assert_eq!(format!("{:?}", ring), "[0, 1]");
+    }
+
+    #[test]
+    fn test_zero_capacity() {
+        let mut ring = RingBuffer::<u32, 0>::new(OverflowPolicy::Overwrite);
+
+        assert_eq!(ring.push(1), Err(1));
+        assert!(ring.is_empty());
+        assert!(ring.is_full());
+    }
+
+    #[test]
+    fn test_owned_values_are_dropped() {
+        let mut ring = RingBuffer::<String, 2>::new(OverflowPolicy::Overwrite);
+
+        ring.extend(["a", "b", "c"].map(String::from));
+        ring.clear();
+
+        assert!(ring.is_empty());
+        assert_eq!(ring.peek(), None);
+    }
+}
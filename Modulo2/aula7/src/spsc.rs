@@ -0,0 +1,357 @@
+//! Fila circular lock-free com um único produtor e um único consumidor (SPSC).
+//!
+//! Segue a mesma ideia do `RingPointer`: índices que voltam ao início ao chegar no fim do buffer.
+//! A diferença é que a escrita e a leitura acontecem em contextos diferentes (ex.: uma
+//! interrupção produzindo amostras e uma task consumindo), então cada lado recebe o seu handle
+//! ([`Producer`] e [`Consumer`]) e a sincronização é feita apenas com atômicos, sem seção crítica.
+//!
+//! Usa somente `core`, podendo ser usada em alvos `no_std`.
+
+use core::cell::UnsafeCell;
+use core::mem::MaybeUninit;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+pub struct Queue<T, const N: usize> {
+    buffer: [UnsafeCell<MaybeUninit<T>>; N],
+    // Os índices andam no intervalo 0..2N, assim conseguimos diferenciar a fila cheia
+    // (tail - head == N) da fila vazia (tail == head) sem desperdiçar uma posição do buffer.
+    // Próxima posição a ser lida, só é escrita pelo consumidor
+    head: AtomicUsize,
+    // Próxima posição a ser escrita, só é escrita pelo produtor
+    tail: AtomicUsize,
+}
+
+// !!! Seguro pois o `split` garante que só existe um produtor e um consumidor por vez, e cada
+//     posição do buffer só é acessada por um dos dois lados dependendo de `head` e `tail`.
+unsafe impl<T: Send, const N: usize> Sync for Queue<T, N> {}
+
+impl<T, const N: usize> Queue<T, N> {
+    /// Por ser `const`, a fila pode ser criada em uma `static` ou em um recurso local do RTIC.
+    pub const fn new() -> Self {
+        Self {
+            buffer: [const { UnsafeCell::new(MaybeUninit::uninit()) }; N],
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    pub fn len(&self) -> usize {
+        let head = self.head.load(Ordering::Acquire);
+        let tail = self.tail.load(Ordering::Acquire);
+
+        Self::distance(head, tail)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len() == N
+    }
+
+    /// Divide a fila nos handles de escrita e leitura.
+    ///
+    /// Como exige `&mut self`, o borrow checker garante que não existam dois produtores ou dois
+    /// consumidores ao mesmo tempo.
+    pub fn split(&mut self) -> (Producer<'_, T, N>, Consumer<'_, T, N>) {
+        let queue: &Self = self;
+
+        (Producer { queue }, Consumer { queue })
+    }
+
+    fn distance(head: usize, tail: usize) -> usize {
+        if N == 0 {
+            return 0;
+        }
+
+        (tail + 2 * N - head) % (2 * N)
+    }
+
+    fn increment(index: usize) -> usize {
+        (index + 1) % (2 * N)
+    }
+}
+
+impl<T, const N: usize> Default for Queue<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Drop for Queue<T, N> {
+    fn drop(&mut self) {
+        let mut head = *self.head.get_mut();
+        let tail = *self.tail.get_mut();
+
+        while head != tail {
+            // SAFETY: as posições entre head e tail foram escritas e ainda não foram lidas.
+            unsafe { self.buffer[head % N].get_mut().assume_init_drop() };
+            head = Self::increment(head);
+        }
+    }
+}
+
+pub struct Producer<'a, T, const N: usize> {
+    queue: &'a Queue<T, N>,
+}
+
+impl<T, const N: usize> Producer<'_, T, N> {
+    /// Insere `value` no final da fila, devolvendo-o caso a fila esteja cheia.
+    pub fn enqueue(&mut self, value: T) -> Result<(), T> {
+        let tail = self.queue.tail.load(Ordering::Relaxed);
+        // Acquire: garante que a leitura do consumidor nesta posição já terminou
+        let head = self.queue.head.load(Ordering::Acquire);
+
+        if Queue::<T, N>::distance(head, tail) == N {
+            return Err(value);
+        }
+
+        // SAFETY: a posição está livre e somente o produtor escreve nela.
+        unsafe { (*self.queue.buffer[tail % N].get()).write(value) };
+        // Release: publica o valor escrito antes de o consumidor enxergar o novo tail
+        self.queue
+            .tail
+            .store(Queue::<T, N>::increment(tail), Ordering::Release);
+
+        Ok(())
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.queue.is_full()
+    }
+
+    pub fn capacity(&self) -> usize {
+        N
+    }
+}
+
+pub struct Consumer<'a, T, const N: usize> {
+    queue: &'a Queue<T, N>,
+}
+
+impl<T, const N: usize> Consumer<'_, T, N> {
+    /// Remove o elemento mais antigo da fila.
+    pub fn dequeue(&mut self) -> Option<T> {
+        let head = self.queue.head.load(Ordering::Relaxed);
+        // Acquire: garante que enxergamos o valor escrito pelo produtor
+        let tail = self.queue.tail.load(Ordering::Acquire);
+
+        if head == tail {
+            return None;
+        }
+
+        // SAFETY: a posição foi escrita pelo produtor e somente o consumidor lê dela.
+        let value = unsafe { (*self.queue.buffer[head % N].get()).assume_init_read() };
+        // Release: libera a posição para o produtor somente depois de lida
+        self.queue
+            .head
+            .store(Queue::<T, N>::increment(head), Ordering::Release);
+
+        Some(value)
+    }
+
+    /// Retorna o elemento mais antigo sem removê-lo.
+    pub fn peek(&self) -> Option<&T> {
+        let head = self.queue.head.load(Ordering::Relaxed);
+        let tail = self.queue.tail.load(Ordering::Acquire);
+
+        if head == tail {
+            return None;
+        }
+
+        // SAFETY: a posição só é liberada para o produtor após um `dequeue`, que exige `&mut self`.
+        Some(unsafe { (*self.queue.buffer[head % N].get()).assume_init_ref() })
+    }
+
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+}
+
+/// Assim como o `ProtoIterator` do `RingPointer`, o consumidor pode ser percorrido com `next`,
+/// que esvazia os elementos disponíveis no momento.
+impl<T, const N: usize> Iterator for Consumer<'_, T, N> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.dequeue()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_fifo_order() {
+        let mut queue = Queue::<u32, 3>::new();
+        let (mut producer, mut consumer) = queue.split();
+
+        assert_eq!(consumer.dequeue(), None);
+        assert_eq!(producer.enqueue(1), Ok(()));
+        assert_eq!(producer.enqueue(2), Ok(()));
+        assert_eq!(consumer.peek(), Some(&1));
+        assert_eq!(consumer.len(), 2);
+        assert_eq!(consumer.dequeue(), Some(1));
+        assert_eq!(consumer.dequeue(), Some(2));
+        assert_eq!(consumer.dequeue(), None);
+    }
+
+    #[test]
+    fn test_full_rejects() {
+        let mut queue = Queue::<u32, 2>::new();
+        let (mut producer, mut consumer) = queue.split();
+
+        producer.enqueue(1).unwrap();
+        producer.enqueue(2).unwrap();
+
+        assert!(producer.is_full());
+        assert_eq!(producer.enqueue(3), Err(3));
+        assert_eq!(consumer.dequeue(), Some(1));
+        assert_eq!(producer.enqueue(3), Ok(()));
+        assert_eq!(consumer.by_ref().collect::<Vec<_>>(), vec![2, 3]);
+    }
+
+    #[test]
+    fn test_wrap_around() {
+        let mut queue = Queue::<usize, 3>::new();
+        let (mut producer, mut consumer) = queue.split();
+
+        // Dá várias voltas para passar pelo limite de 2N dos índices
+        for i in 0..100 {
+            producer.enqueue(i).unwrap();
+            producer.enqueue(i + 1).unwrap();
+            assert_eq!(consumer.dequeue(), Some(i));
+            assert_eq!(consumer.dequeue(), Some(i + 1));
+        }
+
+        assert!(consumer.is_empty());
+    }
+
+    #[test]
+    fn test_zero_capacity() {
+        let mut queue = Queue::<u32, 0>::new();
+        let (mut producer, mut consumer) = queue.split();
+
+        assert_eq!(producer.enqueue(1), Err(1));
+        assert_eq!(consumer.dequeue(), None);
+        assert!(consumer.is_empty());
+    }
+
+    #[test]
+    fn test_drop_remaining_items() {
+        let counter = Arc::new(());
+        let mut queue = Queue::<Arc<()>, 4>::new();
+
+        {
+            let (mut producer, mut consumer) = queue.split();
+            for _ in 0..4 {
+                producer.enqueue(counter.clone()).unwrap();
+            }
+            consumer.dequeue();
+        }
+
+        assert_eq!(Arc::strong_count(&counter), 4);
+        drop(queue);
+        assert_eq!(Arc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn test_stress_two_threads() {
+        const TOTAL: usize = 100_000;
+        let mut queue = Queue::<usize, 16>::new();
+        let (mut producer, mut consumer) = queue.split();
+
+        std::thread::scope(|s| {
+            s.spawn(move || {
+                for i in 0..TOTAL {
+                    let mut value = i;
+                    while let Err(v) = producer.enqueue(value) {
+                        value = v;
+                        std::thread::yield_now();
+                    }
+                }
+            });
+
+            s.spawn(move || {
+                let mut expected = 0;
+                while expected < TOTAL {
+                    if let Some(value) = consumer.dequeue() {
+                        assert_eq!(value, expected);
+                        expected += 1;
+                    } else {
+                        std::thread::yield_now();
+                    }
+                }
+            });
+        });
+
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_stress_no_leak_or_double_drop() {
+        static CREATED: AtomicUsize = AtomicUsize::new(0);
+        static DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+        struct Sample;
+
+        impl Sample {
+            fn new() -> Self {
+                CREATED.fetch_add(1, Ordering::Relaxed);
+                Sample
+            }
+        }
+
+        impl Drop for Sample {
+            fn drop(&mut self) {
+                DROPPED.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        const TOTAL: usize = 100_000;
+        let mut queue = Queue::<Sample, 7>::new();
+        let (mut producer, mut consumer) = queue.split();
+
+        std::thread::scope(|s| {
+            s.spawn(move || {
+                let mut sent = 0;
+                while sent < TOTAL {
+                    // Amostras rejeitadas voltam no Err e são liberadas aqui mesmo
+                    if producer.enqueue(Sample::new()).is_ok() {
+                        sent += 1;
+                    } else {
+                        std::thread::yield_now();
+                    }
+                }
+            });
+
+            // Consome só uma parte, o restante deve ser liberado pelo Drop da fila
+            let mut received = 0;
+            while received < TOTAL - 5 {
+                if consumer.dequeue().is_some() {
+                    received += 1;
+                } else {
+                    std::thread::yield_now();
+                }
+            }
+        });
+
+        assert_eq!(queue.len(), 5);
+        drop(queue);
+        assert_eq!(
+            CREATED.load(Ordering::Relaxed),
+            DROPPED.load(Ordering::Relaxed)
+        );
+    }
+}
@@ -0,0 +1,528 @@
+//! Máquina de estados genérica, dirigida por uma tabela de transições.
+//!
+//! Em vez de escrever um `struct` por estado (como o `Player` com `Box<dyn State>`), a tabela de
+//! transições é declarada uma única vez com o [`Builder`], e a mesma máquina pode ser usada para
+//! o player, o aquecedor, a conexão Wi-Fi, o debounce de botão etc.
+//!
+//! ```ignore
+//! let mut player = StateMachine::builder(Stopped)
+//!     .transition(Stopped, Play, Playing)
+//!     .transition(Playing, Stop, Stopped)
+//!     .on_entry(Playing, |music: &mut String| println!("Playing {music}"))
+//!     .build("Track1.mp3".to_owned());
+//!
+//! player.handle(Play)?;
+//! ```
+
+use std::fmt::{Debug, Display, Formatter};
+
+type Guard<C> = Box<dyn Fn(&C) -> bool>;
+type Action<C> = Box<dyn Fn(&mut C)>;
+
+struct Transition<S, E, C> {
+    from: S,
+    event: E,
+    to: S,
+    // O nome aparece nos diagramas exportados
+    guard: Option<(String, Guard<C>)>,
+    action: Option<Action<C>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransitionError<S, E> {
+    /// Não existe transição para `event` partindo de `state`.
+    NoTransition { state: S, event: E },
+    /// Existem transições, mas todas foram recusadas pelas suas guards.
+    GuardRejected { state: S, event: E },
+}
+
+impl<S: Debug, E: Debug> Display for TransitionError<S, E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TransitionError::NoTransition { state, event } => {
+                write!(f, "No transition from {:?} on {:?}", state, event)
+            }
+            TransitionError::GuardRejected { state, event } => {
+                write!(
+                    f,
+                    "Transition from {:?} on {:?} rejected by guard",
+                    state, event
+                )
+            }
+        }
+    }
+}
+
+impl<S: Debug, E: Debug> std::error::Error for TransitionError<S, E> {}
+
+pub struct Builder<S, E, C> {
+    initial: S,
+    transitions: Vec<Transition<S, E, C>>,
+    on_entry: Vec<(S, Action<C>)>,
+    on_exit: Vec<(S, Action<C>)>,
+}
+
+impl<S, E, C> Builder<S, E, C>
+where
+    S: Copy + PartialEq + Debug,
+    E: Copy + PartialEq + Debug,
+{
+    pub fn transition(mut self, from: S, event: E, to: S) -> Self {
+        self.transitions.push(Transition {
+            from,
+            event,
+            to,
+            guard: None,
+            action: None,
+        });
+        self
+    }
+
+    /// Adiciona uma guard à última transição declarada.
+    ///
+    /// Quando mais de uma transição atende ao mesmo evento, a primeira cuja guard for aceita é a
+    /// escolhida.
+    pub fn guard(mut self, name: &str, guard: impl Fn(&C) -> bool + 'static) -> Self {
+        self.last_transition().guard = Some((name.to_owned(), Box::new(guard)));
+        self
+    }
+
+    /// Adiciona uma ação à última transição declarada, executada entre a saída do estado de
+    /// origem e a entrada no estado de destino.
+    pub fn action(mut self, action: impl Fn(&mut C) + 'static) -> Self {
+        self.last_transition().action = Some(Box::new(action));
+        self
+    }
+
+    pub fn on_entry(mut self, state: S, action: impl Fn(&mut C) + 'static) -> Self {
+        self.on_entry.push((state, Box::new(action)));
+        self
+    }
+
+    pub fn on_exit(mut self, state: S, action: impl Fn(&mut C) + 'static) -> Self {
+        self.on_exit.push((state, Box::new(action)));
+        self
+    }
+
+    pub fn build(self, context: C) -> StateMachine<S, E, C> {
+        StateMachine {
+            state: self.initial,
+            context,
+            initial: self.initial,
+            transitions: self.transitions,
+            on_entry: self.on_entry,
+            on_exit: self.on_exit,
+        }
+    }
+
+    fn last_transition(&mut self) -> &mut Transition<S, E, C> {
+        self.transitions
+            .last_mut()
+            .expect("guard/action must come after a transition")
+    }
+}
+
+pub struct StateMachine<S, E, C> {
+    state: S,
+    context: C,
+    initial: S,
+    transitions: Vec<Transition<S, E, C>>,
+    on_entry: Vec<(S, Action<C>)>,
+    on_exit: Vec<(S, Action<C>)>,
+}
+
+impl<S, E, C> StateMachine<S, E, C>
+where
+    S: Copy + PartialEq + Debug,
+    E: Copy + PartialEq + Debug,
+{
+    pub fn builder(initial: S) -> Builder<S, E, C> {
+        Builder {
+            initial,
+            transitions: vec![],
+            on_entry: vec![],
+            on_exit: vec![],
+        }
+    }
+
+    pub fn state(&self) -> S {
+        self.state
+    }
+
+    pub fn context(&self) -> &C {
+        &self.context
+    }
+
+    pub fn context_mut(&mut self) -> &mut C {
+        &mut self.context
+    }
+
+    /// Indica se `event` seria aceito no estado atual, sem executar a transição.
+    pub fn can_handle(&self, event: E) -> bool {
+        self.find(event).is_ok()
+    }
+
+    /// Processa `event` e retorna o novo estado.
+    ///
+    /// A ordem de execução é: ações de saída do estado atual, ação da transição e ações de
+    /// entrada do novo estado. Se o evento for recusado, nada é executado e o estado não muda.
+    pub fn handle(&mut self, event: E) -> Result<S, TransitionError<S, E>> {
+        let index = self.find(event)?;
+        let from = self.state;
+        let to = self.transitions[index].to;
+
+        for (_, action) in self.on_exit.iter().filter(|(s, _)| *s == from) {
+            action(&mut self.context);
+        }
+
+        if let Some(action) = &self.transitions[index].action {
+            action(&mut self.context);
+        }
+
+        self.state = to;
+
+        for (_, action) in self.on_entry.iter().filter(|(s, _)| *s == to) {
+            action(&mut self.context);
+        }
+
+        Ok(to)
+    }
+
+    fn find(&self, event: E) -> Result<usize, TransitionError<S, E>> {
+        let mut candidates = self
+            .transitions
+            .iter()
+            .enumerate()
+            .filter(|(_, t)| t.from == self.state && t.event == event)
+            .peekable();
+
+        if candidates.peek().is_none() {
+            return Err(TransitionError::NoTransition {
+                state: self.state,
+                event,
+            });
+        }
+
+        candidates
+            .find(|(_, t)| match &t.guard {
+                Some((_, guard)) => guard(&self.context),
+                None => true,
+            })
+            .map(|(i, _)| i)
+            .ok_or(TransitionError::GuardRejected {
+                state: self.state,
+                event,
+            })
+    }
+
+    fn label(transition: &Transition<S, E, C>) -> String {
+        match &transition.guard {
+            Some((name, _)) => format!("{:?} [{}]", transition.event, name),
+            None => format!("{:?}", transition.event),
+        }
+    }
+}
+
+mod export {
+    use super::*;
+
+    impl<S, E, C> StateMachine<S, E, C>
+    where
+        S: Copy + PartialEq + Debug,
+        E: Copy + PartialEq + Debug,
+    {
+        /// Exporta a tabela de transições no formato DOT do Graphviz.
+        pub fn to_graphviz(&self) -> String {
+            let mut out = String::from("digraph {\n");
+            out += "    __start [shape = point];\n";
+            out += &format!(
+                "    __start -> {};\n",
+                quote(&format!("{:?}", self.initial))
+            );
+
+            for t in self.transitions.iter() {
+                out += &format!(
+                    "    {} -> {} [label = {}];\n",
+                    quote(&format!("{:?}", t.from)),
+                    quote(&format!("{:?}", t.to)),
+                    quote(&Self::label(t))
+                );
+            }
+
+            out += "}\n";
+            out
+        }
+
+        /// Exporta a tabela de transições como um `stateDiagram-v2` do Mermaid.
+        ///
+        /// Cada estado é declarado com um ID `sN` e o `Debug` como descrição, porque o Mermaid
+        /// não aceita espaços, parênteses ou chaves no ID.
+        pub fn to_mermaid(&self) -> String {
+            let mut states = vec![self.initial];
+            for t in self.transitions.iter() {
+                for state in [t.from, t.to] {
+                    if !states.contains(&state) {
+                        states.push(state);
+                    }
+                }
+            }
+            let id = |state: S| states.iter().position(|&s| s == state).unwrap();
+
+            let mut out = String::from("stateDiagram-v2\n");
+            for (i, state) in states.iter().enumerate() {
+                // O Mermaid não tem escape com `\`, só entidades
+                let name = format!("{:?}", state).replace('"', "#quot;");
+                out += &format!("    state \"{}\" as s{}\n", name, i);
+            }
+            out += &format!("    [*] --> s{}\n", id(self.initial));
+
+            for t in self.transitions.iter() {
+                out += &format!(
+                    "    s{} --> s{} : {}\n",
+                    id(t.from),
+                    id(t.to),
+                    Self::label(t)
+                );
+            }
+
+            out
+        }
+    }
+
+    /// ID entre aspas para o DOT. O `Debug` de variantes com campos (`Retry(3)`,
+    /// `Error { code: 1 }`) não é um identificador válido sem elas.
+    fn quote(text: &str) -> String {
+        format!("\"{}\"", text.replace('"', "\\\""))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Wifi {
+        Disconnected,
+        Connecting,
+        Connected,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum WifiEvent {
+        Connect,
+        Success,
+        Failure,
+        Drop,
+    }
+
+    #[derive(Default)]
+    struct WifiContext {
+        retries: u32,
+        log: Vec<String>,
+    }
+
+    const MAX_RETRIES: u32 = 2;
+
+    fn wifi() -> StateMachine<Wifi, WifiEvent, WifiContext> {
+        use Wifi::*;
+        use WifiEvent::*;
+
+        StateMachine::builder(Disconnected)
+            .transition(Disconnected, Connect, Connecting)
+            .transition(Connecting, Success, Connected)
+            .action(|ctx: &mut WifiContext| ctx.retries = 0)
+            .transition(Connecting, Failure, Connecting)
+            .guard("can retry", |ctx: &WifiContext| ctx.retries < MAX_RETRIES)
+            .action(|ctx: &mut WifiContext| ctx.retries += 1)
+            .transition(Connecting, Failure, Disconnected)
+            .transition(Connected, Drop, Connecting)
+            .on_entry(Connecting, |ctx: &mut WifiContext| {
+                ctx.log.push("enter Connecting".to_owned())
+            })
+            .on_exit(Connecting, |ctx: &mut WifiContext| {
+                ctx.log.push("exit Connecting".to_owned())
+            })
+            .build(WifiContext::default())
+    }
+
+    #[test]
+    fn test_happy_path() {
+        let mut sm = wifi();
+
+        assert_eq!(sm.handle(WifiEvent::Connect), Ok(Wifi::Connecting));
+        assert_eq!(sm.handle(WifiEvent::Success), Ok(Wifi::Connected));
+        assert_eq!(sm.state(), Wifi::Connected);
+        assert_eq!(
+            sm.context().log,
+            vec!["enter Connecting", "exit Connecting"]
+        );
+    }
+
+    #[test]
+    fn test_no_transition_is_error() {
+        let mut sm = wifi();
+
+        assert!(!sm.can_handle(WifiEvent::Success));
+        assert_eq!(
+            sm.handle(WifiEvent::Success),
+            Err(TransitionError::NoTransition {
+                state: Wifi::Disconnected,
+                event: WifiEvent::Success
+            })
+        );
+        assert_eq!(sm.state(), Wifi::Disconnected);
+    }
+
+    #[test]
+    fn test_guard_selects_transition() {
+        let mut sm = wifi();
+        sm.handle(WifiEvent::Connect).unwrap();
+
+        // Duas tentativas permitidas pela guard, depois desiste
+        assert_eq!(sm.handle(WifiEvent::Failure), Ok(Wifi::Connecting));
+        assert_eq!(sm.handle(WifiEvent::Failure), Ok(Wifi::Connecting));
+        assert_eq!(sm.context().retries, 2);
+        assert_eq!(sm.handle(WifiEvent::Failure), Ok(Wifi::Disconnected));
+    }
+
+    #[test]
+    fn test_guard_rejected_is_error() {
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        enum Button {
+            Released,
+            Pressed,
+        }
+
+        // Debounce: só aceita a mudança após 3 amostras estáveis
+        let mut sm = StateMachine::builder(Button::Released)
+            .transition(Button::Released, (), Button::Pressed)
+            .guard("stable", |samples: &u32| *samples >= 3)
+            .build(0u32);
+
+        assert_eq!(
+            sm.handle(()),
+            Err(TransitionError::GuardRejected {
+                state: Button::Released,
+                event: ()
+            })
+        );
+
+        *sm.context_mut() = 3;
+        assert_eq!(sm.handle(()), Ok(Button::Pressed));
+    }
+
+    #[test]
+    fn test_self_transition_runs_exit_and_entry() {
+        let mut sm = wifi();
+        sm.handle(WifiEvent::Connect).unwrap();
+        sm.handle(WifiEvent::Failure).unwrap();
+
+        assert_eq!(
+            sm.context().log,
+            vec!["enter Connecting", "exit Connecting", "enter Connecting"]
+        );
+    }
+
+    #[test]
+    fn test_error_display() {
+        let err = TransitionError::GuardRejected {
+            state: Wifi::Connected,
+            event: WifiEvent::Connect,
+        };
+
+        assert_eq!(
+            err.to_string(),
+            "Transition from Connected on Connect rejected by guard"
+        );
+    }
+
+    #[test]
+    fn test_export_graphviz() {
+        let dot = wifi().to_graphviz();
+
+        assert!(dot.starts_with("digraph {\n"));
+        assert!(dot.contains("    __start -> \"Disconnected\";\n"));
+        assert!(dot
+            .contains("    \"Connecting\" -> \"Connecting\" [label = \"Failure [can retry]\"];\n"));
+        assert!(dot.ends_with("}\n"));
+    }
+
+    /// Estados com campos também viram IDs válidos, com as aspas internas escapadas.
+    #[test]
+    fn test_export_graphviz_quotes_ids() {
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        enum Debounce {
+            Idle,
+            Settling(u8),
+            Pressed { label: &'static str },
+        }
+
+        let dot = StateMachine::builder(Debounce::Idle)
+            .transition(Debounce::Idle, 'p', Debounce::Settling(3))
+            .transition(
+                Debounce::Settling(3),
+                't',
+                Debounce::Pressed { label: "ok" },
+            )
+            .build(())
+            .to_graphviz();
+
+        assert!(dot.contains("    \"Idle\" -> \"Settling(3)\" [label = \"'p'\"];\n"));
+        assert!(dot.contains(
+            "    \"Settling(3)\" -> \"Pressed { label: \\\"ok\\\" }\" [label = \"'t'\"];\n"
+        ));
+    }
+
+    #[test]
+    fn test_export_mermaid() {
+        let mermaid = wifi().to_mermaid();
+
+        assert_eq!(
+            mermaid,
+            "stateDiagram-v2\n\
+             \x20   state \"Disconnected\" as s0\n\
+             \x20   state \"Connecting\" as s1\n\
+             \x20   state \"Connected\" as s2\n\
+             \x20   [*] --> s0\n\
+             \x20   s0 --> s1 : Connect\n\
+             \x20   s1 --> s2 : Success\n\
+             \x20   s1 --> s1 : Failure [can retry]\n\
+             \x20   s1 --> s0 : Failure\n\
+             \x20   s2 --> s1 : Drop\n"
+        );
+    }
+
+    /// Estados com campos aparecem só na descrição; as transições usam os IDs.
+    #[test]
+    fn test_export_mermaid_data_states() {
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        enum Debounce {
+            Idle,
+            Settling(u8),
+            Pressed { label: &'static str },
+        }
+
+        let mermaid = StateMachine::builder(Debounce::Idle)
+            .transition(Debounce::Idle, 'p', Debounce::Settling(3))
+            .transition(
+                Debounce::Settling(3),
+                't',
+                Debounce::Pressed { label: "ok" },
+            )
+            .transition(Debounce::Pressed { label: "ok" }, 'r', Debounce::Idle)
+            .build(())
+            .to_mermaid();
+
+        assert_eq!(
+            mermaid,
+            "stateDiagram-v2\n\
+             \x20   state \"Idle\" as s0\n\
+             \x20   state \"Settling(3)\" as s1\n\
+             \x20   state \"Pressed { label: #quot;ok#quot; }\" as s2\n\
+             \x20   [*] --> s0\n\
+             \x20   s0 --> s1 : 'p'\n\
+             \x20   s1 --> s2 : 't'\n\
+             \x20   s2 --> s0 : 'r'\n"
+        );
+    }
+}
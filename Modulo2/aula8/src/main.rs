@@ -167,7 +167,7 @@ mod display {
         let mut file = File::new("world.txt");
 
         card.root_mut().add_entry(FSEntry::new_file("empty.txt"));
-        file.append("Hello, World!");
+        file.append("Hello, World!").unwrap();
         dir.add_entry(file.into());
         card.root_mut().add_entry(dir.into());
 
@@ -186,7 +186,7 @@ mod operator_overload {
         let mut file = File::new("world.txt");
 
         card.root_mut().add_entry(FSEntry::new_file("empty.txt"));
-        file.append("Hello, World!");
+        file.append("Hello, World!").unwrap();
         let dir = dir + file;
         card.root_mut().add_entry(dir.into());
 
@@ -200,7 +200,7 @@ mod operator_overload {
         let mut file = File::new("world.txt");
 
         card.root_mut().add_entry(FSEntry::new_file("empty.txt"));
-        file.append("Hello, World!");
+        file.append("Hello, World!").unwrap();
         dir += file;
         card.root_mut().add_entry(dir.into());
 
@@ -294,6 +294,72 @@ mod iterator_trait {
     }
 }
 
+mod metadata {
+    use crate::storage::{Directory, File};
+
+    #[test]
+    fn test_append_updates_modified() {
+        let mut file = File::new("world.txt");
+        let created = file.metadata().created();
+
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        file.append("Hello, World!").unwrap();
+
+        assert_eq!(file.size(), 13);
+        assert_eq!(file.metadata().created(), created);
+        assert!(file.metadata().modified() > created);
+
+        let modified = file.metadata().modified();
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        file += "!";
+
+        assert!(file.metadata().modified() > modified);
+    }
+
+    #[test]
+    fn test_read_only_refuses_append() {
+        let mut file = File::new("readme.txt")
+            .with_content("Hello".to_owned())
+            .read_only();
+        let modified = file.metadata().modified();
+
+        assert!(file.append(", World!").is_err());
+        file += ", World!";
+
+        assert_eq!(file.size(), 5);
+        assert_eq!(file.metadata().modified(), modified);
+    }
+
+    #[test]
+    fn test_hidden_by_name() {
+        assert!(File::new(".config").metadata().is_hidden());
+        assert!(!File::new("config").metadata().is_hidden());
+    }
+
+    #[test]
+    fn test_long_listing() {
+        let mut dir = Directory::new_empty("hello/");
+        dir += File::new("world.txt")
+            .with_content("Hello, World!".to_owned())
+            .with_kind("text/plain");
+        dir += File::new("locked.bin").read_only();
+        dir += File::new(".secret");
+        dir += Directory::new_empty("sub/");
+
+        let listing = format!("{:#}", dir);
+        let lines: Vec<_> = listing.lines().collect();
+
+        assert_eq!(dir.size(), 13);
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], "total 13");
+        assert!(lines[1].starts_with("-rw       13 "));
+        assert!(lines[1].ends_with("text/plain   world.txt"));
+        assert!(lines[2].starts_with("-r-        0 "));
+        assert!(lines[3].starts_with("drw        0 "));
+        assert!(!listing.contains(".secret"));
+    }
+}
+
 mod drop_trait {
     use crate::storage::{Directory, File, SDCard};
     use std::io::Write;
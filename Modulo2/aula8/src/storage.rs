@@ -1,5 +1,6 @@
 use std::fmt::{Display, Formatter};
 use std::ops::{Add, AddAssign};
+use std::time::SystemTime;
 
 pub struct SDCard {
     root: Directory,
@@ -30,12 +31,24 @@ pub struct Directory {
     name: String,
     // Default -> ""
     children: Vec<FSEntry>,
+    metadata: Metadata,
 }
 
 pub struct File {
     level: usize,
     name: String,
     content: String,
+    metadata: Metadata,
+}
+
+/// Informações comuns a arquivos e diretórios, semelhante ao que o `ls -l` exibe.
+pub struct Metadata {
+    created: SystemTime,
+    modified: SystemTime,
+    read_only: bool,
+    hidden: bool,
+    // Ex.: "text/plain"
+    kind: Option<String>,
 }
 
 pub enum FSEntry {
@@ -43,12 +56,64 @@ pub enum FSEntry {
     File(File),
 }
 
+impl Metadata {
+    pub fn new(name: &str) -> Self {
+        let now = SystemTime::now();
+
+        Self {
+            created: now,
+            modified: now,
+            read_only: false,
+            // Assim como no Unix, nomes iniciados com '.' são ocultos
+            hidden: name.starts_with('.'),
+            kind: None,
+        }
+    }
+
+    pub fn created(&self) -> SystemTime {
+        self.created
+    }
+
+    pub fn modified(&self) -> SystemTime {
+        self.modified
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    pub fn is_hidden(&self) -> bool {
+        self.hidden
+    }
+
+    pub fn kind(&self) -> Option<&str> {
+        self.kind.as_deref()
+    }
+
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.read_only = read_only;
+    }
+
+    pub fn set_hidden(&mut self, hidden: bool) {
+        self.hidden = hidden;
+    }
+
+    pub fn set_kind(&mut self, kind: Option<&str>) {
+        self.kind = kind.map(str::to_owned);
+    }
+
+    pub fn touch(&mut self) {
+        self.modified = SystemTime::now();
+    }
+}
+
 impl Directory {
     pub fn new_empty(name: &str) -> Self {
         Self {
             name: name.to_owned(),
             children: vec![],
             level: 0,
+            metadata: Metadata::new(name),
         }
     }
 
@@ -58,6 +123,7 @@ impl Directory {
             entry.set_level(self.level + 1);
         }
         self.children = entries;
+        self.metadata.touch();
         self
     }
 
@@ -65,6 +131,19 @@ impl Directory {
         &self.name
     }
 
+    pub fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    pub fn metadata_mut(&mut self) -> &mut Metadata {
+        &mut self.metadata
+    }
+
+    /// Soma do tamanho de todos os arquivos contidos no diretório.
+    pub fn size(&self) -> usize {
+        self.children.iter().map(FSEntry::size).sum()
+    }
+
     pub fn add_entry(&mut self, mut entry: FSEntry) {
         self.children
             .iter()
@@ -76,6 +155,7 @@ impl Directory {
             .or_else(|| {
                 entry.set_level(self.level + 1);
                 self.children.push(entry);
+                self.metadata.touch();
                 None
             });
     }
@@ -98,21 +178,53 @@ impl File {
             name: name.to_owned(),
             content: String::new(),
             level: 0,
+            metadata: Metadata::new(name),
         }
     }
 
     #[allow(unused)]
     pub fn with_content(mut self, content: String) -> Self {
         self.content = content;
+        self.metadata.touch();
         self
     }
 
+    #[allow(unused)]
+    pub fn with_kind(mut self, kind: &str) -> Self {
+        self.metadata.set_kind(Some(kind));
+        self
+    }
+
+    #[allow(unused)]
+    pub fn read_only(mut self) -> Self {
+        self.metadata.set_read_only(true);
+        self
+    }
+
+    pub fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    pub fn metadata_mut(&mut self) -> &mut Metadata {
+        &mut self.metadata
+    }
+
+    pub fn size(&self) -> usize {
+        self.content.len()
+    }
+
     pub fn set_level(&mut self, level: usize) {
         self.level = level;
     }
 
-    pub fn append(&mut self, content: &str) {
+    pub fn append(&mut self, content: &str) -> Result<(), String> {
+        if self.metadata.read_only {
+            return Err(format!("Cannot append to read-only file {}", self.name));
+        }
+
         self.content += content;
+        self.metadata.touch();
+        Ok(())
     }
 }
 
@@ -145,6 +257,20 @@ impl FSEntry {
             FSEntry::File(f) => f.level,
         }
     }
+
+    pub fn metadata(&self) -> &Metadata {
+        match self {
+            FSEntry::Directory(dir) => dir.metadata(),
+            FSEntry::File(f) => f.metadata(),
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        match self {
+            FSEntry::Directory(dir) => dir.size(),
+            FSEntry::File(f) => f.size(),
+        }
+    }
 }
 
 mod from_into {
@@ -204,7 +330,12 @@ mod display {
     }
 
     impl Display for Directory {
+        /// `{}` exibe a árvore completa e `{:#}` exibe uma listagem no estilo `ls -l`.
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            if f.alternate() {
+                return self.fmt_long(f);
+            }
+
             let mut children = String::new();
 
             for child in self.children.iter() {
@@ -216,6 +347,63 @@ mod display {
             write!(f, "{}{}{}", self.name, new_line, children)
         }
     }
+
+    impl Directory {
+        fn fmt_long(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            writeln!(f, "total {}", self.size())?;
+
+            // Assim como o `ls -l`, entradas ocultas não são listadas
+            for child in self.children.iter().filter(|x| !x.metadata().is_hidden()) {
+                let (kind, metadata) = match child {
+                    FSEntry::Directory(dir) => ('d', &dir.metadata),
+                    FSEntry::File(file) => ('-', &file.metadata),
+                };
+                let write = if metadata.read_only { '-' } else { 'w' };
+
+                writeln!(
+                    f,
+                    "{}r{} {:>8} {} {:<12} {}",
+                    kind,
+                    write,
+                    child.size(),
+                    format_time(metadata.modified),
+                    metadata.kind().unwrap_or("-"),
+                    child.name()
+                )?;
+            }
+
+            Ok(())
+        }
+    }
+
+    /// Formata o horário como `AAAA-MM-DD HH:MM` (UTC) sem depender de crates externas.
+    fn format_time(time: SystemTime) -> String {
+        let secs = time
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|x| x.as_secs() as i64)
+            .unwrap_or(0);
+        let (days, secs_of_day) = (secs.div_euclid(86400), secs.rem_euclid(86400));
+
+        // Conversão de dias desde 1970-01-01 para data civil (algoritmo de Howard Hinnant)
+        let z = days + 719468;
+        let era = z.div_euclid(146097);
+        let doe = z.rem_euclid(146097);
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = doy - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+        format!(
+            "{:04}-{:02}-{:02} {:02}:{:02}",
+            year,
+            month,
+            day,
+            secs_of_day / 3600,
+            secs_of_day % 3600 / 60
+        )
+    }
 }
 
 mod operator_overload {
@@ -244,9 +432,13 @@ mod operator_overload {
         }
     }
 
+    /// File += &str
+    ///
+    /// !!! Em um arquivo somente leitura não faz nada, já que `+=` não tem como retornar o
+    /// erro. Use [`File::append`] quando precisar saber se o conteúdo foi escrito.
     impl<'a> AddAssign<&'a str> for File {
         fn add_assign(&mut self, rhs: &'a str) {
-            self.append(rhs);
+            let _ = self.append(rhs);
         }
     }
 
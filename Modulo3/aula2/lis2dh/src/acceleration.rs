@@ -0,0 +1,300 @@
+//! Aceleração com unidade, independente do modo e do fundo de escala do sensor.
+//!
+//! [`Acc`](crate::Acc) é a amostra como ela sai dos registradores; [`Acceleration`] e
+//! [`AccVector`] são o que a aplicação usa para fazer contas, guardar e mostrar as leituras.
+//!
+//! !!! Como o crate é `no_std`, a raiz quadrada e o `atan2` vêm do `libm`. As versões do
+//!     `micromath` são aproximações rápidas demais para isso (a raiz chega a errar 2,5%).
+
+use core::fmt::{Display, Formatter};
+use core::ops::{Add, Mul, Neg, Sub};
+use micromath::vector::Vector3d;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::STANDARD_GRAVITY;
+
+/// Aceleração em um eixo, guardada em g.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(transparent))]
+pub struct Acceleration {
+    g: f32,
+}
+
+impl Acceleration {
+    pub const ZERO: Acceleration = Acceleration { g: 0.0 };
+
+    pub const fn from_g(g: f32) -> Self {
+        Acceleration { g }
+    }
+
+    pub fn from_mg(mg: f32) -> Self {
+        Acceleration { g: mg / 1000.0 }
+    }
+
+    pub fn from_m_s2(m_s2: f32) -> Self {
+        Acceleration {
+            g: m_s2 / STANDARD_GRAVITY,
+        }
+    }
+
+    pub fn g(&self) -> f32 {
+        self.g
+    }
+
+    pub fn mg(&self) -> f32 {
+        self.g * 1000.0
+    }
+
+    pub fn m_s2(&self) -> f32 {
+        self.g * STANDARD_GRAVITY
+    }
+
+    pub fn abs(&self) -> Self {
+        Acceleration {
+            g: libm::fabsf(self.g),
+        }
+    }
+}
+
+impl Display for Acceleration {
+    /// Mostra o valor em g com a unidade. Aceita a precisão do formato, por exemplo `{:.3}`.
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match f.precision() {
+            Some(precision) => write!(f, "{:.*} g", precision, self.g),
+            None => write!(f, "{} g", self.g),
+        }
+    }
+}
+
+impl Add for Acceleration {
+    type Output = Acceleration;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Acceleration::from_g(self.g + rhs.g)
+    }
+}
+
+impl Sub for Acceleration {
+    type Output = Acceleration;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Acceleration::from_g(self.g - rhs.g)
+    }
+}
+
+impl Neg for Acceleration {
+    type Output = Acceleration;
+
+    fn neg(self) -> Self::Output {
+        Acceleration::from_g(-self.g)
+    }
+}
+
+impl Mul<f32> for Acceleration {
+    type Output = Acceleration;
+
+    fn mul(self, rhs: f32) -> Self::Output {
+        Acceleration::from_g(self.g * rhs)
+    }
+}
+
+/// Aceleração nos três eixos do sensor.
+///
+/// Os ângulos supõem o sensor parado, ou seja, que a única aceleração medida é a gravidade. Com
+/// o sensor na horizontal e o eixo Z para cima, a leitura é `(0, 0, 1) g` e todos os ângulos são
+/// zero.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AccVector {
+    pub x: Acceleration,
+    pub y: Acceleration,
+    pub z: Acceleration,
+}
+
+impl AccVector {
+    pub fn new(x: Acceleration, y: Acceleration, z: Acceleration) -> Self {
+        AccVector { x, y, z }
+    }
+
+    pub fn from_g([x, y, z]: [f32; 3]) -> Self {
+        AccVector::new(
+            Acceleration::from_g(x),
+            Acceleration::from_g(y),
+            Acceleration::from_g(z),
+        )
+    }
+
+    pub fn g(&self) -> [f32; 3] {
+        [self.x.g(), self.y.g(), self.z.g()]
+    }
+
+    pub fn magnitude(&self) -> Acceleration {
+        let [x, y, z] = self.g();
+        Acceleration::from_g(libm::sqrtf(x * x + y * y + z * z))
+    }
+
+    /// Ângulo entre o eixo Z e a vertical, em radianos (0 a π).
+    pub fn tilt(&self) -> f32 {
+        let [x, y, z] = self.g();
+        libm::atan2f(libm::sqrtf(x * x + y * y), z)
+    }
+
+    /// Rotação em torno do eixo Y, em radianos (-π/2 a π/2). Positiva quando o eixo X aponta
+    /// para cima.
+    pub fn pitch(&self) -> f32 {
+        let [x, y, z] = self.g();
+        libm::atan2f(x, libm::sqrtf(y * y + z * z))
+    }
+
+    /// Rotação em torno do eixo X, em radianos (-π a π). Positiva quando o eixo Y aponta para
+    /// cima.
+    pub fn roll(&self) -> f32 {
+        let [_, y, z] = self.g();
+        libm::atan2f(y, z)
+    }
+}
+
+impl Display for AccVector {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let [x, y, z] = self.g();
+        match f.precision() {
+            Some(p) => write!(f, "<{:.*}, {:.*}, {:.*}> g", p, x, p, y, p, z),
+            None => write!(f, "<{}, {}, {}> g", x, y, z),
+        }
+    }
+}
+
+impl Add for AccVector {
+    type Output = AccVector;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        AccVector::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl Sub for AccVector {
+    type Output = AccVector;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        AccVector::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl Mul<f32> for AccVector {
+    type Output = AccVector;
+
+    fn mul(self, rhs: f32) -> Self::Output {
+        AccVector::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+/// Componentes em g.
+impl From<AccVector> for Vector3d<f32> {
+    fn from(acc: AccVector) -> Self {
+        let [x, y, z] = acc.g();
+        Vector3d { x, y, z }
+    }
+}
+
+/// Componentes em g.
+impl From<Vector3d<f32>> for AccVector {
+    fn from(vector: Vector3d<f32>) -> Self {
+        AccVector::from_g([vector.x, vector.y, vector.z])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::f32::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+    use micromath::vector::Vector;
+
+    const MAX_ERROR: f32 = 1e-3;
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!(
+            (actual - expected).abs() <= MAX_ERROR,
+            "{} != {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_units() {
+        let acc = Acceleration::from_m_s2(-STANDARD_GRAVITY);
+
+        assert_eq!(acc.g(), -1.0);
+        assert_eq!(acc.mg(), -1000.0);
+        assert_eq!(Acceleration::from_mg(250.0).g(), 0.25);
+        assert_eq!(acc.abs(), Acceleration::from_g(1.0));
+        assert_eq!(format!("{}", acc), "-1 g");
+        assert_eq!(format!("{:.2}", Acceleration::from_g(0.5)), "0.50 g");
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let a = AccVector::from_g([1.0, -2.0, 0.5]);
+        let b = AccVector::from_g([0.5, 0.5, 0.5]);
+
+        assert_eq!((a + b).g(), [1.5, -1.5, 1.0]);
+        assert_eq!((a - b).g(), [0.5, -2.5, 0.0]);
+        assert_eq!((b * 2.0).g(), [1.0, 1.0, 1.0]);
+        assert_eq!(-a.y, Acceleration::from_g(2.0));
+        assert_eq!(format!("{:.1}", a), "<1.0, -2.0, 0.5> g");
+    }
+
+    #[test]
+    fn test_magnitude() {
+        assert_close(AccVector::from_g([0.0, 3.0, 4.0]).magnitude().g(), 5.0);
+        assert_close(AccVector::from_g([0.0, 0.0, -1.0]).magnitude().g(), 1.0);
+    }
+
+    #[test]
+    fn test_angles() {
+        let flat = AccVector::from_g([0.0, 0.0, 1.0]);
+        assert_close(flat.tilt(), 0.0);
+        assert_close(flat.pitch(), 0.0);
+        assert_close(flat.roll(), 0.0);
+
+        // Eixo X para cima
+        let nose_up = AccVector::from_g([1.0, 0.0, 0.0]);
+        assert_close(nose_up.pitch(), FRAC_PI_2);
+        assert_close(nose_up.tilt(), FRAC_PI_2);
+
+        // Inclinado 45° para a esquerda (eixo Y subindo)
+        let rolled = AccVector::from_g([0.0, 0.707, 0.707]);
+        assert_close(rolled.roll(), FRAC_PI_4);
+        assert_close(rolled.tilt(), FRAC_PI_4);
+        assert_close(rolled.pitch(), 0.0);
+
+        // De cabeça para baixo
+        let upside_down = AccVector::from_g([0.0, 0.0, -1.0]);
+        assert_close(upside_down.tilt(), PI);
+    }
+
+    #[test]
+    fn test_vector3d_conversion() {
+        let acc = AccVector::from_g([0.25, -0.5, 1.0]);
+        let vector = Vector3d::from(acc);
+
+        assert_eq!((vector.x, vector.y, vector.z), (0.25, -0.5, 1.0));
+        assert_eq!(AccVector::from(vector), acc);
+
+        // As operações do micromath passam a valer para as leituras
+        let up = Vector3d::from(AccVector::from_g([0.0, 0.0, 1.0]));
+        assert_close(vector.dot(up), 1.0);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let acc = AccVector::from_g([0.25, -0.5, 1.0]);
+        let json = serde_json::to_string(&acc).unwrap();
+
+        assert_eq!(json, r#"{"x":0.25,"y":-0.5,"z":1.0}"#);
+        assert_eq!(serde_json::from_str::<AccVector>(&json).unwrap(), acc);
+    }
+}
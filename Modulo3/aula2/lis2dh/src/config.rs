@@ -0,0 +1,428 @@
+//! Configuração tipada dos registradores `CTRL_REG1` a `CTRL_REG4`.
+//!
+//! Cada parte da configuração sabe se converter para os bits do seu registrador e voltar, de modo
+//! que o driver só precisa escrever (ou ler) os quatro bytes de uma vez.
+
+/// Frequência de amostragem (`ODR[3:0]` do `CTRL_REG1`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DataRate {
+    #[default]
+    PowerDown,
+    Hz1,
+    Hz10,
+    Hz25,
+    Hz50,
+    Hz100,
+    Hz200,
+    Hz400,
+    /// Só existe no modo [`Mode::LowPower`].
+    Hz1620LowPower,
+    /// 1344 Hz nos modos normal e alta resolução, 5376 Hz no modo de baixo consumo.
+    Hz1344Or5376,
+}
+
+impl DataRate {
+    fn bits(self) -> u8 {
+        self as u8
+    }
+
+    fn from_bits(bits: u8) -> Self {
+        match bits {
+            1 => DataRate::Hz1,
+            2 => DataRate::Hz10,
+            3 => DataRate::Hz25,
+            4 => DataRate::Hz50,
+            5 => DataRate::Hz100,
+            6 => DataRate::Hz200,
+            7 => DataRate::Hz400,
+            8 => DataRate::Hz1620LowPower,
+            9 => DataRate::Hz1344Or5376,
+            _ => DataRate::PowerDown,
+        }
+    }
+
+    /// Frequência em Hz, que para as taxas mais altas depende do modo de operação.
+    pub fn hz(self, mode: Mode) -> f32 {
+        match self {
+            DataRate::PowerDown => 0.0,
+            DataRate::Hz1 => 1.0,
+            DataRate::Hz10 => 10.0,
+            DataRate::Hz25 => 25.0,
+            DataRate::Hz50 => 50.0,
+            DataRate::Hz100 => 100.0,
+            DataRate::Hz200 => 200.0,
+            DataRate::Hz400 => 400.0,
+            DataRate::Hz1620LowPower => 1620.0,
+            DataRate::Hz1344Or5376 if mode == Mode::LowPower => 5376.0,
+            DataRate::Hz1344Or5376 => 1344.0,
+        }
+    }
+}
+
+/// Modo de operação, que define a resolução das amostras (`LPen` no `CTRL_REG1` e `HR` no
+/// `CTRL_REG4`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Mode {
+    /// 8 bits
+    LowPower,
+    /// 10 bits
+    #[default]
+    Normal,
+    /// 12 bits
+    HighResolution,
+}
+
+impl Mode {
+    pub fn resolution_bits(self) -> u8 {
+        match self {
+            Mode::LowPower => 8,
+            Mode::Normal => 10,
+            Mode::HighResolution => 12,
+        }
+    }
+}
+
+/// Fundo de escala (`FS[1:0]` do `CTRL_REG4`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FullScale {
+    #[default]
+    G2,
+    G4,
+    G8,
+    G16,
+}
+
+impl FullScale {
+    pub fn g(self) -> f32 {
+        match self {
+            FullScale::G2 => 2.0,
+            FullScale::G4 => 4.0,
+            FullScale::G8 => 8.0,
+            FullScale::G16 => 16.0,
+        }
+    }
+
+    /// Sensibilidade em mg/dígito para o modo dado (tabela 4 do datasheet).
+    ///
+    /// !!! O ±16 g não é o dobro do ±8 g: a sensibilidade é 3x maior.
+    pub fn sensitivity_mg(self, mode: Mode) -> f32 {
+        let high_resolution = match self {
+            FullScale::G2 => 1.0,
+            FullScale::G4 => 2.0,
+            FullScale::G8 => 4.0,
+            FullScale::G16 => 12.0,
+        };
+
+        match mode {
+            Mode::HighResolution => high_resolution,
+            Mode::Normal => high_resolution * 4.0,
+            Mode::LowPower => high_resolution * 16.0,
+        }
+    }
+
+    /// Valor em mg de 1 LSB dos limiares de interrupção e clique.
+    pub fn threshold_mg(self) -> f32 {
+        match self {
+            FullScale::G2 => 16.0,
+            FullScale::G4 => 32.0,
+            FullScale::G8 => 62.0,
+            FullScale::G16 => 186.0,
+        }
+    }
+
+    /// Converte um limiar em mg para o valor de 7 bits dos registradores, saturando.
+    pub fn threshold(self, mg: f32) -> u8 {
+        let lsb = mg / self.threshold_mg() + 0.5;
+        if lsb <= 0.0 {
+            0
+        } else {
+            (lsb as u8).min(0x7F)
+        }
+    }
+
+    fn bits(self) -> u8 {
+        self as u8
+    }
+
+    fn from_bits(bits: u8) -> Self {
+        match bits & 0b11 {
+            0 => FullScale::G2,
+            1 => FullScale::G4,
+            2 => FullScale::G8,
+            _ => FullScale::G16,
+        }
+    }
+}
+
+/// Eixos habilitados (`Xen`, `Yen` e `Zen` do `CTRL_REG1`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Axes {
+    pub x: bool,
+    pub y: bool,
+    pub z: bool,
+}
+
+impl Axes {
+    pub const ALL: Axes = Axes {
+        x: true,
+        y: true,
+        z: true,
+    };
+
+    pub const NONE: Axes = Axes {
+        x: false,
+        y: false,
+        z: false,
+    };
+
+    fn bits(self) -> u8 {
+        (self.z as u8) << 2 | (self.y as u8) << 1 | self.x as u8
+    }
+
+    fn from_bits(bits: u8) -> Self {
+        Axes {
+            x: bits & 0b001 != 0,
+            y: bits & 0b010 != 0,
+            z: bits & 0b100 != 0,
+        }
+    }
+}
+
+impl Default for Axes {
+    fn default() -> Self {
+        Axes::ALL
+    }
+}
+
+/// Modo do filtro passa-altas (`HPM[1:0]` do `CTRL_REG2`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HighPassMode {
+    /// Reinicia o filtro ao ler o registrador `REFERENCE`.
+    #[default]
+    NormalWithReset,
+    /// Usa o valor de `REFERENCE` como referência para a filtragem.
+    Reference,
+    Normal,
+    /// Reinicia o filtro quando ocorre um evento de interrupção.
+    AutoResetOnInterrupt,
+}
+
+/// Filtro passa-altas interno (`CTRL_REG2`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct HighPass {
+    pub mode: HighPassMode,
+    /// `HPCF[2:1]`, a frequência de corte depende da ODR (tabela 33 do datasheet).
+    pub cutoff: u8,
+    /// Envia os dados filtrados para os registradores de saída e para a FIFO (`FDS`).
+    pub filtered_data: bool,
+    /// Aplica o filtro na detecção de clique.
+    pub click: bool,
+    /// Aplica o filtro nos geradores de interrupção 1 e 2.
+    pub interrupt1: bool,
+    pub interrupt2: bool,
+}
+
+impl HighPass {
+    fn bits(self) -> u8 {
+        (self.mode as u8) << 6
+            | (self.cutoff & 0b11) << 4
+            | (self.filtered_data as u8) << 3
+            | (self.click as u8) << 2
+            | (self.interrupt2 as u8) << 1
+            | self.interrupt1 as u8
+    }
+
+    fn from_bits(bits: u8) -> Self {
+        HighPass {
+            mode: match bits >> 6 {
+                0 => HighPassMode::NormalWithReset,
+                1 => HighPassMode::Reference,
+                2 => HighPassMode::Normal,
+                _ => HighPassMode::AutoResetOnInterrupt,
+            },
+            cutoff: (bits >> 4) & 0b11,
+            filtered_data: bits & (1 << 3) != 0,
+            click: bits & (1 << 2) != 0,
+            interrupt2: bits & (1 << 1) != 0,
+            interrupt1: bits & 1 != 0,
+        }
+    }
+}
+
+/// Fontes roteadas para o pino INT1 (`CTRL_REG3`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Int1Routing {
+    pub click: bool,
+    pub interrupt1: bool,
+    pub interrupt2: bool,
+    pub data_ready: bool,
+    pub fifo_watermark: bool,
+    pub fifo_overrun: bool,
+}
+
+impl Int1Routing {
+    fn bits(self) -> u8 {
+        (self.click as u8) << 7
+            | (self.interrupt1 as u8) << 6
+            | (self.interrupt2 as u8) << 5
+            | (self.data_ready as u8) << 4
+            | (self.fifo_watermark as u8) << 2
+            | (self.fifo_overrun as u8) << 1
+    }
+
+    fn from_bits(bits: u8) -> Self {
+        Int1Routing {
+            click: bits & (1 << 7) != 0,
+            interrupt1: bits & (1 << 6) != 0,
+            interrupt2: bits & (1 << 5) != 0,
+            data_ready: bits & (1 << 4) != 0,
+            fifo_watermark: bits & (1 << 2) != 0,
+            fifo_overrun: bits & (1 << 1) != 0,
+        }
+    }
+}
+
+/// Configuração completa dos registradores `CTRL_REG1` a `CTRL_REG4`.
+///
+/// O `Default` corresponde aos valores de reset do sensor (`CTRL_REG1 = 0x07`, os outros
+/// zerados): os três eixos habilitados e a frequência em `PowerDown`, até ser escolhida.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Config {
+    pub data_rate: DataRate,
+    pub mode: Mode,
+    pub scale: FullScale,
+    pub axes: Axes,
+    /// Com `BDU` ligado os bytes alto e baixo de uma amostra só são atualizados depois que os
+    /// dois forem lidos, evitando misturar metades de amostras diferentes.
+    pub block_data_update: bool,
+    pub high_pass: HighPass,
+    pub int1: Int1Routing,
+}
+
+/// `CTRL_REG4`: BDU, FS[1:0] e HR. Os bits BLE, ST e SIM não são usados pelo driver e ficam zerados.
+const BDU: u8 = 1 << 7;
+const HR: u8 = 1 << 3;
+/// `CTRL_REG1`: LPen
+const LPEN: u8 = 1 << 3;
+
+impl Config {
+    /// Verifica combinações que o sensor não aceita.
+    pub fn is_valid(&self) -> bool {
+        !(self.data_rate == DataRate::Hz1620LowPower && self.mode != Mode::LowPower)
+    }
+
+    /// Frequência de amostragem em Hz.
+    pub fn sample_rate(&self) -> f32 {
+        self.data_rate.hz(self.mode)
+    }
+
+    /// Valores de `CTRL_REG1` a `CTRL_REG4`, nessa ordem.
+    pub fn to_registers(&self) -> [u8; 4] {
+        let low_power = if self.mode == Mode::LowPower { LPEN } else { 0 };
+        let high_resolution = if self.mode == Mode::HighResolution {
+            HR
+        } else {
+            0
+        };
+        let bdu = if self.block_data_update { BDU } else { 0 };
+
+        [
+            self.data_rate.bits() << 4 | low_power | self.axes.bits(),
+            self.high_pass.bits(),
+            self.int1.bits(),
+            bdu | self.scale.bits() << 4 | high_resolution,
+        ]
+    }
+
+    pub fn from_registers([ctrl1, ctrl2, ctrl3, ctrl4]: [u8; 4]) -> Self {
+        let mode = match (ctrl1 & LPEN != 0, ctrl4 & HR != 0) {
+            (true, _) => Mode::LowPower,
+            (false, true) => Mode::HighResolution,
+            (false, false) => Mode::Normal,
+        };
+
+        Config {
+            data_rate: DataRate::from_bits(ctrl1 >> 4),
+            mode,
+            scale: FullScale::from_bits(ctrl4 >> 4),
+            axes: Axes::from_bits(ctrl1),
+            block_data_update: ctrl4 & BDU != 0,
+            high_pass: HighPass::from_bits(ctrl2),
+            int1: Int1Routing::from_bits(ctrl3),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reset_values() {
+        // CTRL_REG1 volta do reset com os três eixos habilitados
+        assert_eq!(Config::default().to_registers(), [0x07, 0, 0, 0]);
+        assert_eq!(Config::from_registers([0x07, 0, 0, 0]), Config::default());
+    }
+
+    #[test]
+    fn test_registers_round_trip() {
+        let config = Config {
+            data_rate: DataRate::Hz100,
+            mode: Mode::HighResolution,
+            scale: FullScale::G8,
+            axes: Axes {
+                x: true,
+                y: false,
+                z: true,
+            },
+            block_data_update: true,
+            high_pass: HighPass {
+                mode: HighPassMode::Normal,
+                cutoff: 1,
+                filtered_data: true,
+                ..Default::default()
+            },
+            int1: Int1Routing {
+                data_ready: true,
+                ..Default::default()
+            },
+        };
+
+        let registers = config.to_registers();
+
+        assert_eq!(registers, [0x55, 0x98, 0x10, 0xA8]);
+        assert_eq!(Config::from_registers(registers), config);
+    }
+
+    #[test]
+    fn test_low_power_only_rate() {
+        let mut config = Config {
+            data_rate: DataRate::Hz1620LowPower,
+            ..Default::default()
+        };
+        assert!(!config.is_valid());
+
+        config.mode = Mode::LowPower;
+        assert!(config.is_valid());
+        assert_eq!(config.to_registers()[0], 0x8F);
+    }
+
+    #[test]
+    fn test_sample_rate_depends_on_mode() {
+        let mut config = Config {
+            data_rate: DataRate::Hz1344Or5376,
+            ..Default::default()
+        };
+        assert_eq!(config.sample_rate(), 1344.0);
+
+        config.mode = Mode::LowPower;
+        assert_eq!(config.sample_rate(), 5376.0);
+    }
+
+    #[test]
+    fn test_sensitivity() {
+        assert_eq!(FullScale::G2.sensitivity_mg(Mode::HighResolution), 1.0);
+        assert_eq!(FullScale::G4.sensitivity_mg(Mode::Normal), 8.0);
+        assert_eq!(FullScale::G16.sensitivity_mg(Mode::LowPower), 192.0);
+    }
+}
@@ -0,0 +1,489 @@
+//! Eventos do sensor entregues de forma assíncrona, a partir de um pino de interrupção.
+//!
+//! Em vez de ler o sensor periodicamente, a aplicação espera o pino INT1 ou INT2 ser ativado e
+//! só então lê os registradores de fonte (`INTx_SRC`, `CLICK_SRC`, `FIFO_SRC_REG` e
+//! `STATUS_REG`) das fontes roteadas para aquele pino:
+//!
+//! ```ignore
+//! lis2dh.read_config().await?;
+//! let mut events = lis2dh.events(int1_pin, IntPin::Int1);
+//! loop {
+//!     match events.next().await? {
+//!         Event::Click(click) => ...,
+//!         Event::FifoWatermark(_) => {
+//!             let len = events.driver_mut().read_fifo(&mut samples).await?;
+//!             ...
+//!         }
+//!         _ => {}
+//!     }
+//! }
+//! ```
+//!
+//! !!! O roteamento e a polaridade dos pinos vêm da cópia que o driver guarda dos registradores.
+//! Com um sensor já configurado, chame [`Lis2dh::read_config`] antes.
+//!
+//! Com a feature `stream`, `Events::into_stream` entrega a mesma sequência como um
+//! `futures_core::Stream`, para combinar com outros streams (`select`, `filter`, `take` etc.).
+
+use crate::config::Int1Routing;
+use crate::fifo::FifoStatus;
+use crate::interrupt::{ClickSource, Int2Routing, Interrupt, InterruptSource};
+use crate::{register, Error, Lis2dh};
+use embedded_hal_async::digital::Wait;
+use embedded_hal_async::spi::SpiDevice;
+
+/// `ZYXDA` do `STATUS_REG`
+const ZYXDA: u8 = 1 << 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntPin {
+    Int1,
+    Int2,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    Interrupt1(InterruptSource),
+    Interrupt2(InterruptSource),
+    Click(ClickSource),
+    FifoWatermark(FifoStatus),
+    FifoOverrun(FifoStatus),
+    /// Nova amostra disponível nos registradores de saída.
+    DataReady,
+}
+
+/// Fontes que podem estar ligadas a um pino. Cada leitura do pino gera no máximo uma de cada.
+const SOURCES: usize = 6;
+
+#[derive(Default)]
+struct Sources {
+    click: bool,
+    interrupt1: bool,
+    interrupt2: bool,
+    fifo_watermark: bool,
+    fifo_overrun: bool,
+    data_ready: bool,
+}
+
+impl From<Int1Routing> for Sources {
+    fn from(routing: Int1Routing) -> Self {
+        Sources {
+            click: routing.click,
+            interrupt1: routing.interrupt1,
+            interrupt2: routing.interrupt2,
+            fifo_watermark: routing.fifo_watermark,
+            fifo_overrun: routing.fifo_overrun,
+            data_ready: routing.data_ready,
+        }
+    }
+}
+
+impl Sources {
+    fn is_empty(&self) -> bool {
+        !(self.click
+            || self.interrupt1
+            || self.interrupt2
+            || self.fifo_watermark
+            || self.fifo_overrun
+            || self.data_ready)
+    }
+}
+
+impl From<Int2Routing> for Sources {
+    fn from(routing: Int2Routing) -> Self {
+        Sources {
+            click: routing.click,
+            interrupt1: routing.interrupt1,
+            interrupt2: routing.interrupt2,
+            ..Default::default()
+        }
+    }
+}
+
+pub struct Events<'a, S, P> {
+    lis2dh: &'a mut Lis2dh<S>,
+    pin: P,
+    line: IntPin,
+    // Eventos já lidos do sensor e ainda não entregues, na ordem em que serão entregues
+    pending: [Option<Event>; SOURCES],
+}
+
+impl<S: SpiDevice> Lis2dh<S> {
+    /// Sequência de eventos das fontes roteadas para `line`, que deve estar ligado a `pin`.
+    ///
+    /// O roteamento usado é o configurado pelo driver em [`Lis2dh::route_int1`] e
+    /// [`Lis2dh::route_int2`], ou lido do sensor por [`Lis2dh::read_config`]. O `boot` e o
+    /// `activity` do INT2 não geram eventos.
+    pub fn events<P: Wait>(&mut self, pin: P, line: IntPin) -> Events<'_, S, P> {
+        Events {
+            lis2dh: self,
+            pin,
+            line,
+            pending: [None; SOURCES],
+        }
+    }
+}
+
+impl<S: SpiDevice, P: Wait> Events<'_, S, P> {
+    /// Espera o próximo evento.
+    ///
+    /// Retorna [`Error::NotRouted`] se nenhuma fonte de eventos estiver roteada para o pino: o
+    /// pino é lido por nível, então esperar por ele sem ter o que ler nunca terminaria.
+    pub async fn next(&mut self) -> Result<Event, Error<S::Error>> {
+        loop {
+            if let Some(event) = self.pending.iter_mut().find_map(Option::take) {
+                return Ok(event);
+            }
+            if self.sources().is_empty() {
+                return Err(Error::NotRouted);
+            }
+
+            // A polaridade do CTRL_REG6 vale para os dois pinos
+            if self.lis2dh.int2.active_low {
+                self.pin.wait_for_low().await
+            } else {
+                self.pin.wait_for_high().await
+            }
+            .map_err(|_| Error::Pin)?;
+
+            self.read_sources().await?;
+        }
+    }
+
+    /// Os eventos como um [`Stream`](futures_core::Stream) que nunca termina. Um erro de
+    /// leitura é entregue como `Err` e o stream continua esperando o pino.
+    ///
+    /// !!! `Events` não implementa `Stream` diretamente: o `poll_next` precisaria guardar a
+    /// espera do pino, que empresta o próprio `Events`. O `unfold` guarda esse futuro dentro do
+    /// stream, sem alocação.
+    #[cfg(feature = "stream")]
+    pub fn into_stream<'a>(
+        self,
+    ) -> impl futures_core::Stream<Item = Result<Event, Error<S::Error>>> + 'a
+    where
+        Self: 'a,
+    {
+        futures_util::stream::unfold(self, |mut events| async move {
+            let event = events.next().await;
+            Some((event, events))
+        })
+    }
+
+    /// O driver, por exemplo para esvaziar a FIFO depois de um [`Event::FifoWatermark`] sem
+    /// perder os eventos que ainda não foram entregues.
+    pub fn driver_mut(&mut self) -> &mut Lis2dh<S> {
+        self.lis2dh
+    }
+
+    /// Devolve o pino, por exemplo para voltar a usar o driver diretamente.
+    pub fn release(self) -> P {
+        self.pin
+    }
+
+    fn sources(&self) -> Sources {
+        match self.line {
+            IntPin::Int1 => Sources::from(self.lis2dh.config.int1),
+            IntPin::Int2 => Sources::from(self.lis2dh.int2),
+        }
+    }
+
+    async fn read_sources(&mut self) -> Result<(), Error<S::Error>> {
+        let sources = self.sources();
+        let mut pending = self.pending.iter_mut();
+        let mut push = |event| {
+            if let Some(slot) = pending.next() {
+                *slot = Some(event);
+            }
+        };
+
+        if sources.click {
+            let click = self.lis2dh.click_source().await?;
+            if click.active {
+                push(Event::Click(click));
+            }
+        }
+
+        if sources.interrupt1 {
+            let source = self.lis2dh.interrupt_source(Interrupt::One).await?;
+            if source.active {
+                push(Event::Interrupt1(source));
+            }
+        }
+
+        if sources.interrupt2 {
+            let source = self.lis2dh.interrupt_source(Interrupt::Two).await?;
+            if source.active {
+                push(Event::Interrupt2(source));
+            }
+        }
+
+        if sources.fifo_watermark || sources.fifo_overrun {
+            let status = self.lis2dh.fifo_status().await?;
+            if sources.fifo_watermark && status.watermark {
+                push(Event::FifoWatermark(status));
+            }
+            if sources.fifo_overrun && status.overrun {
+                push(Event::FifoOverrun(status));
+            }
+        }
+
+        if sources.data_ready {
+            let [status] = self.lis2dh.read::<1>(register::STATUS_REG).await?;
+            if status & ZYXDA != 0 {
+                push(Event::DataReady);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Axes, Config, DataRate, FullScale};
+    use crate::fifo::{FifoConfig, FifoMode, FIFO_SIZE};
+    use crate::interrupt::{ClickConfig, InterruptConfig};
+    use crate::sim::{Axis, Lis2dh12};
+    use crate::Acc;
+    use aula7::bus::gpio::PinState;
+    use aula7::bus::mock::{PinMock, PinTransaction};
+    use futures::executor::block_on;
+
+    fn high(times: usize) -> Vec<PinTransaction> {
+        vec![PinTransaction::Get(PinState::High); times]
+    }
+
+    #[test]
+    fn test_free_fall_and_click_on_int1() {
+        let mut sim = Lis2dh12::new();
+        let mut lis2dh = Lis2dh::new(&mut sim);
+        block_on(async {
+            lis2dh
+                .configure(Config {
+                    data_rate: DataRate::Hz100,
+                    ..Default::default()
+                })
+                .await?;
+            lis2dh
+                .configure_interrupt(
+                    Interrupt::One,
+                    InterruptConfig::free_fall(FullScale::G2.threshold(350.0), 1),
+                )
+                .await?;
+            lis2dh
+                .configure_click(ClickConfig {
+                    single: Axes::ALL,
+                    threshold: 20,
+                    latch: true,
+                    ..Default::default()
+                })
+                .await?;
+            lis2dh
+                .route_int1(Int1Routing {
+                    click: true,
+                    interrupt1: true,
+                    ..Default::default()
+                })
+                .await
+        })
+        .unwrap();
+
+        sim.set_acceleration([0.0, 0.0, 0.0]);
+        sim.click(Axis::Y, false, false);
+        assert!(sim.int1());
+
+        let pin = PinMock::new(&high(1));
+        let mut lis2dh = Lis2dh::new(&mut sim);
+        block_on(lis2dh.read_config()).unwrap();
+        let mut events = lis2dh.events(pin.clone(), IntPin::Int1);
+
+        // Uma única ativação do pino entrega os dois eventos
+        let Event::Click(click) = block_on(events.next()).unwrap() else {
+            panic!("expected click");
+        };
+        assert!(click.single && click.axes.y);
+        let Event::Interrupt1(fall) = block_on(events.next()).unwrap() else {
+            panic!("expected free fall");
+        };
+        assert!(fall.events.x_low && fall.events.y_low && fall.events.z_low);
+
+        pin.done();
+        assert!(!sim.int1());
+    }
+
+    #[test]
+    fn test_fifo_watermark_event() {
+        let mut sim = Lis2dh12::new();
+        let mut lis2dh = Lis2dh::new(&mut sim);
+        block_on(async {
+            lis2dh
+                .configure_fifo(FifoConfig {
+                    mode: FifoMode::Stream,
+                    watermark: 4,
+                    ..Default::default()
+                })
+                .await?;
+            lis2dh
+                .route_int1(Int1Routing {
+                    fifo_watermark: true,
+                    ..Default::default()
+                })
+                .await
+        })
+        .unwrap();
+
+        for _ in 0..5 {
+            sim.set_acceleration([0.0, 0.0, 1.0]);
+        }
+        assert!(sim.int1());
+
+        let mut lis2dh = Lis2dh::new(&mut sim);
+        block_on(lis2dh.read_config()).unwrap();
+        let mut events = lis2dh.events(PinMock::new(&high(1)), IntPin::Int1);
+
+        match block_on(events.next()).unwrap() {
+            Event::FifoWatermark(status) => assert_eq!(status.len(), 5),
+            event => panic!("unexpected {:?}", event),
+        }
+    }
+
+    #[test]
+    fn test_driver_mut_keeps_pending_events() {
+        let mut sim = Lis2dh12::new();
+        let mut lis2dh = Lis2dh::new(&mut sim);
+        block_on(async {
+            lis2dh
+                .configure_fifo(FifoConfig {
+                    mode: FifoMode::Stream,
+                    watermark: 4,
+                    ..Default::default()
+                })
+                .await?;
+            lis2dh
+                .route_int1(Int1Routing {
+                    fifo_watermark: true,
+                    fifo_overrun: true,
+                    ..Default::default()
+                })
+                .await
+        })
+        .unwrap();
+
+        for _ in 0..FIFO_SIZE {
+            sim.set_acceleration([0.0, 0.0, 1.0]);
+        }
+
+        let mut lis2dh = Lis2dh::new(&mut sim);
+        block_on(lis2dh.read_config()).unwrap();
+        let pin = PinMock::new(&high(1));
+        let mut events = lis2dh.events(pin.clone(), IntPin::Int1);
+
+        assert!(matches!(
+            block_on(events.next()),
+            Ok(Event::FifoWatermark(_))
+        ));
+        let mut samples = [(Acc::default(), Acc::default(), Acc::default()); FIFO_SIZE];
+        let len = block_on(events.driver_mut().read_fifo(&mut samples)).unwrap();
+        assert_eq!(len, FIFO_SIZE);
+
+        // O overrun lido junto com o watermark ainda é entregue, sem esperar o pino de novo
+        assert!(matches!(block_on(events.next()), Ok(Event::FifoOverrun(_))));
+        pin.done();
+    }
+
+    #[test]
+    fn test_int2_routing_read_from_device() {
+        let mut sim = Lis2dh12::new();
+        let mut lis2dh = Lis2dh::new(&mut sim);
+        block_on(async {
+            lis2dh
+                .configure_interrupt(
+                    Interrupt::Two,
+                    InterruptConfig::free_fall(FullScale::G2.threshold(350.0), 1),
+                )
+                .await?;
+            lis2dh
+                .route_int2(Int2Routing {
+                    interrupt2: true,
+                    active_low: true,
+                    ..Default::default()
+                })
+                .await
+        })
+        .unwrap();
+
+        sim.set_acceleration([0.0, 0.0, 0.0]);
+        assert!(sim.int2());
+
+        // Driver novo, como depois de um reset do MCU: o roteamento vem do CTRL_REG6
+        let mut lis2dh = Lis2dh::new(&mut sim);
+        block_on(lis2dh.read_config()).unwrap();
+        let pin = PinMock::new(&[PinTransaction::Get(PinState::Low)]);
+        let mut events = lis2dh.events(pin.clone(), IntPin::Int2);
+
+        assert!(matches!(block_on(events.next()), Ok(Event::Interrupt2(_))));
+        pin.done();
+    }
+
+    #[test]
+    fn test_not_routed() {
+        let mut sim = Lis2dh12::new();
+        let mut lis2dh = Lis2dh::new(&mut sim);
+        block_on(lis2dh.read_config()).unwrap();
+
+        let pin = PinMock::new(&[]);
+        let mut events = lis2dh.events(pin.clone(), IntPin::Int2);
+        assert_eq!(block_on(events.next()), Err(Error::NotRouted));
+        pin.done();
+    }
+
+    #[cfg(feature = "stream")]
+    #[test]
+    fn test_stream() {
+        use futures::StreamExt;
+
+        let mut sim = Lis2dh12::new();
+        block_on(Lis2dh::new(&mut sim).route_int1(Int1Routing {
+            data_ready: true,
+            ..Default::default()
+        }))
+        .unwrap();
+        sim.set_acceleration([0.0, 0.0, 1.0]);
+
+        let mut lis2dh = Lis2dh::new(&mut sim);
+        block_on(lis2dh.read_config()).unwrap();
+        let pin = PinMock::new(&high(1));
+        let events = lis2dh.events(pin.clone(), IntPin::Int1).into_stream();
+
+        let first: Vec<_> = block_on(events.take(1).collect());
+        assert_eq!(first, [Ok(Event::DataReady)]);
+        pin.done();
+    }
+
+    #[test]
+    fn test_data_ready_event() {
+        let mut sim = Lis2dh12::new();
+        block_on(Lis2dh::new(&mut sim).route_int1(Int1Routing {
+            data_ready: true,
+            ..Default::default()
+        }))
+        .unwrap();
+        assert!(!sim.int1());
+
+        sim.set_acceleration([0.0, 0.0, 1.0]);
+        assert!(sim.int1());
+
+        let mut lis2dh = Lis2dh::new(&mut sim);
+        block_on(lis2dh.read_config()).unwrap();
+        let pin = PinMock::new(&high(1));
+        let mut events = lis2dh.events(pin.clone(), IntPin::Int1);
+        assert_eq!(block_on(events.next()), Ok(Event::DataReady));
+        drop(events);
+
+        // Ler a amostra desliga o ZYXDA
+        block_on(lis2dh.read_all()).unwrap();
+        assert!(!sim.int1());
+        pin.done();
+    }
+}
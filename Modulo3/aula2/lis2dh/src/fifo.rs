@@ -0,0 +1,279 @@
+//! FIFO de 32 amostras do LIS2DH12 (`FIFO_CTRL_REG` e `FIFO_SRC_REG`).
+//!
+//! Com a FIFO habilitada, uma leitura com auto incremento a partir de `OUT_X_L` volta para
+//! `OUT_X_L` depois de `OUT_Z_H` e já entrega a próxima amostra. Assim o driver esvazia a FIFO
+//! inteira em uma única transação SPI, em vez de uma transação por amostra.
+
+use crate::{register, Acc, Error, Lis2dh};
+use embedded_hal_async::spi::SpiDevice;
+
+/// Quantidade de amostras que cabem na FIFO.
+pub const FIFO_SIZE: usize = 32;
+
+/// Bytes de uma amostra com os três eixos.
+const SAMPLE_SIZE: usize = 6;
+
+/// `FIFO_EN` do `CTRL_REG5`
+pub(crate) const FIFO_EN: u8 = 1 << 6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FifoMode {
+    /// FIFO desligada, os registradores de saída têm sempre a última amostra.
+    #[default]
+    Bypass,
+    /// Enche a FIFO e para de coletar quando ela fica cheia.
+    Fifo,
+    /// Enche a FIFO e, quando cheia, descarta a amostra mais antiga.
+    Stream,
+    /// Funciona como `Stream` até o gerador de interrupção escolhido em [`FifoTrigger`] disparar,
+    /// a partir daí como `Fifo`. Útil para guardar o que aconteceu antes de um evento.
+    StreamToFifo,
+}
+
+/// Gerador de interrupção que dispara o modo [`FifoMode::StreamToFifo`] (bit `TR`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FifoTrigger {
+    #[default]
+    Interrupt1,
+    Interrupt2,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FifoConfig {
+    pub mode: FifoMode,
+    /// Nível da FIFO (0 a 31) a partir do qual o bit `WTM` é ligado.
+    pub watermark: u8,
+    pub trigger: FifoTrigger,
+}
+
+impl FifoConfig {
+    pub fn bits(&self) -> u8 {
+        (self.mode as u8) << 6 | (self.trigger as u8) << 5 | (self.watermark & 0x1F)
+    }
+
+    pub fn from_bits(bits: u8) -> Self {
+        FifoConfig {
+            mode: match bits >> 6 {
+                0 => FifoMode::Bypass,
+                1 => FifoMode::Fifo,
+                2 => FifoMode::Stream,
+                _ => FifoMode::StreamToFifo,
+            },
+            watermark: bits & 0x1F,
+            trigger: if bits & (1 << 5) != 0 {
+                FifoTrigger::Interrupt2
+            } else {
+                FifoTrigger::Interrupt1
+            },
+        }
+    }
+}
+
+/// Conteúdo do `FIFO_SRC_REG`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FifoStatus {
+    /// O nível da FIFO passou do `watermark`.
+    pub watermark: bool,
+    /// A FIFO está cheia (32 amostras não lidas). No modo `Stream`, a próxima amostra sobrescreve
+    /// a mais antiga.
+    pub overrun: bool,
+    pub empty: bool,
+    // FSS[4:0], só tem 5 bits, então não representa a FIFO cheia
+    unread: u8,
+}
+
+impl FifoStatus {
+    pub fn from_bits(bits: u8) -> Self {
+        FifoStatus {
+            watermark: bits & (1 << 7) != 0,
+            overrun: bits & (1 << 6) != 0,
+            empty: bits & (1 << 5) != 0,
+            unread: bits & 0x1F,
+        }
+    }
+
+    /// Quantidade de amostras não lidas.
+    pub fn len(&self) -> usize {
+        if self.overrun {
+            FIFO_SIZE
+        } else {
+            self.unread as usize
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.empty
+    }
+}
+
+impl<S: SpiDevice> Lis2dh<S> {
+    /// Configura a FIFO. Qualquer modo diferente de `Bypass` também liga o `FIFO_EN`.
+    ///
+    /// !!! Para trocar entre `Fifo` e `Stream` o datasheet recomenda passar por `Bypass`, que
+    ///     também é a forma de limpar a FIFO e o `overrun`.
+    pub async fn configure_fifo(&mut self, fifo: FifoConfig) -> Result<(), Error<S::Error>> {
+        let enable = if fifo.mode == FifoMode::Bypass {
+            0
+        } else {
+            FIFO_EN
+        };
+
+        self.modify(register::CTRL_REG5, FIFO_EN, enable).await?;
+        self.write(register::FIFO_CTRL_REG, &[fifo.bits()]).await?;
+
+        Ok(())
+    }
+
+    pub async fn fifo_status(&mut self) -> Result<FifoStatus, Error<S::Error>> {
+        let [bits] = self.read::<1>(register::FIFO_SRC_REG).await?;
+        Ok(FifoStatus::from_bits(bits))
+    }
+
+    /// Lê as amostras disponíveis na FIFO, até `buf.len()`, em uma única transação.
+    ///
+    /// Retorna quantas amostras foram escritas em `buf`.
+    pub async fn read_fifo(
+        &mut self,
+        buf: &mut [(Acc, Acc, Acc)],
+    ) -> Result<usize, Error<S::Error>> {
+        let status = self.fifo_status().await?;
+        let len = status.len().min(buf.len());
+        if len == 0 {
+            return Ok(0);
+        }
+
+        let mut bytes = [0; FIFO_SIZE * SAMPLE_SIZE];
+        let bytes = &mut bytes[..len * SAMPLE_SIZE];
+        self.read_into(register::OUT_X_L, bytes).await?;
+
+        for (sample, bytes) in buf.iter_mut().zip(bytes.chunks_exact(SAMPLE_SIZE)) {
+            *sample = (
+                self.acc(&bytes[..2]),
+                self.acc(&bytes[2..4]),
+                self.acc(&bytes[4..]),
+            );
+        }
+
+        Ok(len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sim::Lis2dh12;
+    use aula7::bus::mock::{SpiMock, SpiTransaction};
+    use futures::executor::block_on;
+
+    fn stream(watermark: u8) -> FifoConfig {
+        FifoConfig {
+            mode: FifoMode::Stream,
+            watermark,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_fifo_config_bits() {
+        let config = FifoConfig {
+            mode: FifoMode::StreamToFifo,
+            watermark: 10,
+            trigger: FifoTrigger::Interrupt2,
+        };
+
+        assert_eq!(config.bits(), 0xEA);
+        assert_eq!(FifoConfig::from_bits(0xEA), config);
+    }
+
+    #[test]
+    fn test_status_len() {
+        assert_eq!(FifoStatus::from_bits(0x20).len(), 0);
+        assert_eq!(FifoStatus::from_bits(0x85).len(), 5);
+        assert_eq!(FifoStatus::from_bits(0xDF).len(), FIFO_SIZE);
+    }
+
+    #[test]
+    fn test_read_fifo_single_transaction() {
+        let mut sim = Lis2dh12::new();
+        let mut lis2dh = Lis2dh::new(&mut sim);
+        block_on(lis2dh.configure_fifo(stream(0))).unwrap();
+
+        for i in 1..=3 {
+            sim.set_raw_acceleration([i << 6, -(i << 6), 0]);
+        }
+
+        let mut lis2dh = Lis2dh::new(&mut sim);
+        let mut buf = [(Acc::default(), Acc::default(), Acc::default()); 8];
+        let len = block_on(lis2dh.read_fifo(&mut buf)).unwrap();
+
+        assert_eq!(len, 3);
+        let counts: Vec<_> = buf[..len]
+            .iter()
+            .map(|(x, y, _)| (x.counts(), y.counts()))
+            .collect();
+        assert_eq!(counts, vec![(1, -1), (2, -2), (3, -3)]);
+        assert!(block_on(lis2dh.fifo_status()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_stream_overrun_keeps_newest() {
+        let mut sim = Lis2dh12::new();
+        block_on(Lis2dh::new(&mut sim).configure_fifo(stream(16))).unwrap();
+
+        for i in 0..40 {
+            sim.set_raw_acceleration([i << 6, 0, 0]);
+        }
+
+        let mut lis2dh = Lis2dh::new(&mut sim);
+        let status = block_on(lis2dh.fifo_status()).unwrap();
+        assert!(status.overrun && status.watermark);
+        assert_eq!(status.len(), FIFO_SIZE);
+
+        let mut buf = [(Acc::default(), Acc::default(), Acc::default()); FIFO_SIZE];
+        assert_eq!(block_on(lis2dh.read_fifo(&mut buf)), Ok(FIFO_SIZE));
+        assert_eq!(buf[0].0.counts(), 8);
+        assert_eq!(buf[FIFO_SIZE - 1].0.counts(), 39);
+    }
+
+    #[test]
+    fn test_fifo_mode_stops_when_full() {
+        let mut sim = Lis2dh12::new();
+        block_on(Lis2dh::new(&mut sim).configure_fifo(FifoConfig {
+            mode: FifoMode::Fifo,
+            ..Default::default()
+        }))
+        .unwrap();
+
+        for i in 0..40 {
+            sim.set_raw_acceleration([i << 6, 0, 0]);
+        }
+
+        let mut lis2dh = Lis2dh::new(&mut sim);
+        let mut buf = [(Acc::default(), Acc::default(), Acc::default()); 2];
+        assert_eq!(block_on(lis2dh.read_fifo(&mut buf)), Ok(2));
+        assert_eq!(buf[0].0.counts(), 0);
+        assert_eq!(buf[1].0.counts(), 1);
+        assert_eq!(block_on(lis2dh.fifo_status()).unwrap().len(), 30);
+    }
+
+    /// Confere que a leitura das amostras é uma única transação com auto incremento.
+    #[test]
+    fn test_read_fifo_bus_frames() {
+        let spi = SpiMock::new(&[
+            SpiTransaction::Select,
+            SpiTransaction::Write(vec![0xEF]),
+            SpiTransaction::Read(vec![0x82]),
+            SpiTransaction::Deselect,
+            SpiTransaction::Select,
+            SpiTransaction::Write(vec![0xE8]),
+            SpiTransaction::Read(vec![0x40, 0, 0, 0, 0, 0, 0x80, 0, 0, 0, 0, 0]),
+            SpiTransaction::Deselect,
+        ]);
+        let mut lis2dh = Lis2dh::new(spi.clone());
+        let mut buf = [(Acc::default(), Acc::default(), Acc::default()); 4];
+
+        assert_eq!(block_on(lis2dh.read_fifo(&mut buf)), Ok(2));
+        assert_eq!(buf[1].0.counts(), 2);
+        spi.done();
+    }
+}
@@ -0,0 +1,489 @@
+//! Geradores de interrupção, detecção de clique e roteamento para os pinos INT1 e INT2.
+//!
+//! O LIS2DH12 tem dois geradores de interrupção iguais (`INT1_*` e `INT2_*`) que comparam cada
+//! eixo com um limiar, além de um detector de clique simples e duplo. Cada fonte pode ser ligada
+//! aos pinos INT1 (`CTRL_REG3`, ver [`Int1Routing`](crate::config::Int1Routing)) e INT2
+//! (`CTRL_REG6`, ver [`Int2Routing`]).
+
+use crate::config::{Axes, Config, Int1Routing};
+use crate::{register, Error, Lis2dh};
+use embedded_hal_async::spi::SpiDevice;
+
+/// Qual dos dois geradores de interrupção.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interrupt {
+    One,
+    Two,
+}
+
+impl Interrupt {
+    /// Endereço do `INTx_CFG`; `SRC`, `THS` e `DURATION` vêm logo em seguida.
+    fn base(self) -> u8 {
+        match self {
+            Interrupt::One => register::INT1_CFG,
+            Interrupt::Two => register::INT2_CFG,
+        }
+    }
+
+    /// Bits `LIR_INTx` e `D4D_INTx` do `CTRL_REG5`.
+    fn ctrl5_bits(self) -> (u8, u8) {
+        match self {
+            Interrupt::One => (1 << 3, 1 << 2),
+            Interrupt::Two => (1 << 1, 1 << 0),
+        }
+    }
+}
+
+/// Como os eventos dos eixos são combinados (`AOI` e `6D` do `INTx_CFG`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Combination {
+    /// Qualquer evento habilitado dispara.
+    #[default]
+    Or,
+    /// Todos os eventos habilitados precisam acontecer juntos.
+    And,
+    /// Dispara quando a orientação muda.
+    Movement6D,
+    /// Dispara enquanto o sensor está em uma orientação conhecida.
+    Position6D,
+}
+
+/// Eventos de cada eixo: acima (`high`) ou abaixo (`low`) do limiar, em valor absoluto.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AxisEvents {
+    pub x_low: bool,
+    pub x_high: bool,
+    pub y_low: bool,
+    pub y_high: bool,
+    pub z_low: bool,
+    pub z_high: bool,
+}
+
+impl AxisEvents {
+    pub const ALL_LOW: AxisEvents = AxisEvents {
+        x_low: true,
+        x_high: false,
+        y_low: true,
+        y_high: false,
+        z_low: true,
+        z_high: false,
+    };
+
+    pub const ALL_HIGH: AxisEvents = AxisEvents {
+        x_low: false,
+        x_high: true,
+        y_low: false,
+        y_high: true,
+        z_low: false,
+        z_high: true,
+    };
+
+    pub fn bits(&self) -> u8 {
+        (self.z_high as u8) << 5
+            | (self.z_low as u8) << 4
+            | (self.y_high as u8) << 3
+            | (self.y_low as u8) << 2
+            | (self.x_high as u8) << 1
+            | self.x_low as u8
+    }
+
+    pub fn from_bits(bits: u8) -> Self {
+        AxisEvents {
+            x_low: bits & (1 << 0) != 0,
+            x_high: bits & (1 << 1) != 0,
+            y_low: bits & (1 << 2) != 0,
+            y_high: bits & (1 << 3) != 0,
+            z_low: bits & (1 << 4) != 0,
+            z_high: bits & (1 << 5) != 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct InterruptConfig {
+    pub combination: Combination,
+    pub events: AxisEvents,
+    /// Limiar de 7 bits, ver
+    /// [`FullScale::threshold`](crate::config::FullScale::threshold).
+    pub threshold: u8,
+    /// Número de amostras (1/ODR) que a condição precisa durar.
+    pub duration: u8,
+    /// Mantém a interrupção ativa até o `INTx_SRC` ser lido (`LIR_INTx`).
+    pub latch: bool,
+}
+
+impl InterruptConfig {
+    /// Queda livre: os três eixos abaixo do limiar ao mesmo tempo. Valores típicos são 350 mg
+    /// durante 30 ms.
+    pub fn free_fall(threshold: u8, duration: u8) -> Self {
+        InterruptConfig {
+            combination: Combination::And,
+            events: AxisEvents::ALL_LOW,
+            threshold,
+            duration,
+            latch: true,
+        }
+    }
+
+    /// Despertar: qualquer eixo acima do limiar. Normalmente usado com o filtro passa-altas
+    /// ligado para o gerador (ver [`HighPass`](crate::config::HighPass)), para ignorar a
+    /// gravidade.
+    pub fn wake_up(threshold: u8, duration: u8) -> Self {
+        InterruptConfig {
+            combination: Combination::Or,
+            events: AxisEvents::ALL_HIGH,
+            threshold,
+            duration,
+            latch: true,
+        }
+    }
+
+    /// `INTx_CFG`
+    pub fn cfg_bits(&self) -> u8 {
+        let combination = match self.combination {
+            Combination::Or => 0b00,
+            Combination::And => 0b10,
+            Combination::Movement6D => 0b01,
+            Combination::Position6D => 0b11,
+        };
+        combination << 6 | self.events.bits()
+    }
+}
+
+/// Conteúdo do `INTx_SRC`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct InterruptSource {
+    pub active: bool,
+    pub events: AxisEvents,
+}
+
+impl InterruptSource {
+    pub fn from_bits(bits: u8) -> Self {
+        InterruptSource {
+            active: bits & (1 << 6) != 0,
+            events: AxisEvents::from_bits(bits),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClickConfig {
+    pub single: Axes,
+    pub double: Axes,
+    /// Limiar de 7 bits, ver
+    /// [`FullScale::threshold`](crate::config::FullScale::threshold).
+    pub threshold: u8,
+    /// Tempo máximo acima do limiar para ser considerado um clique, em amostras.
+    pub time_limit: u8,
+    /// Tempo após o primeiro clique em que o detector fica cego, em amostras.
+    pub latency: u8,
+    /// Janela para o segundo clique de um clique duplo, em amostras.
+    pub window: u8,
+    /// Mantém a interrupção ativa até o `CLICK_SRC` ser lido.
+    pub latch: bool,
+}
+
+impl Default for ClickConfig {
+    fn default() -> Self {
+        ClickConfig {
+            single: Axes::NONE,
+            double: Axes::NONE,
+            threshold: 0,
+            time_limit: 0,
+            latency: 0,
+            window: 0,
+            latch: false,
+        }
+    }
+}
+
+impl ClickConfig {
+    /// `CLICK_CFG`
+    pub fn cfg_bits(&self) -> u8 {
+        let mut bits = 0;
+        for (i, (single, double)) in [
+            (self.single.x, self.double.x),
+            (self.single.y, self.double.y),
+            (self.single.z, self.double.z),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            bits |= (single as u8) << (2 * i) | (double as u8) << (2 * i + 1);
+        }
+        bits
+    }
+}
+
+/// Conteúdo do `CLICK_SRC`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClickSource {
+    pub active: bool,
+    pub single: bool,
+    pub double: bool,
+    /// O clique foi no sentido negativo do eixo.
+    pub negative: bool,
+    pub axes: Axes,
+}
+
+impl ClickSource {
+    pub fn from_bits(bits: u8) -> Self {
+        ClickSource {
+            active: bits & (1 << 6) != 0,
+            double: bits & (1 << 5) != 0,
+            single: bits & (1 << 4) != 0,
+            negative: bits & (1 << 3) != 0,
+            axes: Axes {
+                x: bits & (1 << 0) != 0,
+                y: bits & (1 << 1) != 0,
+                z: bits & (1 << 2) != 0,
+            },
+        }
+    }
+}
+
+/// Fontes roteadas para o pino INT2 (`CTRL_REG6`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Int2Routing {
+    pub click: bool,
+    pub interrupt1: bool,
+    pub interrupt2: bool,
+    pub boot: bool,
+    pub activity: bool,
+    /// Inverte a polaridade dos dois pinos (`INT_POLARITY`).
+    pub active_low: bool,
+}
+
+impl Int2Routing {
+    pub fn bits(&self) -> u8 {
+        (self.click as u8) << 7
+            | (self.interrupt1 as u8) << 6
+            | (self.interrupt2 as u8) << 5
+            | (self.boot as u8) << 4
+            | (self.activity as u8) << 3
+            | (self.active_low as u8) << 1
+    }
+
+    pub fn from_bits(bits: u8) -> Self {
+        Int2Routing {
+            click: bits & (1 << 7) != 0,
+            interrupt1: bits & (1 << 6) != 0,
+            interrupt2: bits & (1 << 5) != 0,
+            boot: bits & (1 << 4) != 0,
+            activity: bits & (1 << 3) != 0,
+            active_low: bits & (1 << 1) != 0,
+        }
+    }
+}
+
+/// `LIR_Click` do `CLICK_THS`
+pub(crate) const LIR_CLICK: u8 = 1 << 7;
+
+impl<S: SpiDevice> Lis2dh<S> {
+    pub async fn configure_interrupt(
+        &mut self,
+        interrupt: Interrupt,
+        config: InterruptConfig,
+    ) -> Result<(), Error<S::Error>> {
+        let (lir, d4d) = interrupt.ctrl5_bits();
+        let latch = if config.latch { lir } else { 0 };
+
+        self.modify(register::CTRL_REG5, lir | d4d, latch).await?;
+        self.write(interrupt.base(), &[config.cfg_bits()]).await?;
+        // INTx_THS e INTx_DURATION ficam depois do INTx_SRC, que é somente leitura
+        self.write(
+            interrupt.base() + 2,
+            &[config.threshold & 0x7F, config.duration & 0x7F],
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Lê o `INTx_SRC`. Com `latch`, a leitura também libera a interrupção.
+    pub async fn interrupt_source(
+        &mut self,
+        interrupt: Interrupt,
+    ) -> Result<InterruptSource, Error<S::Error>> {
+        let [bits] = self.read::<1>(interrupt.base() + 1).await?;
+        Ok(InterruptSource::from_bits(bits))
+    }
+
+    pub async fn configure_click(&mut self, config: ClickConfig) -> Result<(), Error<S::Error>> {
+        let latch = if config.latch { LIR_CLICK } else { 0 };
+
+        self.write(register::CLICK_CFG, &[config.cfg_bits()])
+            .await?;
+        self.write(
+            register::CLICK_THS,
+            &[
+                latch | (config.threshold & 0x7F),
+                config.time_limit & 0x7F,
+                config.latency,
+                config.window,
+            ],
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Lê o `CLICK_SRC`. Com `latch`, a leitura também libera a interrupção.
+    pub async fn click_source(&mut self) -> Result<ClickSource, Error<S::Error>> {
+        let [bits] = self.read::<1>(register::CLICK_SRC).await?;
+        Ok(ClickSource::from_bits(bits))
+    }
+
+    /// Escolhe as fontes ligadas ao pino INT1. Faz parte do `CTRL_REG3`, por isso fica em
+    /// [`Config::int1`](crate::config::Config::int1).
+    pub async fn route_int1(&mut self, routing: Int1Routing) -> Result<(), Error<S::Error>> {
+        self.configure(Config {
+            int1: routing,
+            ..self.config
+        })
+        .await
+    }
+
+    pub async fn route_int2(&mut self, routing: Int2Routing) -> Result<(), Error<S::Error>> {
+        self.write(register::CTRL_REG6, &[routing.bits()]).await?;
+        self.int2 = routing;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{DataRate, FullScale};
+    use crate::sim::{Axis, Lis2dh12};
+    use futures::executor::block_on;
+
+    #[test]
+    fn test_threshold_conversion() {
+        assert_eq!(FullScale::G2.threshold(350.0), 22);
+        assert_eq!(FullScale::G16.threshold(186.0), 1);
+        assert_eq!(FullScale::G2.threshold(5000.0), 0x7F);
+        assert_eq!(FullScale::G2.threshold(-1.0), 0);
+    }
+
+    #[test]
+    fn test_interrupt_registers() {
+        let mut sim = Lis2dh12::new();
+        let mut lis2dh = Lis2dh::new(&mut sim);
+
+        block_on(lis2dh.configure_interrupt(Interrupt::Two, InterruptConfig::free_fall(22, 3)))
+            .unwrap();
+
+        assert_eq!(sim.register(register::INT2_CFG), 0x95);
+        assert_eq!(sim.register(register::INT2_THS), 22);
+        assert_eq!(sim.register(register::INT2_DURATION), 3);
+        assert_eq!(sim.register(register::CTRL_REG5), 1 << 1);
+    }
+
+    #[test]
+    fn test_free_fall_detection() {
+        let mut sim = Lis2dh12::new();
+        let mut lis2dh = Lis2dh::new(&mut sim);
+        block_on(lis2dh.configure(Config {
+            data_rate: DataRate::Hz100,
+            ..Default::default()
+        }))
+        .unwrap();
+        let threshold = FullScale::G2.threshold(350.0);
+        block_on(
+            lis2dh.configure_interrupt(Interrupt::One, InterruptConfig::free_fall(threshold, 2)),
+        )
+        .unwrap();
+        block_on(lis2dh.route_int1(Int1Routing {
+            interrupt1: true,
+            ..Default::default()
+        }))
+        .unwrap();
+
+        // Parado: a gravidade mantém o Z acima do limiar
+        sim.set_acceleration([0.0, 0.0, 1.0]);
+        sim.set_acceleration([0.0, 0.0, 1.0]);
+        assert!(!sim.int1());
+
+        // Em queda todos os eixos ficam perto de zero, mas só dispara após 2 amostras
+        sim.set_acceleration([0.05, -0.1, 0.1]);
+        assert!(!sim.int1());
+        sim.set_acceleration([0.05, -0.1, 0.1]);
+        assert!(sim.int1());
+
+        // Travada até ler o INT1_SRC, mesmo depois que a queda termina
+        sim.set_acceleration([0.0, 0.0, 3.0]);
+        let mut lis2dh = Lis2dh::new(&mut sim);
+        let source = block_on(lis2dh.interrupt_source(Interrupt::One)).unwrap();
+        assert!(source.active);
+        assert!(source.events.z_low);
+        assert!(!sim.int1());
+    }
+
+    #[test]
+    fn test_wake_up_detection() {
+        let mut sim = Lis2dh12::new();
+        let mut lis2dh = Lis2dh::new(&mut sim);
+        block_on(lis2dh.configure_interrupt(
+            Interrupt::Two,
+            InterruptConfig {
+                latch: false,
+                ..InterruptConfig::wake_up(FullScale::G2.threshold(500.0), 0)
+            },
+        ))
+        .unwrap();
+        block_on(lis2dh.route_int2(Int2Routing {
+            interrupt2: true,
+            ..Default::default()
+        }))
+        .unwrap();
+
+        sim.set_acceleration([0.1, 0.2, 0.3]);
+        assert!(!sim.int2());
+        sim.set_acceleration([-0.8, 0.0, 0.0]);
+        assert!(sim.int2());
+        // Sem latch a interrupção acompanha as amostras
+        sim.set_acceleration([0.0, 0.0, 0.0]);
+        assert!(!sim.int2());
+    }
+
+    #[test]
+    fn test_click_registers_and_source() {
+        let mut sim = Lis2dh12::new();
+        let mut lis2dh = Lis2dh::new(&mut sim);
+        let config = ClickConfig {
+            single: Axes {
+                x: false,
+                y: false,
+                z: true,
+            },
+            double: Axes {
+                x: false,
+                y: false,
+                z: true,
+            },
+            threshold: 40,
+            time_limit: 10,
+            latency: 20,
+            window: 50,
+            latch: true,
+        };
+
+        block_on(lis2dh.configure_click(config)).unwrap();
+
+        assert_eq!(sim.register(register::CLICK_CFG), 0x30);
+        assert_eq!(sim.register(register::CLICK_THS), 0x80 | 40);
+        assert_eq!(sim.register(register::TIME_WINDOW), 50);
+
+        sim.click(Axis::Z, true, true);
+        // Clique no X não está habilitado
+        sim.click(Axis::X, false, false);
+
+        let mut lis2dh = Lis2dh::new(&mut sim);
+        let source = block_on(lis2dh.click_source()).unwrap();
+        assert!(source.active && source.double && source.negative && source.axes.z);
+        assert!(!source.axes.x);
+        assert!(!block_on(lis2dh.click_source()).unwrap().active);
+    }
+}
@@ -0,0 +1,511 @@
+//! Driver do acelerômetro LIS2DH12.
+//!
+//! O driver é genérico sobre [`SpiDevice`] do `embedded-hal-async`, então não sabe nada sobre
+//! pinos, DMA ou qual MCU está sendo usado: essa configuração fica na camada da placa, que
+//! entrega um `SpiDevice` pronto (com o chip select já controlado). Com isso o mesmo driver roda
+//! no ESP32-C3, em outro MCU ou no host, usando o simulador do módulo [`sim`].
+
+#![cfg_attr(not(test), no_std)]
+
+pub mod acceleration;
+pub mod config;
+pub mod events;
+pub mod fifo;
+pub mod interrupt;
+pub mod motion;
+pub mod sim;
+
+pub use acceleration::{AccVector, Acceleration};
+use config::{Config, DataRate, FullScale, Mode};
+use core::fmt::{Debug, Display, Formatter};
+use embedded_hal_async::spi::{Operation, SpiDevice};
+use interrupt::Int2Routing;
+
+/// Endereços dos registradores usados pelo driver.
+pub mod register {
+    pub const WHO_AM_I: u8 = 0x0F;
+    pub const CTRL_REG1: u8 = 0x20;
+    pub const CTRL_REG2: u8 = 0x21;
+    pub const CTRL_REG3: u8 = 0x22;
+    pub const CTRL_REG4: u8 = 0x23;
+    pub const CTRL_REG5: u8 = 0x24;
+    pub const CTRL_REG6: u8 = 0x25;
+    pub const STATUS_REG: u8 = 0x27;
+    pub const OUT_X_L: u8 = 0x28;
+    pub const OUT_Y_L: u8 = 0x2A;
+    pub const OUT_Z_L: u8 = 0x2C;
+    pub const OUT_Z_H: u8 = 0x2D;
+    pub const FIFO_CTRL_REG: u8 = 0x2E;
+    pub const FIFO_SRC_REG: u8 = 0x2F;
+    pub const INT1_CFG: u8 = 0x30;
+    pub const INT1_SRC: u8 = 0x31;
+    pub const INT1_THS: u8 = 0x32;
+    pub const INT1_DURATION: u8 = 0x33;
+    pub const INT2_CFG: u8 = 0x34;
+    pub const INT2_SRC: u8 = 0x35;
+    pub const INT2_THS: u8 = 0x36;
+    pub const INT2_DURATION: u8 = 0x37;
+    pub const CLICK_CFG: u8 = 0x38;
+    pub const CLICK_SRC: u8 = 0x39;
+    pub const CLICK_THS: u8 = 0x3A;
+    pub const TIME_LIMIT: u8 = 0x3B;
+    pub const TIME_LATENCY: u8 = 0x3C;
+    pub const TIME_WINDOW: u8 = 0x3D;
+}
+
+/// Valor fixo do `WHO_AM_I` do LIS2DH12.
+pub const DEVICE_ID: u8 = 0x33;
+
+/// Aceleração da gravidade padrão, em m/s².
+pub const STANDARD_GRAVITY: f32 = 9.80665;
+
+// Primeiro byte de cada transação SPI: bit 7 indica leitura e bit 6 incrementa o endereço
+// automaticamente a cada byte (MS), permitindo ler vários registradores seguidos.
+const READ: u8 = 1 << 7;
+const AUTO_INCREMENT: u8 = 1 << 6;
+const ADDRESS_MASK: u8 = 0x3F;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error<E> {
+    Spi(E),
+    /// Combinação de configuração que o sensor não aceita (ver [`Config::is_valid`]).
+    InvalidConfig,
+    /// O `WHO_AM_I` lido não é o do LIS2DH12.
+    WrongDevice(u8),
+    /// Falha ao esperar o pino de interrupção.
+    Pin,
+    /// Nenhuma fonte que gera [`Event`](crate::events::Event) está roteada para o pino esperado.
+    NotRouted,
+}
+
+impl<E> From<E> for Error<E> {
+    fn from(error: E) -> Self {
+        Error::Spi(error)
+    }
+}
+
+/// Amostra de um eixo.
+///
+/// Os registradores de saída guardam um valor em complemento de dois alinhado à esquerda: dos 16
+/// bits, só os 8, 10 ou 12 mais significativos são válidos, dependendo do [`Mode`]. Por isso a
+/// amostra guarda também o modo e o fundo de escala com que foi lida.
+#[derive(Clone, Copy)]
+pub struct Acc {
+    val: i16,
+    scale: FullScale,
+    mode: Mode,
+}
+
+impl Acc {
+    pub fn new(raw: i16, scale: FullScale, mode: Mode) -> Self {
+        Acc {
+            val: raw,
+            scale,
+            mode,
+        }
+    }
+
+    /// Valor dos registradores `OUT_*_L`/`OUT_*_H`, sem ajuste.
+    pub fn raw(&self) -> i16 {
+        self.val
+    }
+
+    /// Valor já alinhado à direita, com a resolução do modo de operação.
+    pub fn counts(&self) -> i16 {
+        // Deslocamento aritmético: mantém o sinal
+        self.val >> (16 - self.mode.resolution_bits())
+    }
+
+    pub fn mg(&self) -> f32 {
+        self.counts() as f32 * self.scale.sensitivity_mg(self.mode)
+    }
+
+    /// Aceleração em g.
+    pub fn acc(&self) -> f32 {
+        self.mg() / 1000.0
+    }
+
+    pub fn m_s2(&self) -> f32 {
+        self.acc() * STANDARD_GRAVITY
+    }
+
+    pub fn acceleration(&self) -> Acceleration {
+        Acceleration::from_mg(self.mg())
+    }
+}
+
+impl Default for Acc {
+    fn default() -> Self {
+        Acc::new(0, FullScale::default(), Mode::default())
+    }
+}
+
+impl Debug for Acc {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Acc")
+            .field("raw", &self.val)
+            .field("mg", &self.mg())
+            .finish()
+    }
+}
+
+impl Display for Acc {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(&self.acceleration(), f)
+    }
+}
+
+impl From<Acc> for Acceleration {
+    fn from(acc: Acc) -> Self {
+        acc.acceleration()
+    }
+}
+
+impl From<(Acc, Acc, Acc)> for AccVector {
+    fn from((x, y, z): (Acc, Acc, Acc)) -> Self {
+        AccVector::new(x.into(), y.into(), z.into())
+    }
+}
+
+pub struct Lis2dh<S> {
+    spi: S,
+    // Cópia do que foi escrito nos registradores de controle, usada para converter as amostras
+    config: Config,
+    // CTRL_REG6, que fica fora do `Config`
+    int2: Int2Routing,
+}
+
+fn array_to_i16(array: &[u8]) -> i16 {
+    i16::from_le_bytes([array[0], array[1]])
+}
+
+impl<S: SpiDevice> Lis2dh<S> {
+    /// Cria o driver supondo que o sensor está com a configuração de reset. Use [`Lis2dh::init`]
+    /// para conferir o dispositivo e ler a configuração atual.
+    pub fn new(spi: S) -> Self {
+        Self {
+            spi,
+            config: Config::default(),
+            int2: Int2Routing::default(),
+        }
+    }
+
+    /// Devolve o barramento, por exemplo para reaproveitá-lo com outro dispositivo.
+    pub fn release(self) -> S {
+        self.spi
+    }
+
+    /// Confere o `WHO_AM_I` e lê a configuração atual dos registradores de controle.
+    pub async fn init(&mut self) -> Result<(), Error<S::Error>> {
+        let id = self.who_i_am().await?;
+        if id != DEVICE_ID {
+            return Err(Error::WrongDevice(id));
+        }
+
+        self.read_config().await?;
+        Ok(())
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Escreve `CTRL_REG1` a `CTRL_REG4` em uma única transação.
+    pub async fn configure(&mut self, config: Config) -> Result<(), Error<S::Error>> {
+        if !config.is_valid() {
+            return Err(Error::InvalidConfig);
+        }
+
+        self.write(register::CTRL_REG1, &config.to_registers())
+            .await?;
+        self.config = config;
+
+        Ok(())
+    }
+
+    /// Lê `CTRL_REG1` a `CTRL_REG6` em uma única transação. Além do [`Config`], atualiza o
+    /// roteamento do INT2 e a polaridade dos pinos (`CTRL_REG6`), usados por
+    /// [`Lis2dh::events`].
+    pub async fn read_config(&mut self) -> Result<Config, Error<S::Error>> {
+        let [ctrl1, ctrl2, ctrl3, ctrl4, _, ctrl6] = self.read::<6>(register::CTRL_REG1).await?;
+        self.config = Config::from_registers([ctrl1, ctrl2, ctrl3, ctrl4]);
+        self.int2 = Int2Routing::from_bits(ctrl6);
+
+        Ok(self.config)
+    }
+
+    pub async fn set_data_rate(&mut self, data_rate: DataRate) -> Result<(), Error<S::Error>> {
+        self.configure(Config {
+            data_rate,
+            ..self.config
+        })
+        .await
+    }
+
+    pub async fn set_full_scale(&mut self, scale: FullScale) -> Result<(), Error<S::Error>> {
+        self.configure(Config {
+            scale,
+            ..self.config
+        })
+        .await
+    }
+
+    pub async fn set_mode(&mut self, mode: Mode) -> Result<(), Error<S::Error>> {
+        self.configure(Config {
+            mode,
+            ..self.config
+        })
+        .await
+    }
+
+    async fn write(&mut self, address: u8, tx_buffer: &[u8]) -> Result<(), S::Error> {
+        let address = [(address & ADDRESS_MASK) | AUTO_INCREMENT];
+
+        self.spi
+            .transaction(&mut [Operation::Write(&address), Operation::Write(tx_buffer)])
+            .await
+    }
+
+    async fn read<const N: usize>(&mut self, address: u8) -> Result<[u8; N], S::Error> {
+        let mut rx_buffer = [0; N];
+        self.read_into(address, &mut rx_buffer).await?;
+
+        Ok(rx_buffer)
+    }
+
+    /// Lê `rx_buffer.len()` bytes a partir de `address` em uma única transação.
+    async fn read_into(&mut self, address: u8, rx_buffer: &mut [u8]) -> Result<(), S::Error> {
+        let address = [(address & ADDRESS_MASK) | READ | AUTO_INCREMENT];
+
+        self.spi
+            .transaction(&mut [Operation::Write(&address), Operation::Read(rx_buffer)])
+            .await
+    }
+
+    /// Altera somente os bits de `mask` em um registrador.
+    async fn modify(&mut self, address: u8, mask: u8, value: u8) -> Result<(), S::Error> {
+        let [current] = self.read::<1>(address).await?;
+        self.write(address, &[(current & !mask) | (value & mask)])
+            .await
+    }
+
+    fn acc(&self, bytes: &[u8]) -> Acc {
+        Acc::new(array_to_i16(bytes), self.config.scale, self.config.mode)
+    }
+
+    pub async fn who_i_am(&mut self) -> Result<u8, Error<S::Error>> {
+        let result = self.read::<1>(register::WHO_AM_I).await?;
+        Ok(result[0])
+    }
+
+    pub async fn read_x(&mut self) -> Result<Acc, Error<S::Error>> {
+        let result = self.read::<2>(register::OUT_X_L).await?;
+        Ok(self.acc(&result))
+    }
+
+    pub async fn read_y(&mut self) -> Result<Acc, Error<S::Error>> {
+        let result = self.read::<2>(register::OUT_Y_L).await?;
+        Ok(self.acc(&result))
+    }
+
+    pub async fn read_z(&mut self) -> Result<Acc, Error<S::Error>> {
+        let result = self.read::<2>(register::OUT_Z_L).await?;
+        Ok(self.acc(&result))
+    }
+
+    pub async fn read_all(&mut self) -> Result<(Acc, Acc, Acc), Error<S::Error>> {
+        let result = self.read::<6>(register::OUT_X_L).await?;
+        Ok((
+            self.acc(&result[..2]),
+            self.acc(&result[2..4]),
+            self.acc(&result[4..]),
+        ))
+    }
+
+    /// Lê os três eixos já convertidos para [`Acceleration`].
+    pub async fn read_acceleration(&mut self) -> Result<AccVector, Error<S::Error>> {
+        Ok(self.read_all().await?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sim::Lis2dh12;
+    use aula7::bus::mock::{SpiMock, SpiTransaction};
+    use futures::executor::block_on;
+
+    #[test]
+    fn test_who_i_am() {
+        let mut lis2dh = Lis2dh::new(Lis2dh12::new());
+
+        assert_eq!(block_on(lis2dh.who_i_am()), Ok(DEVICE_ID));
+    }
+
+    #[test]
+    fn test_read_x() {
+        let mut sim = Lis2dh12::new();
+        sim.set_raw_acceleration([0x1234, 0, 0]);
+        let mut lis2dh = Lis2dh::new(sim);
+
+        let x = block_on(lis2dh.read_x()).unwrap();
+
+        assert_eq!(x.raw(), 0x1234);
+    }
+
+    #[test]
+    fn test_read_all_sign() {
+        let mut sim = Lis2dh12::new();
+        sim.set_raw_acceleration([0, i16::MIN, -64]);
+        let mut lis2dh = Lis2dh::new(sim);
+
+        let (x, y, z) = block_on(lis2dh.read_all()).unwrap();
+
+        assert_eq!([x.raw(), y.raw(), z.raw()], [0, i16::MIN, -64]);
+        // Modo normal (10 bits) e ±2 g: 4 mg por dígito
+        assert_eq!([x.counts(), y.counts(), z.counts()], [0, -512, -1]);
+        assert_eq!(y.acc(), -2.048);
+        assert_eq!(z.mg(), -4.0);
+    }
+
+    #[test]
+    fn test_acc_units() {
+        let acc = Acc::new(1000 << 4, FullScale::G2, Mode::HighResolution);
+
+        assert_eq!(acc.counts(), 1000);
+        assert_eq!(acc.acc(), 1.0);
+        assert_eq!(acc.m_s2(), STANDARD_GRAVITY);
+        assert_eq!(acc.acceleration(), Acceleration::from_g(1.0));
+        assert_eq!(format!("{}", acc), "1 g");
+        assert_eq!(format!("{:?}", acc), "Acc { raw: 16000, mg: 1000.0 }");
+    }
+
+    #[test]
+    fn test_read_acceleration() {
+        let mut sim = Lis2dh12::new();
+        sim.set_acceleration([0.5, -0.5, 1.0]);
+        let mut lis2dh = Lis2dh::new(sim);
+
+        let acc = block_on(lis2dh.read_acceleration()).unwrap();
+
+        assert_eq!(acc, AccVector::from_g([0.5, -0.5, 1.0]));
+    }
+
+    #[test]
+    fn test_configure_changes_conversion() {
+        let mut lis2dh = Lis2dh::new(Lis2dh12::new());
+        let config = Config {
+            data_rate: DataRate::Hz100,
+            mode: Mode::HighResolution,
+            scale: FullScale::G8,
+            block_data_update: true,
+            ..Default::default()
+        };
+
+        block_on(lis2dh.configure(config)).unwrap();
+
+        let mut sim = lis2dh.release();
+        assert_eq!(sim.config(), config);
+        assert_eq!(sim.register(register::CTRL_REG4), 0xA8);
+
+        sim.set_acceleration([1.0, -0.5, 20.0]);
+        let mut lis2dh = Lis2dh::new(sim);
+        block_on(lis2dh.read_config()).unwrap();
+        let (x, y, z) = block_on(lis2dh.read_all()).unwrap();
+
+        assert_eq!(x.acc(), 1.0);
+        assert_eq!(y.acc(), -0.5);
+        // Satura no fundo de escala: 2047 * 4 mg
+        assert_eq!(z.mg(), 8188.0);
+    }
+
+    #[test]
+    fn test_setters_keep_other_fields() {
+        let mut lis2dh = Lis2dh::new(Lis2dh12::new());
+
+        block_on(lis2dh.set_data_rate(DataRate::Hz400)).unwrap();
+        block_on(lis2dh.set_full_scale(FullScale::G16)).unwrap();
+        block_on(lis2dh.set_mode(Mode::LowPower)).unwrap();
+
+        let expected = Config {
+            data_rate: DataRate::Hz400,
+            scale: FullScale::G16,
+            mode: Mode::LowPower,
+            ..Default::default()
+        };
+        assert_eq!(lis2dh.config(), &expected);
+        assert_eq!(lis2dh.release().config(), expected);
+    }
+
+    #[test]
+    fn test_invalid_config_is_not_written() {
+        let spi = SpiMock::new(&[]);
+        let mut lis2dh = Lis2dh::new(spi.clone());
+        let config = Config {
+            data_rate: DataRate::Hz1620LowPower,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            block_on(lis2dh.configure(config)),
+            Err(Error::InvalidConfig)
+        );
+        spi.done();
+    }
+
+    #[test]
+    fn test_init() {
+        let spi = SpiMock::new(&[
+            SpiTransaction::Select,
+            SpiTransaction::Write(vec![0xCF]),
+            SpiTransaction::Read(vec![0x33]),
+            SpiTransaction::Deselect,
+            SpiTransaction::Select,
+            SpiTransaction::Write(vec![0xE0]),
+            SpiTransaction::Read(vec![0x2F, 0x00, 0x00, 0x30, 0x00, 0x02]),
+            SpiTransaction::Deselect,
+            SpiTransaction::Select,
+            SpiTransaction::Write(vec![0xCF]),
+            SpiTransaction::Read(vec![0x32]),
+            SpiTransaction::Deselect,
+        ]);
+        let mut lis2dh = Lis2dh::new(spi.clone());
+
+        block_on(lis2dh.init()).unwrap();
+        assert_eq!(lis2dh.config().data_rate, DataRate::Hz10);
+        assert_eq!(lis2dh.config().mode, Mode::LowPower);
+        assert_eq!(lis2dh.config().scale, FullScale::G16);
+        assert!(lis2dh.int2.active_low);
+
+        assert_eq!(block_on(lis2dh.init()), Err(Error::WrongDevice(0x32)));
+        spi.done();
+    }
+
+    #[test]
+    fn test_write_register() {
+        let mut lis2dh = Lis2dh::new(Lis2dh12::new());
+
+        block_on(lis2dh.write(0x20, &[0x57, 0x01])).unwrap();
+
+        let sim = lis2dh.release();
+        assert_eq!(sim.register(0x20), 0x57);
+        assert_eq!(sim.register(0x21), 0x01);
+    }
+
+    /// Confere os bytes exatos enviados no barramento, não só o resultado.
+    #[test]
+    fn test_read_all_bus_frames() {
+        let spi = SpiMock::new(&[
+            SpiTransaction::Select,
+            SpiTransaction::Write(vec![0xE8]),
+            SpiTransaction::Read(vec![1, 0, 2, 0, 3, 0]),
+            SpiTransaction::Deselect,
+        ]);
+        let mut lis2dh = Lis2dh::new(spi.clone());
+
+        let (x, y, z) = block_on(lis2dh.read_all()).unwrap();
+
+        assert_eq!([x.raw(), y.raw(), z.raw()], [1, 2, 3]);
+        spi.done();
+    }
+}
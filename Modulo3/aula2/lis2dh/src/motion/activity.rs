@@ -0,0 +1,164 @@
+//! Classificador de atividade pela intensidade do movimento.
+//!
+//! A cada janela de amostras calcula o desvio padrão do módulo da aceleração: parado ele fica
+//! no nível do ruído do sensor, andando fica em alguns décimos de g e correndo passa de meio g.
+//! É simples, mas suficiente para decidir, por exemplo, quando vale a pena ligar o contador de
+//! passos ou aumentar a taxa de amostragem.
+
+use crate::AccVector;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Activity {
+    #[default]
+    Still,
+    Walking,
+    Running,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ActivityConfig {
+    /// Quantidade de amostras em cada janela. Umas duas passadas (2 s) funciona bem.
+    pub window: u32,
+    /// Desvio padrão, em g, abaixo do qual o sensor é considerado parado.
+    pub still_threshold: f32,
+    /// Desvio padrão, em g, a partir do qual o movimento é considerado corrida.
+    pub running_threshold: f32,
+}
+
+impl Default for ActivityConfig {
+    fn default() -> Self {
+        // Ajustados com os traces sintéticos (pasta `synthetic`), não com gravações reais
+        ActivityConfig {
+            window: 100,
+            still_threshold: 0.05,
+            running_threshold: 0.5,
+        }
+    }
+}
+
+pub struct ActivityClassifier {
+    config: ActivityConfig,
+    // Média e soma dos quadrados das diferenças (algoritmo de Welford) da janela atual
+    count: u32,
+    mean: f32,
+    m2: f32,
+    activity: Activity,
+}
+
+impl ActivityClassifier {
+    pub fn new(config: ActivityConfig) -> Self {
+        ActivityClassifier {
+            config,
+            count: 0,
+            mean: 0.0,
+            m2: 0.0,
+            activity: Activity::default(),
+        }
+    }
+
+    /// Processa uma amostra. Ao fim de cada janela retorna a nova classificação.
+    pub fn update(&mut self, sample: AccVector) -> Option<Activity> {
+        let magnitude = sample.magnitude().g();
+        self.count += 1;
+        let delta = magnitude - self.mean;
+        self.mean += delta / self.count as f32;
+        self.m2 += delta * (magnitude - self.mean);
+
+        if self.count < self.config.window {
+            return None;
+        }
+
+        let deviation = libm::sqrtf(self.m2 / self.count as f32);
+        self.activity = self.classify(deviation);
+        self.count = 0;
+        self.mean = 0.0;
+        self.m2 = 0.0;
+
+        Some(self.activity)
+    }
+
+    /// Classificação da última janela completa.
+    pub fn activity(&self) -> Activity {
+        self.activity
+    }
+
+    fn classify(&self, deviation: f32) -> Activity {
+        if deviation < self.config.still_threshold {
+            Activity::Still
+        } else if deviation < self.config.running_threshold {
+            Activity::Walking
+        } else {
+            Activity::Running
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::motion::synthetic;
+
+    fn classify(csv: &str) -> Vec<Activity> {
+        let mut classifier = ActivityClassifier::new(ActivityConfig::default());
+        synthetic::load(csv)
+            .into_iter()
+            .filter_map(|sample| classifier.update(sample))
+            .collect()
+    }
+
+    #[test]
+    fn test_traces() {
+        // 500 amostras: 5 janelas de 100
+        assert_eq!(classify(synthetic::STILL), vec![Activity::Still; 5]);
+        assert_eq!(classify(synthetic::WALKING), vec![Activity::Walking; 5]);
+        assert_eq!(classify(synthetic::RUNNING), vec![Activity::Running; 5]);
+    }
+
+    /// Confere cada gravação real da pasta `recorded` com a atividade do nome do arquivo. A
+    /// primeira janela pode pegar o começo da gravação, então basta a maioria estar certa.
+    #[test]
+    fn test_recorded_traces() {
+        let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("recorded");
+
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            let name = path.file_name().unwrap().to_string_lossy().into_owned();
+            let expected = if !name.ends_with(".csv") {
+                continue;
+            } else if name.starts_with("still_") {
+                Activity::Still
+            } else if name.starts_with("walking_") {
+                Activity::Walking
+            } else if name.starts_with("running_") {
+                Activity::Running
+            } else {
+                continue;
+            };
+
+            let windows = classify(&std::fs::read_to_string(&path).unwrap());
+            let right = windows.iter().filter(|&&a| a == expected).count();
+            assert!(
+                2 * right > windows.len(),
+                "{name}: expected {expected:?}, got {windows:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_activity_changes() {
+        let mut classifier = ActivityClassifier::new(ActivityConfig::default());
+        let walking = synthetic::load(synthetic::WALKING);
+        let still = synthetic::load(synthetic::STILL);
+
+        for &sample in &walking[..100] {
+            classifier.update(sample);
+        }
+        assert_eq!(classifier.activity(), Activity::Walking);
+
+        for &sample in &still[..99] {
+            assert_eq!(classifier.update(sample), None);
+        }
+        assert_eq!(classifier.activity(), Activity::Walking);
+        assert_eq!(classifier.update(still[99]), Some(Activity::Still));
+    }
+}
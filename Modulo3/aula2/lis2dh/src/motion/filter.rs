@@ -0,0 +1,178 @@
+//! Filtros IIR de primeira ordem (equivalentes a um circuito RC).
+//!
+//! O passa-baixas separa a gravidade (que muda devagar) do movimento; o passa-altas faz o
+//! contrário e tira a gravidade e o offset do sensor.
+
+use crate::AccVector;
+use core::f32::consts::PI;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterKind {
+    LowPass,
+    HighPass,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Filter {
+    kind: FilterKind,
+    alpha: f32,
+    input: f32,
+    output: f32,
+    // A primeira amostra inicializa o estado, evitando o transitório de partir do zero
+    primed: bool,
+}
+
+impl Filter {
+    /// `cutoff` é a frequência de corte (-3 dB) e `sample_rate` a taxa de amostragem, em Hz.
+    pub fn new(kind: FilterKind, cutoff: f32, sample_rate: f32) -> Self {
+        let rc = 1.0 / (2.0 * PI * cutoff);
+        let dt = 1.0 / sample_rate;
+        let alpha = match kind {
+            FilterKind::LowPass => dt / (rc + dt),
+            FilterKind::HighPass => rc / (rc + dt),
+        };
+
+        Filter {
+            kind,
+            alpha,
+            input: 0.0,
+            output: 0.0,
+            primed: false,
+        }
+    }
+
+    pub fn low_pass(cutoff: f32, sample_rate: f32) -> Self {
+        Filter::new(FilterKind::LowPass, cutoff, sample_rate)
+    }
+
+    pub fn high_pass(cutoff: f32, sample_rate: f32) -> Self {
+        Filter::new(FilterKind::HighPass, cutoff, sample_rate)
+    }
+
+    pub fn kind(&self) -> FilterKind {
+        self.kind
+    }
+
+    /// Filtra uma nova amostra e devolve a saída.
+    pub fn update(&mut self, input: f32) -> f32 {
+        if !self.primed {
+            self.primed = true;
+            self.input = input;
+            self.output = match self.kind {
+                FilterKind::LowPass => input,
+                FilterKind::HighPass => 0.0,
+            };
+            return self.output;
+        }
+
+        self.output = match self.kind {
+            FilterKind::LowPass => self.output + self.alpha * (input - self.output),
+            FilterKind::HighPass => self.alpha * (self.output + input - self.input),
+        };
+        self.input = input;
+
+        self.output
+    }
+
+    /// Última saída.
+    pub fn output(&self) -> f32 {
+        self.output
+    }
+
+    pub fn reset(&mut self) {
+        self.primed = false;
+        self.input = 0.0;
+        self.output = 0.0;
+    }
+}
+
+/// O mesmo filtro aplicado a cada eixo.
+#[derive(Debug, Clone, Copy)]
+pub struct VectorFilter {
+    axes: [Filter; 3],
+}
+
+impl VectorFilter {
+    pub fn new(kind: FilterKind, cutoff: f32, sample_rate: f32) -> Self {
+        VectorFilter {
+            axes: [Filter::new(kind, cutoff, sample_rate); 3],
+        }
+    }
+
+    pub fn update(&mut self, input: AccVector) -> AccVector {
+        let [x, y, z] = input.g();
+        AccVector::from_g([
+            self.axes[0].update(x),
+            self.axes[1].update(y),
+            self.axes[2].update(z),
+        ])
+    }
+
+    pub fn output(&self) -> AccVector {
+        AccVector::from_g(self.axes.map(|axis| axis.output()))
+    }
+
+    pub fn reset(&mut self) {
+        self.axes.iter_mut().for_each(Filter::reset);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::motion::synthetic::{self, SAMPLE_RATE};
+
+    /// Amplitude da saída, em regime, para uma senoide de amplitude 1 em `frequency`.
+    fn gain(mut filter: Filter, frequency: f32) -> f32 {
+        let mut peak: f32 = 0.0;
+        for i in 0..2000 {
+            let t = i as f32 / SAMPLE_RATE;
+            let output = filter.update((2.0 * PI * frequency * t).sin());
+            if i > 1000 {
+                peak = peak.max(output.abs());
+            }
+        }
+        peak
+    }
+
+    #[test]
+    fn test_cutoff_attenuation() {
+        let low_pass = Filter::low_pass(1.0, SAMPLE_RATE);
+        assert!(gain(low_pass, 0.1) > 0.98);
+        assert!((gain(low_pass, 1.0) - 0.707).abs() < 0.05);
+        assert!(gain(low_pass, 10.0) < 0.2);
+
+        let high_pass = Filter::high_pass(1.0, SAMPLE_RATE);
+        assert!(gain(high_pass, 0.1) < 0.15);
+        assert!((gain(high_pass, 1.0) - 0.707).abs() < 0.05);
+        assert!(gain(high_pass, 5.0) > 0.9);
+    }
+
+    #[test]
+    fn test_first_sample_primes_state() {
+        let mut low_pass = Filter::low_pass(1.0, SAMPLE_RATE);
+        assert_eq!(low_pass.update(1.0), 1.0);
+        assert_eq!(low_pass.update(1.0), 1.0);
+
+        let mut high_pass = Filter::high_pass(1.0, SAMPLE_RATE);
+        assert_eq!(high_pass.update(1.0), 0.0);
+
+        low_pass.reset();
+        assert_eq!(low_pass.update(-1.0), -1.0);
+    }
+
+    /// Parado, o passa-baixas devolve a gravidade e o passa-altas só o ruído.
+    #[test]
+    fn test_still_trace_separates_gravity() {
+        let mut gravity = VectorFilter::new(FilterKind::LowPass, 0.5, SAMPLE_RATE);
+        let mut motion = VectorFilter::new(FilterKind::HighPass, 0.5, SAMPLE_RATE);
+
+        for sample in synthetic::load(synthetic::STILL) {
+            gravity.update(sample);
+            motion.update(sample);
+        }
+
+        assert!((gravity.output().magnitude().g() - 1.0).abs() < 0.02);
+        assert!(motion.output().magnitude().g() < 0.03);
+    }
+}
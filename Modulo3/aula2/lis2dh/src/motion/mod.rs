@@ -0,0 +1,47 @@
+//! Processamento das amostras do acelerômetro, sem alocação, para rodar no próprio MCU.
+//!
+//! Todos os algoritmos recebem uma amostra [`AccVector`](crate::AccVector) por vez, na taxa de
+//! amostragem configurada no sensor, e guardam só o estado necessário entre uma amostra e a
+//! próxima:
+//!
+//! - [`filter`]: filtros passa-baixas e passa-altas de primeira ordem;
+//! - [`orientation`]: pitch e roll a partir da gravidade filtrada;
+//! - [`steps`]: contador de passos por detecção de picos com limiar adaptativo;
+//! - [`activity`]: classificação simples entre parado, andando e correndo.
+//!
+//! !!! Os testes usam os traces em CSV da pasta `synthetic`, que são **sintéticos**: gerados por
+//! `synthetic/generate.py`, e não gravados de um LIS2DH12. Os limiares padrão do contador de
+//! passos e do classificador foram ajustados com esse mesmo gerador, então os testes só garantem
+//! que os algoritmos continuam se comportando como antes, não que acertam com movimento real.
+//! Antes de confiar nesses limiares no dispositivo, grave traces reais (no mesmo formato
+//! `t,x,y,z`) na pasta `recorded` e ajuste com eles: o README de lá explica como gravar, e um
+//! teste confere o classificador com cada gravação.
+
+pub mod activity;
+pub mod filter;
+pub mod orientation;
+pub mod steps;
+
+#[cfg(test)]
+pub(crate) mod synthetic {
+    use crate::AccVector;
+
+    /// Taxa de amostragem dos traces sintéticos, em Hz.
+    pub const SAMPLE_RATE: f32 = 50.0;
+
+    pub const STILL: &str = include_str!("../../synthetic/still.csv");
+    pub const TILT: &str = include_str!("../../synthetic/tilt.csv");
+    pub const WALKING: &str = include_str!("../../synthetic/walking.csv");
+    pub const RUNNING: &str = include_str!("../../synthetic/running.csv");
+
+    /// Lê um trace com as colunas `t,x,y,z`, em segundos e g.
+    pub fn load(csv: &str) -> Vec<AccVector> {
+        csv.lines()
+            .skip(1)
+            .map(|line| {
+                let values: Vec<f32> = line.split(',').map(|v| v.parse().unwrap()).collect();
+                AccVector::from_g([values[1], values[2], values[3]])
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,106 @@
+//! Pitch e roll a partir do acelerômetro.
+//!
+//! Parado, o acelerômetro só mede a gravidade, e a direção dela dá a inclinação do sensor. Com
+//! movimento, a aceleração do movimento soma com a gravidade; por isso as amostras passam antes
+//! por um passa-baixas, que deixa passar a gravidade e atenua a vibração. Quanto menor a
+//! frequência de corte, mais estável o ângulo e mais lenta a resposta.
+//!
+//! !!! O yaw (rotação em torno da vertical) não muda a direção da gravidade, então não dá para
+//!     calculá-lo só com o acelerômetro.
+
+use super::filter::{FilterKind, VectorFilter};
+use crate::AccVector;
+
+/// Ângulos em radianos, com a mesma convenção de [`AccVector::pitch`] e [`AccVector::roll`].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Orientation {
+    pub pitch: f32,
+    pub roll: f32,
+}
+
+impl Orientation {
+    /// Orientação do sensor supondo que `gravity` é só a gravidade.
+    pub fn from_gravity(gravity: &AccVector) -> Self {
+        Orientation {
+            pitch: gravity.pitch(),
+            roll: gravity.roll(),
+        }
+    }
+
+    pub fn pitch_degrees(&self) -> f32 {
+        self.pitch.to_degrees()
+    }
+
+    pub fn roll_degrees(&self) -> f32 {
+        self.roll.to_degrees()
+    }
+}
+
+pub struct OrientationEstimator {
+    gravity: VectorFilter,
+}
+
+impl OrientationEstimator {
+    /// `cutoff` é a frequência de corte do passa-baixas, em Hz. Algo entre 0,5 e 2 Hz funciona
+    /// bem para um sensor preso ao corpo.
+    pub fn new(cutoff: f32, sample_rate: f32) -> Self {
+        OrientationEstimator {
+            gravity: VectorFilter::new(FilterKind::LowPass, cutoff, sample_rate),
+        }
+    }
+
+    pub fn update(&mut self, sample: AccVector) -> Orientation {
+        Orientation::from_gravity(&self.gravity.update(sample))
+    }
+
+    /// Gravidade estimada, no referencial do sensor.
+    pub fn gravity(&self) -> AccVector {
+        self.gravity.output()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::motion::synthetic::{self, SAMPLE_RATE};
+
+    #[test]
+    fn test_tilt_trace() {
+        let mut estimator = OrientationEstimator::new(1.0, SAMPLE_RATE);
+        let samples = synthetic::load(synthetic::TILT);
+
+        // Pitch de 0° a 40° nos primeiros 5 s, depois roll de 0° a -30° até 9 s
+        let mut angles = samples.iter().map(|&sample| estimator.update(sample));
+        let start = angles.next().unwrap();
+        let after_pitch = angles.nth(5 * SAMPLE_RATE as usize + 25).unwrap();
+        let end = angles.last().unwrap();
+
+        assert!(start.pitch_degrees().abs() < 2.0);
+        assert!((after_pitch.pitch_degrees() - 40.0).abs() < 1.0);
+        assert!(after_pitch.roll_degrees().abs() < 3.0);
+        assert!((end.pitch_degrees() - 40.0).abs() < 1.0);
+        assert!((end.roll_degrees() + 30.0).abs() < 1.0);
+    }
+
+    /// Andando, o filtro mantém o ângulo estável mesmo com ±0,3 g de vibração.
+    #[test]
+    fn test_walking_trace_is_stable() {
+        let mut estimator = OrientationEstimator::new(0.5, SAMPLE_RATE);
+        let angles: Vec<_> = synthetic::load(synthetic::WALKING)
+            .into_iter()
+            .map(|sample| estimator.update(sample))
+            .skip(2 * SAMPLE_RATE as usize)
+            .collect();
+
+        let (min, max) = angles
+            .iter()
+            .fold((f32::MAX, f32::MIN), |(min, max), angle| {
+                (min.min(angle.roll_degrees()), max.max(angle.roll_degrees()))
+            });
+        assert!(max - min < 3.0, "roll varied {}°", max - min);
+
+        // Gravidade do trace: (0,05, 0,10, 0,99) g
+        let gravity = estimator.gravity();
+        assert!((gravity.roll().to_degrees() - 5.8).abs() < 1.5);
+    }
+}
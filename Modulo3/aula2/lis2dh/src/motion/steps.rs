@@ -0,0 +1,192 @@
+//! Contador de passos.
+//!
+//! Cada passo aparece como um pico no módulo da aceleração. O sinal passa por um passa-faixa
+//! (passa-altas para tirar a gravidade e passa-baixas para tirar a vibração) e cada máximo local
+//! acima do limiar conta como passo, desde que respeite o intervalo mínimo entre passos.
+//!
+//! O limiar é adaptativo: acompanha uma média da altura dos últimos picos, então o mesmo
+//! contador funciona andando devagar ou correndo, sem contar as oscilações menores entre um
+//! passo e outro.
+
+use super::filter::Filter;
+use crate::AccVector;
+
+#[derive(Debug, Clone, Copy)]
+pub struct StepConfig {
+    /// Taxa de amostragem, em Hz.
+    pub sample_rate: f32,
+    /// Intervalo mínimo entre passos, em segundos. Picos mais próximos que isso são ignorados.
+    pub min_interval: f32,
+    /// Intervalo máximo entre passos, em segundos. Acima disso a cadência é considerada zero e
+    /// o limiar volta a cair até o mínimo.
+    pub max_interval: f32,
+    /// Limiar mínimo, em g, para ignorar o ruído com o sensor parado.
+    pub min_threshold: f32,
+    /// Fração da altura média dos picos usada como limiar.
+    pub threshold_ratio: f32,
+}
+
+impl Default for StepConfig {
+    fn default() -> Self {
+        // Ajustados com os traces sintéticos (pasta `synthetic`), não com gravações reais
+        StepConfig {
+            sample_rate: 50.0,
+            min_interval: 0.25,
+            max_interval: 2.0,
+            min_threshold: 0.08,
+            threshold_ratio: 0.5,
+        }
+    }
+}
+
+pub struct StepCounter {
+    config: StepConfig,
+    high_pass: Filter,
+    low_pass: Filter,
+    // As duas últimas saídas do filtro, para achar os máximos locais
+    previous: [f32; 2],
+    // Amostras desde o último passo
+    since_last: u32,
+    // Intervalo entre os dois últimos passos, em amostras
+    interval: Option<u32>,
+    peak_average: f32,
+    steps: u32,
+}
+
+impl StepCounter {
+    pub fn new(config: StepConfig) -> Self {
+        StepCounter {
+            config,
+            high_pass: Filter::high_pass(0.5, config.sample_rate),
+            low_pass: Filter::low_pass(4.0, config.sample_rate),
+            previous: [0.0; 2],
+            since_last: u32::MAX,
+            interval: None,
+            peak_average: 0.0,
+            steps: 0,
+        }
+    }
+
+    /// Processa uma amostra. Retorna `true` se ela completou um passo.
+    pub fn update(&mut self, sample: AccVector) -> bool {
+        let value = self
+            .low_pass
+            .update(self.high_pass.update(sample.magnitude().g()));
+        let [before, candidate] = self.previous;
+        self.previous = [candidate, value];
+        self.since_last = self.since_last.saturating_add(1);
+
+        let max_samples = self.samples(self.config.max_interval);
+        if self.since_last > max_samples {
+            // Parou de andar: o limiar volta devagar para o mínimo e a cadência zera
+            self.peak_average *= 0.99;
+            self.interval = None;
+        }
+
+        let is_peak = candidate > before && candidate >= value;
+        if !is_peak
+            || candidate < self.threshold()
+            || self.since_last < self.samples(self.config.min_interval)
+        {
+            return false;
+        }
+
+        // O pico é a amostra anterior, então o intervalo termina nela
+        let since_last = self.since_last - 1;
+        self.interval = (since_last <= max_samples).then_some(since_last);
+        self.since_last = 1;
+        self.peak_average = if self.steps == 0 {
+            candidate
+        } else {
+            0.8 * self.peak_average + 0.2 * candidate
+        };
+        self.steps += 1;
+
+        true
+    }
+
+    pub fn steps(&self) -> u32 {
+        self.steps
+    }
+
+    /// Limiar atual, em g.
+    pub fn threshold(&self) -> f32 {
+        (self.config.threshold_ratio * self.peak_average).max(self.config.min_threshold)
+    }
+
+    /// Passos por minuto, calculado com o intervalo entre os dois últimos passos.
+    pub fn cadence(&self) -> f32 {
+        match self.interval {
+            Some(interval) => 60.0 * self.config.sample_rate / interval as f32,
+            None => 0.0,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = StepCounter::new(self.config);
+    }
+
+    fn samples(&self, seconds: f32) -> u32 {
+        (seconds * self.config.sample_rate) as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::motion::synthetic::{self, SAMPLE_RATE};
+
+    fn count(csv: &str) -> StepCounter {
+        let mut counter = StepCounter::new(StepConfig {
+            sample_rate: SAMPLE_RATE,
+            ..Default::default()
+        });
+        for sample in synthetic::load(csv) {
+            counter.update(sample);
+        }
+        counter
+    }
+
+    #[test]
+    fn test_walking_trace() {
+        // 18 passos em 10 s, a ~1,8 passo/s
+        let counter = count(synthetic::WALKING);
+
+        assert!(
+            (17..=19).contains(&counter.steps()),
+            "{} steps",
+            counter.steps()
+        );
+        assert!((counter.cadence() - 108.0).abs() < 15.0);
+    }
+
+    #[test]
+    fn test_running_trace() {
+        // 28 passos em 10 s, a ~2,8 passos/s
+        let counter = count(synthetic::RUNNING);
+
+        assert!(
+            (27..=29).contains(&counter.steps()),
+            "{} steps",
+            counter.steps()
+        );
+        assert!((counter.cadence() - 168.0).abs() < 20.0);
+    }
+
+    #[test]
+    fn test_still_trace_has_no_steps() {
+        let counter = count(synthetic::STILL);
+
+        assert_eq!(counter.steps(), 0);
+        assert_eq!(counter.cadence(), 0.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut counter = count(synthetic::WALKING);
+        counter.reset();
+
+        assert_eq!(counter.steps(), 0);
+        assert_eq!(counter.threshold(), StepConfig::default().min_threshold);
+    }
+}
@@ -0,0 +1,477 @@
+//! LIS2DH12 simulado no nível dos registradores.
+//!
+//! Implementa [`SpiDevice`](embedded_hal_async::spi::SpiDevice) interpretando os bytes da mesma
+//! forma que o sensor: o primeiro byte de cada transação é o comando (leitura/escrita, auto
+//! incremento e endereço) e os seguintes leem ou escrevem os registradores a partir dali. Assim o
+//! driver é testado contra o protocolo, sem depender da ordem exata das chamadas.
+//!
+//! Cada chamada de [`Lis2dh12::set_acceleration`] equivale a uma nova amostra do sensor: ela passa
+//! pela FIFO e pelos geradores de interrupção. Simplificações em relação ao sensor real: os
+//! filtros passa-altas e os modos 6D não são simulados, e o clique é injetado diretamente com
+//! [`Lis2dh12::click`] em vez de ser detectado a partir das amostras.
+
+use crate::config::Config;
+use crate::fifo::{FifoConfig, FifoMode, FifoTrigger, FIFO_EN, FIFO_SIZE};
+use crate::interrupt::LIR_CLICK;
+use crate::{register, ADDRESS_MASK, AUTO_INCREMENT, DEVICE_ID, READ};
+use core::convert::Infallible;
+use embedded_hal::spi::{ErrorType, Operation, SpiDevice};
+
+const REGISTERS: usize = 0x40;
+
+/// Bit `IA` dos registradores `INTx_SRC` e `CLICK_SRC`.
+const IA: u8 = 1 << 6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Axis {
+    X,
+    Y,
+    Z,
+}
+
+pub struct Lis2dh12 {
+    registers: [u8; REGISTERS],
+    fifo: [[i16; 3]; FIFO_SIZE],
+    // Índice da amostra mais antiga e quantidade de amostras na FIFO
+    fifo_head: usize,
+    fifo_len: usize,
+    // No modo StreamToFifo, indica se o gatilho já aconteceu
+    triggered: bool,
+    // Amostras seguidas em que a condição de cada gerador de interrupção foi satisfeita
+    durations: [u8; 2],
+    data_ready: bool,
+}
+
+/// Comando recebido no primeiro byte da transação.
+struct Command {
+    address: u8,
+    read: bool,
+    auto_increment: bool,
+}
+
+impl Lis2dh12 {
+    pub fn new() -> Self {
+        let mut registers = [0; REGISTERS];
+        registers[register::WHO_AM_I as usize] = DEVICE_ID;
+        registers[register::CTRL_REG1 as usize] = Config::default().to_registers()[0];
+
+        Self {
+            registers,
+            fifo: [[0; 3]; FIFO_SIZE],
+            fifo_head: 0,
+            fifo_len: 0,
+            triggered: false,
+            durations: [0; 2],
+            data_ready: false,
+        }
+    }
+
+    /// Gera uma nova amostra com os valores brutos de `OUT_X`, `OUT_Y` e `OUT_Z`.
+    pub fn set_raw_acceleration(&mut self, raw: [i16; 3]) {
+        for (address, value) in [register::OUT_X_L, register::OUT_Y_L, register::OUT_Z_L]
+            .into_iter()
+            .zip(raw)
+        {
+            let [low, high] = value.to_le_bytes();
+            self.registers[address as usize] = low;
+            self.registers[address as usize + 1] = high;
+        }
+        self.data_ready = true;
+
+        // Sem latch, o clique só fica ativo até a próxima amostra
+        if self.registers[register::CLICK_THS as usize] & LIR_CLICK == 0 {
+            self.registers[register::CLICK_SRC as usize] = 0;
+        }
+
+        for generator in 0..2 {
+            self.evaluate_interrupt(generator, raw);
+        }
+
+        if self.fifo_enabled() {
+            self.push_fifo(raw);
+        }
+    }
+
+    /// Gera uma nova amostra em g, codificada de acordo com o fundo de escala e a resolução
+    /// configurados no momento. Valores fora da escala saturam, como no sensor.
+    pub fn set_acceleration(&mut self, g: [f32; 3]) {
+        let config = self.config();
+        let bits = config.mode.resolution_bits();
+        let sensitivity = config.scale.sensitivity_mg(config.mode);
+        let max = (1 << (bits - 1)) - 1;
+
+        let raw = g.map(|g| {
+            let counts = g * 1000.0 / sensitivity;
+            // Arredonda para o inteiro mais próximo (`round` não existe em `core`)
+            let counts = if counts >= 0.0 {
+                counts + 0.5
+            } else {
+                counts - 0.5
+            } as i32;
+            (counts.clamp(-max - 1, max) << (16 - bits)) as i16
+        });
+        self.set_raw_acceleration(raw);
+    }
+
+    /// Simula um clique detectado no eixo `axis`. Só tem efeito se o clique (simples ou duplo)
+    /// estiver habilitado para o eixo no `CLICK_CFG`.
+    pub fn click(&mut self, axis: Axis, double: bool, negative: bool) {
+        let axis = axis as u8;
+        let enable = 1 << (2 * axis + double as u8);
+        if self.registers[register::CLICK_CFG as usize] & enable == 0 {
+            return;
+        }
+
+        let kind = if double { 1 << 5 } else { 1 << 4 };
+        let sign = if negative { 1 << 3 } else { 0 };
+        self.registers[register::CLICK_SRC as usize] = IA | kind | sign | 1 << axis;
+    }
+
+    /// Configuração atual, lida dos registradores de controle.
+    pub fn config(&self) -> Config {
+        let ctrl = register::CTRL_REG1 as usize;
+        let mut registers = [0; 4];
+        registers.copy_from_slice(&self.registers[ctrl..ctrl + 4]);
+
+        Config::from_registers(registers)
+    }
+
+    /// Valor de um registrador, sem os efeitos colaterais de uma leitura pelo SPI.
+    pub fn register(&self, address: u8) -> u8 {
+        let address = address % REGISTERS as u8;
+        match address {
+            register::STATUS_REG => {
+                if self.data_ready {
+                    0x0F
+                } else {
+                    0
+                }
+            }
+            register::FIFO_SRC_REG => self.fifo_src(),
+            register::OUT_X_L..=register::OUT_Z_H if self.fifo_enabled() && self.fifo_len > 0 => {
+                let offset = (address - register::OUT_X_L) as usize;
+                self.fifo[self.fifo_head][offset / 2].to_le_bytes()[offset % 2]
+            }
+            _ => self.registers[address as usize],
+        }
+    }
+
+    /// O pino INT1 está ativo (independente da polaridade configurada).
+    pub fn int1(&self) -> bool {
+        let routing = self.registers[register::CTRL_REG3 as usize];
+        let fifo = self.fifo_src();
+
+        [
+            (
+                1 << 7,
+                self.registers[register::CLICK_SRC as usize] & IA != 0,
+            ),
+            (
+                1 << 6,
+                self.registers[register::INT1_SRC as usize] & IA != 0,
+            ),
+            (
+                1 << 5,
+                self.registers[register::INT2_SRC as usize] & IA != 0,
+            ),
+            (1 << 4, self.data_ready),
+            (1 << 2, fifo & (1 << 7) != 0),
+            (1 << 1, fifo & (1 << 6) != 0),
+        ]
+        .into_iter()
+        .any(|(bit, active)| routing & bit != 0 && active)
+    }
+
+    /// O pino INT2 está ativo (independente da polaridade configurada).
+    pub fn int2(&self) -> bool {
+        let routing = self.registers[register::CTRL_REG6 as usize];
+
+        [
+            (1 << 7, register::CLICK_SRC),
+            (1 << 6, register::INT1_SRC),
+            (1 << 5, register::INT2_SRC),
+        ]
+        .into_iter()
+        .any(|(bit, src)| routing & bit != 0 && self.registers[src as usize] & IA != 0)
+    }
+
+    fn fifo_config(&self) -> FifoConfig {
+        FifoConfig::from_bits(self.registers[register::FIFO_CTRL_REG as usize])
+    }
+
+    fn fifo_enabled(&self) -> bool {
+        self.registers[register::CTRL_REG5 as usize] & FIFO_EN != 0
+            && self.fifo_config().mode != FifoMode::Bypass
+    }
+
+    fn fifo_src(&self) -> u8 {
+        let watermark = self.fifo_len > self.fifo_config().watermark as usize;
+        let overrun = self.fifo_len == FIFO_SIZE;
+        let empty = self.fifo_len == 0;
+
+        (watermark as u8) << 7
+            | (overrun as u8) << 6
+            | (empty as u8) << 5
+            | self.fifo_len as u8 & 0x1F
+    }
+
+    fn push_fifo(&mut self, sample: [i16; 3]) {
+        let config = self.fifo_config();
+        let stop_when_full = match config.mode {
+            FifoMode::Bypass => return,
+            FifoMode::Fifo => true,
+            FifoMode::Stream => false,
+            FifoMode::StreamToFifo => {
+                let trigger = match config.trigger {
+                    FifoTrigger::Interrupt1 => register::INT1_SRC,
+                    FifoTrigger::Interrupt2 => register::INT2_SRC,
+                };
+                self.triggered |= self.registers[trigger as usize] & IA != 0;
+                self.triggered
+            }
+        };
+
+        if self.fifo_len == FIFO_SIZE {
+            if stop_when_full {
+                return;
+            }
+            self.pop_fifo();
+        }
+
+        self.fifo[(self.fifo_head + self.fifo_len) % FIFO_SIZE] = sample;
+        self.fifo_len += 1;
+    }
+
+    fn pop_fifo(&mut self) {
+        if self.fifo_len > 0 {
+            self.fifo_head = (self.fifo_head + 1) % FIFO_SIZE;
+            self.fifo_len -= 1;
+        }
+    }
+
+    /// Avalia um gerador de interrupção (0 para `INT1_*`, 1 para `INT2_*`) com a nova amostra.
+    fn evaluate_interrupt(&mut self, generator: usize, sample: [i16; 3]) {
+        let base = (register::INT1_CFG + 4 * generator as u8) as usize;
+        let cfg = self.registers[base];
+        let threshold = (self.registers[base + 2] & 0x7F) as i16;
+        let duration = (self.registers[base + 3] & 0x7F).max(1);
+        let latch = [1 << 3, 1 << 1][generator];
+        let latched = self.registers[register::CTRL_REG5 as usize] & latch != 0;
+
+        // O limiar tem a resolução do byte mais significativo da amostra
+        let mut events = 0;
+        for (i, value) in sample.into_iter().enumerate() {
+            let value = (value >> 8).abs();
+            if value < threshold {
+                events |= 1 << (2 * i);
+            }
+            if value > threshold {
+                events |= 1 << (2 * i + 1);
+            }
+        }
+
+        let enabled = cfg & 0x3F;
+        let hit = events & enabled;
+        let and = cfg & (1 << 7) != 0;
+        let condition = enabled != 0 && if and { hit == enabled } else { hit != 0 };
+
+        self.durations[generator] = if condition {
+            self.durations[generator].saturating_add(1)
+        } else {
+            0
+        };
+
+        let src = &mut self.registers[base + 1];
+        if condition && self.durations[generator] >= duration {
+            *src = IA | hit;
+        } else if !latched {
+            *src = 0;
+        }
+    }
+
+    /// Efeitos colaterais da leitura de um registrador pelo SPI.
+    fn on_read(&mut self, address: u8) {
+        match address {
+            register::INT1_SRC | register::INT2_SRC => {
+                let latch = if address == register::INT1_SRC {
+                    1 << 3
+                } else {
+                    1 << 1
+                };
+                if self.registers[register::CTRL_REG5 as usize] & latch != 0 {
+                    self.registers[address as usize] = 0;
+                }
+            }
+            register::CLICK_SRC
+                if self.registers[register::CLICK_THS as usize] & LIR_CLICK != 0 =>
+            {
+                self.registers[address as usize] = 0;
+            }
+            // Ler o byte alto do Z conclui a leitura da amostra
+            register::OUT_Z_H => {
+                self.data_ready = false;
+                if self.fifo_enabled() {
+                    self.pop_fifo();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn on_write(&mut self, address: u8) {
+        // Voltar para o modo Bypass esvazia a FIFO
+        if address == register::FIFO_CTRL_REG && self.fifo_config().mode == FifoMode::Bypass {
+            self.fifo_len = 0;
+            self.triggered = false;
+        }
+    }
+
+    fn is_read_only(address: u8) -> bool {
+        matches!(
+            address,
+            register::WHO_AM_I | register::STATUS_REG | register::OUT_X_L
+                ..=register::OUT_Z_H
+                    | register::FIFO_SRC_REG
+                    | register::INT1_SRC
+                    | register::INT2_SRC
+                    | register::CLICK_SRC
+        )
+    }
+
+    /// Troca um byte com o mestre: recebe `mosi` e devolve o que o sensor coloca no MISO.
+    fn exchange(&mut self, command: &mut Option<Command>, mosi: u8) -> u8 {
+        let Some(cmd) = command else {
+            *command = Some(Command {
+                address: mosi & ADDRESS_MASK,
+                read: mosi & READ != 0,
+                auto_increment: mosi & AUTO_INCREMENT != 0,
+            });
+            // Enquanto recebe o comando o sensor não dirige o MISO
+            return 0xFF;
+        };
+
+        let miso = if cmd.read {
+            let value = self.register(cmd.address);
+            self.on_read(cmd.address);
+            value
+        } else {
+            // !!! Como no sensor real, escrita em registrador somente leitura é ignorada
+            if !Self::is_read_only(cmd.address) {
+                self.registers[cmd.address as usize] = mosi;
+                self.on_write(cmd.address);
+            }
+            0xFF
+        };
+
+        if cmd.auto_increment {
+            // Com a FIFO ligada, o auto incremento volta ao início da amostra para entregar a
+            // próxima, permitindo ler várias amostras na mesma transação
+            cmd.address = if cmd.address == register::OUT_Z_H && self.fifo_enabled() {
+                register::OUT_X_L
+            } else {
+                (cmd.address + 1) % REGISTERS as u8
+            };
+        }
+
+        miso
+    }
+}
+
+impl Default for Lis2dh12 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ErrorType for Lis2dh12 {
+    type Error = Infallible;
+}
+
+impl SpiDevice for Lis2dh12 {
+    fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), Infallible> {
+        // Cada transação corresponde a um ciclo do chip select, então começa sem comando
+        let mut command = None;
+
+        for operation in operations.iter_mut() {
+            match operation {
+                Operation::Read(buf) => {
+                    for byte in buf.iter_mut() {
+                        *byte = self.exchange(&mut command, 0);
+                    }
+                }
+                Operation::Write(data) => {
+                    for &byte in data.iter() {
+                        self.exchange(&mut command, byte);
+                    }
+                }
+                Operation::Transfer(read, write) => {
+                    for i in 0..read.len().max(write.len()) {
+                        let miso = self.exchange(&mut command, write.get(i).copied().unwrap_or(0));
+                        if let Some(byte) = read.get_mut(i) {
+                            *byte = miso;
+                        }
+                    }
+                }
+                Operation::TransferInPlace(data) => {
+                    for byte in data.iter_mut() {
+                        *byte = self.exchange(&mut command, *byte);
+                    }
+                }
+                Operation::DelayNs(_) => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl embedded_hal_async::spi::SpiDevice for Lis2dh12 {
+    async fn transaction(
+        &mut self,
+        operations: &mut [Operation<'_, u8>],
+    ) -> Result<(), Infallible> {
+        SpiDevice::transaction(self, operations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_full_duplex_read() {
+        let mut sim = Lis2dh12::new();
+        let mut frame = [READ | register::WHO_AM_I, 0];
+
+        sim.transfer_in_place(&mut frame).unwrap();
+
+        assert_eq!(frame, [0xFF, DEVICE_ID]);
+    }
+
+    #[test]
+    fn test_without_auto_increment_repeats_address() {
+        let mut sim = Lis2dh12::new();
+        sim.set_raw_acceleration([0x0201, 0, 0]);
+        let mut buf = [0; 3];
+
+        sim.transaction(&mut [
+            Operation::Write(&[READ | register::OUT_X_L]),
+            Operation::Read(&mut buf),
+        ])
+        .unwrap();
+
+        assert_eq!(buf, [0x01, 0x01, 0x01]);
+    }
+
+    #[test]
+    fn test_read_only_registers_ignore_writes() {
+        let mut sim = Lis2dh12::new();
+
+        sim.write(&[AUTO_INCREMENT | register::WHO_AM_I, 0x00])
+            .unwrap();
+        sim.write(&[register::OUT_X_L, 0xAA]).unwrap();
+
+        assert_eq!(sim.register(register::WHO_AM_I), DEVICE_ID);
+        assert_eq!(sim.register(register::OUT_X_L), 0);
+    }
+}
@@ -0,0 +1,90 @@
+"""Gera os traces sintéticos usados nos testes do módulo `motion`.
+
+!!! Não são gravações de um LIS2DH12 real: são sinais montados aqui, e os limiares do módulo
+`motion` foram ajustados com eles. Servem para testes de regressão, não para validar os
+algoritmos com movimento real.
+
+Os traces simulam o sensor preso à cintura, amostrado a 50 Hz no modo normal (4 mg por dígito),
+com o eixo Z aproximadamente vertical. Cada passo é um ciclo da aceleração vertical, com uma
+harmônica e ruído gaussiano. A semente é fixa, então rodar o script de novo gera os mesmos
+arquivos.
+
+    python3 generate.py
+"""
+
+import math
+import random
+
+RATE = 50.0
+DURATION = 10.0
+RESOLUTION = 0.004
+
+
+def quantize(value):
+    return round(value / RESOLUTION) * RESOLUTION
+
+
+def write(name, samples):
+    with open(name, "w") as file:
+        file.write("t,x,y,z\n")
+        for i, (x, y, z) in enumerate(samples):
+            file.write(
+                f"{i / RATE:.2f},{quantize(x):.3f},{quantize(y):.3f},{quantize(z):.3f}\n"
+            )
+
+
+def gait(seed, cadence, amplitude, noise):
+    """Caminhada ou corrida: `cadence` em passos por segundo e `amplitude` em g."""
+    rng = random.Random(seed)
+    phase = 0.0
+    samples = []
+    for i in range(int(DURATION * RATE)):
+        t = i / RATE
+        # A cadência varia um pouco, como em uma pessoa de verdade
+        phase += 2 * math.pi * (cadence + 0.05 * math.sin(0.5 * t)) / RATE
+        vertical = amplitude * (math.sin(phase) + 0.3 * math.sin(2 * phase + 0.5))
+        forward = 0.5 * amplitude * math.sin(phase + 1.0)
+        samples.append(
+            (
+                0.05 + forward + rng.gauss(0, noise),
+                0.10 + rng.gauss(0, noise),
+                0.99 + vertical + rng.gauss(0, noise),
+            )
+        )
+    return samples, int(phase / (2 * math.pi))
+
+
+def still(seed):
+    rng = random.Random(seed)
+    gravity = (0.17, -0.10, 0.98)
+    return [tuple(g + rng.gauss(0, 0.008) for g in gravity) for _ in range(int(DURATION * RATE))]
+
+
+def tilt(seed):
+    """Pitch de 0° a 40° nos primeiros 5 s, depois roll de 0° a -30°."""
+    rng = random.Random(seed)
+    samples = []
+    for i in range(int(DURATION * RATE)):
+        t = i / RATE
+        pitch = math.radians(40 * min(t / 5, 1))
+        roll = math.radians(-30 * max(min((t - 5) / 4, 1), 0))
+        gravity = (
+            math.sin(pitch),
+            math.cos(pitch) * math.sin(roll),
+            math.cos(pitch) * math.cos(roll),
+        )
+        samples.append(tuple(g + rng.gauss(0, 0.01) for g in gravity))
+    return samples
+
+
+if __name__ == "__main__":
+    walking, steps = gait(1, 1.8, 0.25, 0.02)
+    print("walking", steps)
+    write("walking.csv", walking)
+
+    running, steps = gait(2, 2.8, 0.9, 0.05)
+    print("running", steps)
+    write("running.csv", running)
+
+    write("still.csv", still(3))
+    write("tilt.csv", tilt(4))
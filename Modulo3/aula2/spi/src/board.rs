@@ -0,0 +1,107 @@
+//! Camada da placa: tudo que é específico do ESP32-C3 para falar com o LIS2DH.
+//!
+//! Aqui ficam a escolha dos pinos, a configuração do SPI2 com DMA e um adaptador que expõe o
+//! `SpiDma` do HAL como um `SpiDevice` do `embedded-hal-async` 1.0, que é o que o driver espera.
+
+use embassy_time::{Duration, Timer};
+use embedded_hal::spi::{ErrorKind, ErrorType, Operation};
+use embedded_hal_async::spi::{SpiBus, SpiBusRead, SpiBusWrite};
+use embedded_hal_async_1::spi::SpiDevice;
+use hal::clock::Clocks;
+use hal::dma::{ChannelRx, ChannelTx, DmaPriority};
+use hal::gdma::{Channel0, Channel0RxImpl, Channel0TxImpl, Gdma, SuitablePeripheral0};
+use hal::peripherals::SPI2;
+use hal::prelude::_esp_hal_spi_dma_WithDmaSpi2;
+use hal::prelude::_fugit_RateExtU32;
+use hal::spi::dma::SpiDma;
+use hal::spi::{Spi, SpiMode};
+use hal::system::PeripheralClockControl;
+use hal::IO;
+use lis2dh::Lis2dh;
+
+type Spi2Dma<'a> = SpiDma<
+    'a,
+    SPI2,
+    ChannelTx<'a, Channel0TxImpl, Channel0>,
+    ChannelRx<'a, Channel0RxImpl, Channel0>,
+    SuitablePeripheral0,
+>;
+
+/// `SpiDevice` sobre o SPI2 com DMA. O chip select (GPIO10) é controlado pelo próprio periférico.
+pub struct Lis2dhSpi<'a> {
+    spi: Spi2Dma<'a>,
+}
+
+impl ErrorType for Lis2dhSpi<'_> {
+    type Error = ErrorKind;
+}
+
+impl SpiDevice for Lis2dhSpi<'_> {
+    async fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), ErrorKind> {
+        for operation in operations.iter_mut() {
+            match operation {
+                Operation::Read(buf) => SpiBusRead::read(&mut self.spi, buf).await,
+                Operation::Write(data) => SpiBusWrite::write(&mut self.spi, data).await,
+                Operation::Transfer(read, write) => {
+                    SpiBus::transfer(&mut self.spi, read, write).await
+                }
+                Operation::TransferInPlace(data) => {
+                    SpiBus::transfer_in_place(&mut self.spi, data).await
+                }
+                Operation::DelayNs(ns) => {
+                    Timer::after(Duration::from_micros((*ns as u64).div_ceil(1000))).await;
+                    Ok(())
+                }
+            }
+            .map_err(|_| ErrorKind::Other)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Configura o SPI2 (SCK: GPIO6, MISO: GPIO2, MOSI: GPIO7, CS: GPIO10) com o canal 0 do DMA e
+/// cria o driver sobre ele.
+pub fn lis2dh<'a>(
+    io: IO,
+    dma: Gdma,
+    spi: SPI2,
+    clock_control: &mut PeripheralClockControl,
+    clocks: &Clocks,
+    frequency_khz: u32,
+    tx_descriptors: &'static mut [u32],
+    rx_descriptors: &'static mut [u32],
+) -> Lis2dh<Lis2dhSpi<'a>> {
+    hal::interrupt::enable(
+        hal::peripherals::Interrupt::DMA_CH0,
+        hal::interrupt::Priority::Priority1,
+    )
+    .unwrap();
+
+    let sck = io.pins.gpio6;
+    let miso = io.pins.gpio2;
+    let mosi = io.pins.gpio7;
+    let cs = io.pins.gpio10;
+
+    let dma_channel = dma.channel0;
+
+    let spi = Spi::new(
+        spi,
+        sck,
+        mosi,
+        miso,
+        cs,
+        frequency_khz.kHz(),
+        SpiMode::Mode0,
+        clock_control,
+        clocks,
+    )
+    .with_dma(dma_channel.configure(
+        false,
+        tx_descriptors,
+        rx_descriptors,
+        DmaPriority::Priority0,
+    ));
+
+    Lis2dh::new(Lis2dhSpi { spi })
+}
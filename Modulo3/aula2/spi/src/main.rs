@@ -7,11 +7,11 @@
 #![no_main]
 #![feature(type_alias_impl_trait)]
 
-mod lis2dh;
+mod board;
 
 extern crate alloc;
 
-use crate::lis2dh::Lis2dh;
+use crate::board::Lis2dhSpi;
 use embassy_executor::Executor;
 use embassy_executor::_export::StaticCell;
 use embassy_time::{Duration, Timer};
@@ -21,6 +21,9 @@ use hal::{
     clock::ClockControl, embassy, gdma::*, peripherals::Peripherals, prelude::*, timer::TimerGroup,
     Rtc, IO,
 };
+use lis2dh::config::{Config, DataRate, Mode};
+use lis2dh::fifo::{FifoConfig, FifoMode, FIFO_SIZE};
+use lis2dh::{Acc, AccVector, Lis2dh};
 
 macro_rules! singleton {
     ($val:expr) => {{
@@ -50,13 +53,41 @@ fn init_heap() {
 }
 
 #[embassy_executor::task]
-async fn acc_task(lis2dh: &'static mut Lis2dh<'static>) {
-    let who_i_am = lis2dh.who_i_am().await;
+async fn acc_task(lis2dh: &'static mut Lis2dh<Lis2dhSpi<'static>>) {
+    let who_i_am = lis2dh.who_i_am().await.unwrap();
     println!("Who I am: {}", who_i_am);
 
+    lis2dh
+        .configure(Config {
+            data_rate: DataRate::Hz10,
+            mode: Mode::HighResolution,
+            block_data_update: true,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    // A 10 Hz chegam ~5 amostras a cada 500 ms, que ficam guardadas na FIFO e são lidas de uma vez
+    lis2dh
+        .configure_fifo(FifoConfig {
+            mode: FifoMode::Stream,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    let mut samples = [(Acc::default(), Acc::default(), Acc::default()); FIFO_SIZE];
     loop {
-        let (x, y, z) = lis2dh.read_all().await;
-        println!("Acc: <{:?}, {}, {}>", x, y, z);
+        let len = lis2dh.read_fifo(&mut samples).await.unwrap();
+        for &sample in &samples[..len] {
+            let acc = AccVector::from(sample);
+            println!(
+                "Acc: {:.3} pitch: {:.1}° roll: {:.1}°",
+                acc,
+                acc.pitch().to_degrees(),
+                acc.roll().to_degrees()
+            );
+        }
         Timer::after(Duration::from_millis(500)).await;
     }
 }
@@ -90,7 +121,7 @@ fn main() -> ! {
     let dma = Gdma::new(peripherals.DMA, &mut system.peripheral_clock_control);
     let tx_descriptors = singleton!([0; 8 * 3]);
     let rx_descriptors = singleton!([0; 8 * 3]);
-    let lis2dh = singleton!(Lis2dh::new(
+    let lis2dh = singleton!(board::lis2dh(
         io,
         dma,
         peripherals.SPI2,
@@ -6,6 +6,8 @@ use panic_halt as _;
 
 #[rtic::app(device = stm32f4xx_hal::pac, dispatchers = [USART1])]
 mod app {
+    // Fila SPSC lock-free, implementada (e testada no host) na aula 7 do Módulo 2
+    use aula7::spsc::{Consumer, Producer, Queue};
     #[allow(unused_imports)]
     use rtic::mutex_prelude::TupleExt03;
     use rtt_target::{rprintln, rtt_init_print};
@@ -17,6 +19,8 @@ mod app {
         timer::MonoTimerUs,
     };
 
+    const CLICKS_SIZE: usize = 8;
+
     // Shared resources go here
     #[shared]
     struct Shared {
@@ -32,14 +36,18 @@ mod app {
     struct Local {
         button: PC8<Input>,
         led: PB0<Output<PushPull>>,
+        // A ISR do botão produz e a task display consome, sem precisar de `lock`
+        clicks_producer: Producer<'static, u8, CLICKS_SIZE>,
+        clicks_consumer: Consumer<'static, u8, CLICKS_SIZE>,
     }
 
     #[monotonic(binds = TIM2, default = true)]
     type MicrosecMono = MonoTimerUs<pac::TIM2>;
 
-    #[init]
+    #[init(local = [clicks: Queue<u8, CLICKS_SIZE> = Queue::new()])]
     fn init(mut ctx: init::Context) -> (Shared, Local, init::Monotonics) {
         rtt_init_print!();
+        let (clicks_producer, clicks_consumer) = ctx.local.clicks.split();
         let mut syscfg = ctx.device.SYSCFG.constrain();
         // clocks
         let rcc = ctx.device.RCC.constrain();
@@ -65,7 +73,12 @@ mod app {
                 s3: 30,
                 key: 7,
             },
-            Local { button, led },
+            Local {
+                button,
+                led,
+                clicks_producer,
+                clicks_consumer,
+            },
             init::Monotonics(mono),
         )
     }
@@ -87,22 +100,28 @@ mod app {
         }
     }
 
-    #[task(shared = [counter, &key], priority = 2)]
+    #[task(shared = [counter, &key], local = [clicks_consumer], priority = 2)]
     fn display(mut ctx: display::Context, name: &'static str) {
         rprintln!("Hello, {}", name);
+        while let Some(click) = ctx.local.clicks_consumer.dequeue() {
+            rprintln!("Click #{}", click);
+        }
         let counter = ctx.shared.counter.lock(|counter| *counter);
         let key: &u8 = ctx.shared.key;
         rprintln!("Counter: {}, key: {}", counter, key);
         display::spawn_after(1.secs(), "Rodrigo").ok();
     }
 
-    #[task(binds = EXTI9_5, shared = [counter], local = [button, led, a: u8 = 0], priority = 3)]
+    #[task(binds = EXTI9_5, shared = [counter], local = [button, led, clicks_producer, a: u8 = 0], priority = 3)]
     fn button_click(mut ctx: button_click::Context) {
         ctx.local.button.clear_interrupt_pending_bit();
         ctx.local.led.toggle();
-        ctx.shared.counter.lock(|counter| {
+        let counter = ctx.shared.counter.lock(|counter| {
             *counter += 1;
+            *counter
         });
+        // Se a fila estiver cheia o clique é descartado, a ISR nunca bloqueia
+        ctx.local.clicks_producer.enqueue(counter).ok();
     }
 }
 
@@ -0,0 +1,235 @@
+//! Transformada discreta de Fourier: a direta (O(n²), qualquer tamanho) e a FFT radix-2.
+
+use std::f32::consts::TAU;
+use std::ops::{Add, Mul, Sub};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Complex {
+    pub re: f32,
+    pub im: f32,
+}
+
+impl Complex {
+    pub const ZERO: Complex = Complex { re: 0.0, im: 0.0 };
+
+    pub fn new(re: f32, im: f32) -> Self {
+        Self { re, im }
+    }
+
+    /// `e^(iθ)`
+    pub fn from_angle(theta: f32) -> Self {
+        Self::new(theta.cos(), theta.sin())
+    }
+
+    pub fn norm(&self) -> f32 {
+        self.re.hypot(self.im)
+    }
+
+    pub fn norm_sqr(&self) -> f32 {
+        self.re * self.re + self.im * self.im
+    }
+
+    /// Ângulo em radianos, em `(-π, π]`.
+    pub fn arg(&self) -> f32 {
+        self.im.atan2(self.re)
+    }
+
+    pub fn conj(&self) -> Self {
+        Self::new(self.re, -self.im)
+    }
+
+    pub fn scale(&self, k: f32) -> Self {
+        Self::new(self.re * k, self.im * k)
+    }
+}
+
+impl Add for Complex {
+    type Output = Complex;
+
+    fn add(self, rhs: Complex) -> Complex {
+        Complex::new(self.re + rhs.re, self.im + rhs.im)
+    }
+}
+
+impl Sub for Complex {
+    type Output = Complex;
+
+    fn sub(self, rhs: Complex) -> Complex {
+        Complex::new(self.re - rhs.re, self.im - rhs.im)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Complex;
+
+    fn mul(self, rhs: Complex) -> Complex {
+        Complex::new(
+            self.re * rhs.re - self.im * rhs.im,
+            self.re * rhs.im + self.im * rhs.re,
+        )
+    }
+}
+
+/// DFT direta, `X[k] = Σ x[n] e^(-2πi kn/N)`. Serve para qualquer tamanho e como referência
+/// nos testes; para sinais longos use [`rfft`].
+pub fn dft(input: &[f32]) -> Vec<Complex> {
+    let n = input.len();
+    (0..n)
+        .map(|k| {
+            input
+                .iter()
+                .enumerate()
+                .fold(Complex::ZERO, |sum, (i, &x)| {
+                    // `k * i` pode ser grande; o resto mantém o ângulo preciso em f32
+                    let angle = -TAU * ((k * i) % n) as f32 / n as f32;
+                    sum + Complex::from_angle(angle).scale(x)
+                })
+        })
+        .collect()
+}
+
+/// FFT radix-2 no próprio vetor (Cooley-Tukey iterativa).
+///
+/// !!! O tamanho precisa ser potência de 2.
+pub fn fft(data: &mut [Complex]) {
+    transform(data, false);
+}
+
+/// Transformada inversa, já dividida por `N`.
+pub fn ifft(data: &mut [Complex]) {
+    transform(data, true);
+    let scale = 1.0 / data.len() as f32;
+    data.iter_mut().for_each(|x| *x = x.scale(scale));
+}
+
+fn transform(data: &mut [Complex], inverse: bool) {
+    let n = data.len();
+    assert!(n.is_power_of_two(), "FFT size must be a power of two");
+
+    // Reordena pelos índices com os bits invertidos
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            data.swap(i, j);
+        }
+    }
+
+    let sign = if inverse { 1.0 } else { -1.0 };
+    let mut len = 2;
+    while len <= n {
+        let step = Complex::from_angle(sign * TAU / len as f32);
+        for start in (0..n).step_by(len) {
+            let mut twiddle = Complex::new(1.0, 0.0);
+            for k in 0..len / 2 {
+                let even = data[start + k];
+                let odd = data[start + k + len / 2] * twiddle;
+                data[start + k] = even + odd;
+                data[start + k + len / 2] = even - odd;
+                twiddle = twiddle * step;
+            }
+        }
+        len <<= 1;
+    }
+}
+
+/// FFT de um sinal real. Devolve só as `N/2 + 1` frequências de 0 até Nyquist; as outras são
+/// os conjugados destas.
+///
+/// As amostras pares e ímpares viram a parte real e a imaginária de um sinal com metade do
+/// tamanho, então a conta é uma FFT complexa de `N/2` pontos.
+///
+/// !!! O tamanho precisa ser potência de 2 (e pelo menos 2).
+pub fn rfft(input: &[f32]) -> Vec<Complex> {
+    let n = input.len();
+    assert!(
+        n >= 2 && n.is_power_of_two(),
+        "FFT size must be a power of two"
+    );
+    let half = n / 2;
+
+    let mut z: Vec<Complex> = input
+        .chunks_exact(2)
+        .map(|pair| Complex::new(pair[0], pair[1]))
+        .collect();
+    fft(&mut z);
+
+    (0..=half)
+        .map(|k| {
+            let a = z[k % half];
+            let b = z[(half - k) % half].conj();
+            // Separa as transformadas das amostras pares e das ímpares
+            let even = (a + b).scale(0.5);
+            let odd = (a - b) * Complex::new(0.0, -0.5);
+            even + Complex::from_angle(-TAU * k as f32 / n as f32) * odd
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signals::noise::Rng;
+
+    fn assert_close(a: &[Complex], b: &[Complex], tolerance: f32) {
+        assert_eq!(a.len(), b.len());
+        for (k, (a, b)) in a.iter().zip(b).enumerate() {
+            assert!((*a - *b).norm() < tolerance, "bin {k}: {a:?} != {b:?}");
+        }
+    }
+
+    fn random(n: usize) -> Vec<f32> {
+        let mut rng = Rng::new(3);
+        (0..n).map(|_| rng.next_f32() * 2.0 - 1.0).collect()
+    }
+
+    #[test]
+    fn test_fft_matches_dft() {
+        let input = random(64);
+        let mut data: Vec<Complex> = input.iter().map(|&x| Complex::new(x, 0.0)).collect();
+        fft(&mut data);
+
+        assert_close(&data, &dft(&input), 1e-4);
+    }
+
+    #[test]
+    fn test_rfft_matches_dft() {
+        for n in [2, 8, 256] {
+            let input = random(n);
+            assert_close(&rfft(&input), &dft(&input)[..=n / 2], 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_inverse() {
+        let input: Vec<Complex> = random(32)
+            .chunks(2)
+            .map(|pair| Complex::new(pair[0], pair[1]))
+            .collect();
+        let mut data = input.clone();
+        fft(&mut data);
+        ifft(&mut data);
+
+        assert_close(&data, &input, 1e-5);
+    }
+
+    /// Um cosseno que cabe inteiro na janela cai todo em uma frequência.
+    #[test]
+    fn test_single_tone() {
+        let n = 128;
+        let input: Vec<f32> = (0..n)
+            .map(|i| (TAU * 5.0 * i as f32 / n as f32).cos())
+            .collect();
+        let spectrum = rfft(&input);
+
+        for (k, bin) in spectrum.iter().enumerate() {
+            let expected = if k == 5 { n as f32 / 2.0 } else { 0.0 };
+            assert!((bin.norm() - expected).abs() < 1e-3, "bin {k}: {bin:?}");
+        }
+    }
+}
@@ -0,0 +1,153 @@
+//! Médias: a exponencial (passa-baixas de primeira ordem) e a móvel.
+
+use super::Filter;
+use crate::numeric::Number;
+use core::f32::consts::TAU;
+
+/// Média exponencial, `y += α (x - y)`, o equivalente discreto de um circuito RC.
+///
+/// Começa em zero, então as primeiras saídas sobem devagar até o valor de entrada.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Exponential<T = f32> {
+    alpha: T,
+    output: T,
+}
+
+impl<T: Number> Exponential<T> {
+    /// !!! `alpha` em `(0, 1]`: perto de zero filtra mais e responde mais devagar; `1` não
+    /// filtra.
+    pub fn new(alpha: T) -> Self {
+        assert!(
+            alpha > T::ZERO && alpha <= T::ONE,
+            "alpha must be in (0, 1]"
+        );
+        Self {
+            alpha,
+            output: T::ZERO,
+        }
+    }
+
+    pub fn alpha(&self) -> T {
+        self.alpha
+    }
+
+    /// O mesmo `alpha` em outro tipo, com a saída zerada.
+    pub fn cast<U: Number>(&self) -> Exponential<U> {
+        Exponential::new(U::from_f32(self.alpha.to_f32()))
+    }
+}
+
+impl Exponential {
+    /// Com constante de tempo `tau` segundos, amostrando a `rate` Hz. A resposta ao degrau é
+    /// a do RC nos instantes de amostragem.
+    pub fn from_time_constant(tau: f32, rate: f32) -> Self {
+        Self::new(1.0 - libm::expf(-1.0 / (tau * rate)))
+    }
+
+    /// Com frequência de corte (-3 dB) em `cutoff` Hz. Só é exato para `cutoff` bem abaixo
+    /// da taxa de amostragem.
+    pub fn from_cutoff(cutoff: f32, rate: f32) -> Self {
+        Self::from_time_constant(1.0 / (TAU * cutoff), rate)
+    }
+}
+
+impl<T: Number> Filter<T> for Exponential<T> {
+    fn update(&mut self, input: T) -> T {
+        self.output += self.alpha * (input - self.output);
+        self.output
+    }
+
+    fn reset(&mut self) {
+        self.output = T::ZERO;
+    }
+}
+
+/// Média das últimas `N` amostras.
+///
+/// Antes de chegarem `N` amostras, as que faltam contam como zero.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MovingAverage<const N: usize, T = f32> {
+    history: [T; N],
+    /// Onde entra a próxima amostra (e sai a mais antiga).
+    head: usize,
+    sum: T,
+}
+
+impl<const N: usize, T: Number> MovingAverage<N, T> {
+    pub fn new() -> Self {
+        assert!(N > 0, "moving average over zero samples");
+        Self {
+            history: [T::ZERO; N],
+            head: 0,
+            sum: T::ZERO,
+        }
+    }
+}
+
+impl<const N: usize, T: Number> Default for MovingAverage<N, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize, T: Number> Filter<T> for MovingAverage<N, T> {
+    fn update(&mut self, input: T) -> T {
+        self.sum += input - self.history[self.head];
+        self.history[self.head] = input;
+        self.head = (self.head + 1) % N;
+        if self.head == 0 {
+            // Refaz a soma a cada volta, senão o erro de arredondamento vai se acumulando
+            self.sum = self.history.iter().fold(T::ZERO, |sum, &x| sum + x);
+        }
+        self.sum / T::from_int(N as i32)
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsp::response::FrequencyResponse;
+
+    #[test]
+    fn test_exponential() {
+        let (tau, rate) = (0.1, 1000.0);
+        let mut filter = Exponential::from_time_constant(tau, rate);
+
+        // Depois de uma constante de tempo, 63% do degrau
+        let output: Vec<f32> = filter.process([1.0; 100]).collect();
+        assert!((output[99] - (1.0 - (-1.0f32).exp())).abs() < 1e-3);
+
+        let response =
+            FrequencyResponse::measure(&mut Exponential::from_cutoff(4.0, 1024.0), 1024.0, 8192);
+        assert!((response.gain(0.0) - 1.0).abs() < 1e-3);
+        assert!((response.gain(4.0) - 0.5f32.sqrt()).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_moving_average() {
+        let mut filter = MovingAverage::<4>::new();
+        let output: Vec<f32> = filter.process([4.0, 4.0, 4.0, 4.0, 8.0, 8.0]).collect();
+        assert_eq!(output, [1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+
+        // Não acumula erro em uma sequência longa
+        let mut filter = MovingAverage::<10>::new();
+        let last = filter
+            .process((0..100_000).map(|k| 1000.0 + (k % 7) as f32 * 0.1))
+            .last();
+        let expected = (99_990..100_000)
+            .map(|k| 1000.0 + (k % 7) as f32 * 0.1)
+            .sum::<f32>()
+            / 10.0;
+        assert!((last.unwrap() - expected).abs() < 1e-4);
+
+        // Zeros nos múltiplos de rate / N
+        let response = FrequencyResponse::measure(&mut MovingAverage::<8>::new(), 1024.0, 1024);
+        assert!((response.gain(0.0) - 1.0).abs() < 1e-6);
+        assert!(response.gain(128.0) < 1e-6);
+        assert!(response.gain(256.0) < 1e-6);
+    }
+}
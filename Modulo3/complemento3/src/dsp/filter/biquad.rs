@@ -0,0 +1,246 @@
+//! Filtros IIR de segunda ordem (biquads) pelas fórmulas do "Audio EQ Cookbook" de Robert
+//! Bristow-Johnson, e cascatas deles para ordens maiores.
+//!
+//! As fórmulas vêm de filtros analógicos passados pela transformação bilinear com a
+//! frequência pré-distorcida, então o ganho na frequência de projeto é exatamente o do
+//! analógico.
+
+use super::Filter;
+use crate::numeric::Number;
+use core::f32::consts::{FRAC_1_SQRT_2, PI, TAU};
+
+/// `Q` de um passa-baixas ou passa-altas de Butterworth de segunda ordem: o mais plano sem
+/// pico na resposta.
+pub const BUTTERWORTH_Q: f32 = FRAC_1_SQRT_2;
+
+/// `H(z) = (b0 + b1 z⁻¹ + b2 z⁻²) / (1 + a1 z⁻¹ + a2 z⁻²)`, calculado na forma direta II
+/// transposta (duas variáveis de estado).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Biquad<T = f32> {
+    b: [T; 3],
+    a: [T; 2],
+    state: [T; 2],
+}
+
+impl<T: Number> Biquad<T> {
+    /// `b = [b0, b1, b2]` e `a = [a1, a2]`, já divididos por `a0`.
+    pub fn new(b: [T; 3], a: [T; 2]) -> Self {
+        Self {
+            b,
+            a,
+            state: [T::ZERO; 2],
+        }
+    }
+
+    /// `(b, a)`, no formato de [`Biquad::new`].
+    pub fn coefficients(&self) -> ([T; 3], [T; 2]) {
+        (self.b, self.a)
+    }
+
+    /// Os mesmos coeficientes em outro tipo, com o estado zerado.
+    ///
+    /// !!! Com o corte muito abaixo da taxa de amostragem os polos ficam perto de 1, e o
+    /// arredondamento dos coeficientes muda a resposta (pode até deixar o filtro instável).
+    /// Confira a resposta já convertida. Em [`Q31`](crate::numeric::Q31) não cabe `|a1|`
+    /// maior que 1.
+    pub fn cast<U: Number>(&self) -> Biquad<U> {
+        let convert = |x: T| U::from_f32(x.to_f32());
+        Biquad::new(self.b.map(convert), self.a.map(convert))
+    }
+}
+
+impl Biquad {
+    /// Passa-baixas com corte em `cutoff` Hz. Com [`BUTTERWORTH_Q`] o ganho no corte é
+    /// `1/√2` (-3 dB); `q` maior cria um pico de ganho `q` no corte.
+    pub fn lowpass(cutoff: f32, q: f32, rate: f32) -> Self {
+        let (cos, alpha) = prewarp(cutoff, q, rate);
+        let b1 = 1.0 - cos;
+        Self::normalized(
+            [b1 / 2.0, b1, b1 / 2.0],
+            [1.0 + alpha, -2.0 * cos, 1.0 - alpha],
+        )
+    }
+
+    /// Passa-altas com corte em `cutoff` Hz.
+    pub fn highpass(cutoff: f32, q: f32, rate: f32) -> Self {
+        let (cos, alpha) = prewarp(cutoff, q, rate);
+        let b1 = -(1.0 + cos);
+        Self::normalized(
+            [-b1 / 2.0, b1, -b1 / 2.0],
+            [1.0 + alpha, -2.0 * cos, 1.0 - alpha],
+        )
+    }
+
+    /// Passa-faixa com ganho 1 em `center` Hz e largura de banda (entre os pontos de -3 dB) de
+    /// mais ou menos `center / q`.
+    pub fn bandpass(center: f32, q: f32, rate: f32) -> Self {
+        let (cos, alpha) = prewarp(center, q, rate);
+        Self::normalized([alpha, 0.0, -alpha], [1.0 + alpha, -2.0 * cos, 1.0 - alpha])
+    }
+
+    /// Rejeita-faixa com ganho zero em `center` Hz, para tirar uma interferência (a rede
+    /// elétrica, uma vibração do motor). `q` maior deixa o corte mais estreito.
+    pub fn notch(center: f32, q: f32, rate: f32) -> Self {
+        let (cos, alpha) = prewarp(center, q, rate);
+        Self::normalized(
+            [1.0, -2.0 * cos, 1.0],
+            [1.0 + alpha, -2.0 * cos, 1.0 - alpha],
+        )
+    }
+
+    /// Divide tudo por `a0`.
+    fn normalized(b: [f32; 3], a: [f32; 3]) -> Self {
+        Self::new(b.map(|b| b / a[0]), [a[1] / a[0], a[2] / a[0]])
+    }
+}
+
+/// `cos(ω0)` e `α = sin(ω0) / 2q` das fórmulas do cookbook.
+fn prewarp(frequency: f32, q: f32, rate: f32) -> (f32, f32) {
+    assert!(
+        frequency > 0.0 && frequency < rate / 2.0,
+        "frequency must be between 0 and half the sample rate"
+    );
+    let w0 = TAU * frequency / rate;
+    (libm::cosf(w0), libm::sinf(w0) / (2.0 * q))
+}
+
+impl<T: Number> Filter<T> for Biquad<T> {
+    fn update(&mut self, input: T) -> T {
+        let [b0, b1, b2] = self.b;
+        let [a1, a2] = self.a;
+        let [s1, s2] = self.state;
+
+        let output = b0 * input + s1;
+        self.state = [b1 * input - a1 * output + s2, b2 * input - a2 * output];
+        output
+    }
+
+    fn reset(&mut self) {
+        self.state = [T::ZERO; 2];
+    }
+}
+
+/// `N` biquads em série, um filtro de ordem `2N`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cascade<const N: usize, T = f32> {
+    stages: [Biquad<T>; N],
+}
+
+impl<const N: usize, T: Number> Cascade<N, T> {
+    pub fn new(stages: [Biquad<T>; N]) -> Self {
+        Self { stages }
+    }
+
+    pub fn stages(&self) -> &[Biquad<T>; N] {
+        &self.stages
+    }
+
+    /// Os mesmos estágios em outro tipo; veja [`Biquad::cast`].
+    pub fn cast<U: Number>(&self) -> Cascade<N, U> {
+        Cascade::new(self.stages.each_ref().map(Biquad::cast))
+    }
+}
+
+impl<const N: usize> Cascade<N> {
+    /// Passa-baixas de Butterworth de ordem `2N`: ganho `1/√2` no corte e queda de `12N` dB
+    /// por oitava depois dele.
+    pub fn lowpass(cutoff: f32, rate: f32) -> Self {
+        Self::new(core::array::from_fn(|k| {
+            Biquad::lowpass(cutoff, butterworth_q::<N>(k), rate)
+        }))
+    }
+
+    /// Passa-altas de Butterworth de ordem `2N`.
+    pub fn highpass(cutoff: f32, rate: f32) -> Self {
+        Self::new(core::array::from_fn(|k| {
+            Biquad::highpass(cutoff, butterworth_q::<N>(k), rate)
+        }))
+    }
+}
+
+/// `Q` do estágio `k`: os polos de Butterworth ficam igualmente espaçados em um semicírculo,
+/// e cada par conjugado vira um biquad.
+fn butterworth_q<const N: usize>(k: usize) -> f32 {
+    let angle = PI * (2 * k + 1) as f32 / (4 * N) as f32;
+    1.0 / (2.0 * libm::cosf(angle))
+}
+
+impl<const N: usize, T: Number> Filter<T> for Cascade<N, T> {
+    fn update(&mut self, input: T) -> T {
+        self.stages
+            .iter_mut()
+            .fold(input, |signal, stage| stage.update(signal))
+    }
+
+    fn reset(&mut self) {
+        self.stages.iter_mut().for_each(Biquad::reset);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsp::response::FrequencyResponse;
+
+    const RATE: f32 = 1024.0;
+
+    fn response<F: Filter>(mut filter: F) -> FrequencyResponse {
+        FrequencyResponse::measure(&mut filter, RATE, 4096)
+    }
+
+    #[test]
+    fn test_biquads() {
+        let lowpass = response(Biquad::lowpass(100.0, BUTTERWORTH_Q, RATE));
+        assert!((lowpass.gain(0.0) - 1.0).abs() < 1e-4);
+        assert!((lowpass.gain(100.0) - FRAC_1_SQRT_2).abs() < 1e-3);
+        assert!(lowpass.gain(512.0) < 1e-3);
+
+        let resonant = response(Biquad::lowpass(100.0, 4.0, RATE));
+        assert!((resonant.gain(100.0) - 4.0).abs() < 0.01);
+
+        let highpass = response(Biquad::highpass(100.0, BUTTERWORTH_Q, RATE));
+        assert!(highpass.gain(0.0) < 1e-4);
+        assert!((highpass.gain(100.0) - FRAC_1_SQRT_2).abs() < 1e-3);
+        assert!((highpass.gain(512.0) - 1.0).abs() < 1e-3);
+
+        let bandpass = response(Biquad::bandpass(100.0, 5.0, RATE));
+        assert!((bandpass.gain(100.0) - 1.0).abs() < 1e-3);
+        assert!(bandpass.gain(0.0) < 1e-4);
+        assert!(bandpass.gain(300.0) < 0.1);
+
+        let notch = response(Biquad::notch(60.0, 10.0, RATE));
+        assert!(notch.gain(60.0) < 1e-3);
+        assert!((notch.gain(0.0) - 1.0).abs() < 1e-4);
+        assert!((notch.gain(120.0) - 1.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_butterworth_cascade() {
+        let q: Vec<f32> = (0..2).map(butterworth_q::<2>).collect();
+        assert!((q[0] - 0.5412).abs() < 1e-4 && (q[1] - 1.3066).abs() < 1e-4);
+
+        // Quarta ordem: plano na banda de passagem e pelo menos -24 dB por oitava (a
+        // transformação bilinear ainda atenua um pouco mais perto de Nyquist)
+        let lowpass = response(Cascade::<2>::lowpass(50.0, RATE));
+        assert!((lowpass.gain(25.0) - 1.0).abs() < 0.01);
+        assert!((lowpass.gain(50.0) - FRAC_1_SQRT_2).abs() < 1e-3);
+        assert!(lowpass.gain_db(200.0) < -48.0);
+
+        let highpass = response(Cascade::<3>::highpass(50.0, RATE));
+        assert!((highpass.gain(50.0) - FRAC_1_SQRT_2).abs() < 1e-3);
+        assert!((highpass.gain(200.0) - 1.0).abs() < 0.01);
+    }
+
+    /// Degrau no passa-baixas: chega ao valor final e a saída não depende de onde a cascata
+    /// começou depois do reset.
+    #[test]
+    fn test_step_and_reset() {
+        let mut filter = Cascade::<2>::lowpass(10.0, RATE);
+        let first: Vec<f32> = filter.process([1.0; 1024]).collect();
+        assert!((first[1023] - 1.0).abs() < 1e-4);
+
+        filter.reset();
+        let second: Vec<f32> = filter.process([1.0; 1024]).collect();
+        assert_eq!(first, second);
+    }
+}
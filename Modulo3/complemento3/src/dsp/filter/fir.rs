@@ -0,0 +1,191 @@
+//! Filtros FIR (resposta ao impulso finita), com projeto pelo método da sinc janelada.
+//!
+//! O filtro ideal tem resposta ao impulso `sinc` infinita; o projeto corta `N` coeficientes em
+//! volta do centro e multiplica por uma janela para suavizar o corte. Quanto maior `N`, mais
+//! estreita a transição; a janela troca largura da transição por atenuação (a
+//! [`Hamming`](Window::Hamming) atenua uns 50 dB, a [`Blackman`](Window::Blackman) uns 70 dB).
+
+use super::Filter;
+use crate::dsp::Window;
+use crate::numeric::Number;
+use core::f32::consts::PI;
+
+/// `sin(πx) / (πx)`
+fn sinc(x: f32) -> f32 {
+    if x == 0.0 {
+        1.0
+    } else {
+        libm::sinf(PI * x) / (PI * x)
+    }
+}
+
+/// Filtro FIR com `N` coeficientes, `y[n] = Σ h[k] x[n - k]`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fir<const N: usize, T = f32> {
+    coefficients: [T; N],
+    history: [T; N],
+    /// Posição da amostra mais recente em `history`.
+    head: usize,
+}
+
+impl<const N: usize, T: Number> Fir<N, T> {
+    pub fn new(coefficients: [T; N]) -> Self {
+        assert!(N > 0, "FIR filter without coefficients");
+        Self {
+            coefficients,
+            history: [T::ZERO; N],
+            head: 0,
+        }
+    }
+
+    pub fn coefficients(&self) -> &[T; N] {
+        &self.coefficients
+    }
+
+    /// Atraso, em amostras, de todas as frequências. Vale para os filtros projetados aqui,
+    /// que têm coeficientes simétricos.
+    pub fn delay(&self) -> f32 {
+        (N - 1) as f32 / 2.0
+    }
+
+    /// Os mesmos coeficientes em outro tipo, com a memória zerada.
+    pub fn cast<U: Number>(&self) -> Fir<N, U> {
+        Fir::new(self.coefficients.map(|c| U::from_f32(c.to_f32())))
+    }
+}
+
+impl<const N: usize> Fir<N> {
+    /// Passa-baixas com corte em `cutoff` Hz, amostrando a `rate` Hz. No corte o ganho é 1/2
+    /// (-6 dB); em DC é exatamente 1.
+    pub fn lowpass(cutoff: f32, rate: f32, window: Window) -> Self {
+        let fc = cutoff / rate;
+        let center = (N - 1) as f32 / 2.0;
+        let mut h: [f32; N] = core::array::from_fn(|k| {
+            2.0 * fc * sinc(2.0 * fc * (k as f32 - center)) * window.weight(k, N)
+        });
+
+        let sum: f32 = h.iter().sum();
+        h.iter_mut().for_each(|c| *c /= sum);
+        Self::new(h)
+    }
+
+    /// Passa-altas com corte em `cutoff` Hz: o impulso menos o passa-baixas.
+    ///
+    /// !!! `N` precisa ser ímpar, para o impulso cair no centro.
+    pub fn highpass(cutoff: f32, rate: f32, window: Window) -> Self {
+        Self::new(invert(Self::lowpass(cutoff, rate, window).coefficients))
+    }
+
+    /// Passa-faixa entre `low` e `high` Hz: a diferença de dois passa-baixas.
+    pub fn bandpass(low: f32, high: f32, rate: f32, window: Window) -> Self {
+        let high = Self::lowpass(high, rate, window).coefficients;
+        let low = Self::lowpass(low, rate, window).coefficients;
+        Self::new(core::array::from_fn(|k| high[k] - low[k]))
+    }
+
+    /// Rejeita-faixa entre `low` e `high` Hz: o impulso menos o passa-faixa.
+    ///
+    /// !!! `N` precisa ser ímpar, para o impulso cair no centro.
+    pub fn bandstop(low: f32, high: f32, rate: f32, window: Window) -> Self {
+        Self::new(invert(Self::bandpass(low, high, rate, window).coefficients))
+    }
+}
+
+/// `δ[n - centro] - h[n]`: troca o que passa pelo que é cortado.
+fn invert<const N: usize>(mut h: [f32; N]) -> [f32; N] {
+    assert!(
+        N % 2 == 1,
+        "spectral inversion needs an odd number of coefficients"
+    );
+    h.iter_mut().for_each(|c| *c = -*c);
+    h[N / 2] += 1.0;
+    h
+}
+
+impl<const N: usize, T: Number> Filter<T> for Fir<N, T> {
+    fn update(&mut self, input: T) -> T {
+        self.head = (self.head + 1) % N;
+        self.history[self.head] = input;
+
+        self.coefficients
+            .iter()
+            .enumerate()
+            .fold(T::ZERO, |sum, (k, &c)| {
+                sum + c * self.history[(self.head + N - k) % N]
+            })
+    }
+
+    fn reset(&mut self) {
+        self.history = [T::ZERO; N];
+        self.head = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsp::response::FrequencyResponse;
+
+    const RATE: f32 = 1024.0;
+
+    fn response<const N: usize>(filter: &mut Fir<N>) -> FrequencyResponse {
+        FrequencyResponse::measure(filter, RATE, 1024)
+    }
+
+    #[test]
+    fn test_impulse_response() {
+        let mut filter = Fir::new([1.0, 2.0, 3.0]);
+        let output: Vec<f32> = filter.process([1.0, 0.0, 0.0, 0.0, 1.0, 1.0]).collect();
+        assert_eq!(output, [1.0, 2.0, 3.0, 0.0, 1.0, 3.0]);
+    }
+
+    #[test]
+    fn test_lowpass() {
+        let mut filter = Fir::<101>::lowpass(100.0, RATE, Window::Hamming);
+        assert!(filter
+            .coefficients()
+            .iter()
+            .zip(filter.coefficients().iter().rev())
+            .all(|(a, b)| (a - b).abs() < 1e-7));
+
+        let response = response(&mut filter);
+        assert!((response.gain(0.0) - 1.0).abs() < 1e-5);
+        assert!((response.gain(50.0) - 1.0).abs() < 0.01);
+        assert!((response.gain(100.0) - 0.5).abs() < 0.02);
+        assert!(response.gain_db(150.0) < -50.0);
+        assert!(response.gain_db(300.0) < -50.0);
+
+        // Fase linear: atraso de (N - 1) / 2 amostras
+        let expected = -std::f32::consts::TAU * 10.0 / RATE * filter.delay();
+        let phase = response.at(10.0).arg();
+        assert!((phase - expected).abs() < 1e-3, "{phase} != {expected}");
+    }
+
+    #[test]
+    fn test_highpass_bandpass_and_bandstop() {
+        let highpass = response(&mut Fir::<101>::highpass(100.0, RATE, Window::Blackman));
+        assert!(highpass.gain_db(0.0) < -60.0);
+        assert!((highpass.gain(100.0) - 0.5).abs() < 0.02);
+        assert!((highpass.gain(300.0) - 1.0).abs() < 0.01);
+
+        let bandpass = response(&mut Fir::<201>::bandpass(
+            100.0,
+            200.0,
+            RATE,
+            Window::Blackman,
+        ));
+        assert!(bandpass.gain_db(0.0) < -60.0);
+        assert!((bandpass.gain(150.0) - 1.0).abs() < 0.01);
+        assert!(bandpass.gain_db(400.0) < -60.0);
+
+        let bandstop = response(&mut Fir::<201>::bandstop(
+            100.0,
+            200.0,
+            RATE,
+            Window::Blackman,
+        ));
+        assert!((bandstop.gain(0.0) - 1.0).abs() < 0.01);
+        assert!(bandstop.gain_db(150.0) < -60.0);
+        assert!((bandstop.gain(400.0) - 1.0).abs() < 0.01);
+    }
+}
@@ -0,0 +1,83 @@
+//! Filtro de mediana, que tira picos isolados (leituras erradas do sensor) sem arredondar os
+//! degraus como uma média faria.
+
+use super::Filter;
+use crate::numeric::Number;
+use core::cmp::Ordering;
+
+/// Mediana das últimas `N` amostras. Antes de chegarem `N` amostras, usa as que já vieram.
+///
+/// !!! Não é linear, então não tem resposta em frequência. Um pico some se durar menos de
+/// metade da janela.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Median<const N: usize, T = f32> {
+    history: [T; N],
+    head: usize,
+    len: usize,
+}
+
+impl<const N: usize, T: Number> Median<N, T> {
+    pub fn new() -> Self {
+        assert!(N > 0, "median over zero samples");
+        Self {
+            history: [T::ZERO; N],
+            head: 0,
+            len: 0,
+        }
+    }
+}
+
+impl<const N: usize, T: Number> Default for Median<N, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize, T: Number> Filter<T> for Median<N, T> {
+    fn update(&mut self, input: T) -> T {
+        self.history[self.head] = input;
+        self.head = (self.head + 1) % N;
+        self.len = (self.len + 1).min(N);
+
+        // Enquanto não encheu, as amostras estão no começo do array
+        let mut sorted = self.history;
+        let window = &mut sorted[..self.len];
+        window.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+
+        let middle = self.len / 2;
+        if self.len % 2 == 1 {
+            window[middle]
+        } else {
+            (window[middle - 1] + window[middle]) / T::from_int(2)
+        }
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_median() {
+        let mut filter = Median::<5>::new();
+        let spikes = [1.0, 1.0, 9.0, 1.0, 1.0, -7.0, -7.0, 1.0, 1.0, 1.0];
+        let output: Vec<f32> = filter.process(spikes).collect();
+        assert!(output.iter().all(|&y| y == 1.0), "{output:?}");
+
+        // O degrau passa inteiro, só atrasado de meia janela
+        filter.reset();
+        let output: Vec<f32> = filter
+            .process([0.0, 0.0, 0.0, 0.0, 0.0, 3.0, 3.0, 3.0, 3.0])
+            .collect();
+        assert_eq!(output, [0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 3.0, 3.0]);
+
+        // Janela ainda incompleta
+        let mut filter = Median::<3>::new();
+        assert_eq!(filter.update(2.0), 2.0);
+        assert_eq!(filter.update(4.0), 3.0);
+    }
+}
@@ -0,0 +1,204 @@
+//! Filtros digitais para sequências de amostras, em `f32` ou em ponto fixo.
+//!
+//! ```ignore
+//! let mut filter = Median::<5>::new().then(Cascade::<2>::lowpass(20.0, 1000.0));
+//! let clean: Vec<f32> = filter.process(readings).collect();
+//! ```
+//!
+//! !!! Os filtros usam só `core` e o `libm` e guardam a memória em arrays de tamanho fixo (o
+//!     tamanho é parâmetro genérico), para o mesmo código rodar no microcontrolador. Filtrar
+//!     `Samples` e medir a resposta em frequência (`dsp::response`) fica do lado do `std`.
+//!
+//! Os coeficientes são projetados em `f32` e depois podem ser convertidos com `cast` para um
+//! tipo de ponto fixo, que só faz contas com inteiros:
+//!
+//! ```ignore
+//! let mut filter: Cascade<2, Q16> = Cascade::<2>::lowpass(20.0, 1000.0).cast();
+//! ```
+
+pub mod average;
+pub mod biquad;
+pub mod fir;
+pub mod median;
+
+pub use average::{Exponential, MovingAverage};
+pub use biquad::{Biquad, Cascade};
+pub use fir::Fir;
+pub use median::Median;
+
+use crate::numeric::Number;
+
+/// Filtro que recebe uma amostra por vez, com taxa de amostragem fixa. `T` é o tipo das
+/// amostras, `f32` por padrão.
+pub trait Filter<T: Number = f32> {
+    /// Recebe a próxima amostra e devolve a saída correspondente.
+    fn update(&mut self, input: T) -> T;
+
+    /// Esquece as amostras anteriores, voltando ao estado de quando foi criado.
+    fn reset(&mut self);
+
+    /// Filtra as amostras no próprio slice.
+    fn apply(&mut self, data: &mut [T]) {
+        data.iter_mut().for_each(|x| *x = self.update(*x));
+    }
+
+    /// A saída para cada amostra de `input`, conforme o iterador avança.
+    fn process<I: IntoIterator<Item = T>>(&mut self, input: I) -> Process<'_, Self, I::IntoIter>
+    where
+        Self: Sized,
+    {
+        Process {
+            filter: self,
+            input: input.into_iter(),
+        }
+    }
+
+    /// Passa a saída deste filtro por `next`.
+    fn then<F: Filter<T>>(self, next: F) -> Chain<Self, F>
+    where
+        Self: Sized,
+    {
+        Chain {
+            first: self,
+            second: next,
+        }
+    }
+}
+
+impl<T: Number, F: Filter<T> + ?Sized> Filter<T> for &mut F {
+    fn update(&mut self, input: T) -> T {
+        (**self).update(input)
+    }
+
+    fn reset(&mut self) {
+        (**self).reset()
+    }
+}
+
+/// Iterador de [`Filter::process`].
+pub struct Process<'a, F, I> {
+    filter: &'a mut F,
+    input: I,
+}
+
+impl<T: Number, F: Filter<T>, I: Iterator<Item = T>> Iterator for Process<'_, F, I> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.input.next().map(|x| self.filter.update(x))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.input.size_hint()
+    }
+}
+
+/// Dois filtros em série, de [`Filter::then`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Chain<A, B> {
+    first: A,
+    second: B,
+}
+
+impl<T: Number, A: Filter<T>, B: Filter<T>> Filter<T> for Chain<A, B> {
+    fn update(&mut self, input: T) -> T {
+        self.second.update(self.first.update(input))
+    }
+
+    fn reset(&mut self) {
+        self.first.reset();
+        self.second.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process_apply_and_chain() {
+        let input = [0.0, 4.0, 4.0, 4.0, 100.0, 4.0, 4.0];
+
+        let mut average = MovingAverage::<2>::new();
+        let processed: Vec<f32> = average.process(input).collect();
+        average.reset();
+        let mut applied = input;
+        average.apply(&mut applied);
+        assert_eq!(processed, applied);
+        assert_eq!(processed[..3], [0.0, 2.0, 4.0]);
+
+        // A mediana tira o pico antes da média
+        let mut chain = Median::<3>::new().then(MovingAverage::<2>::new());
+        let output: Vec<f32> = chain.process(input).collect();
+        assert!(output[3..].iter().all(|&y| y == 4.0), "{output:?}");
+
+        chain.reset();
+        assert_eq!(chain.update(8.0), 4.0);
+    }
+
+    /// Os filtros em Q16, com os coeficientes convertidos, contra os mesmos em `f32`.
+    #[test]
+    fn test_fixed_point() {
+        use crate::numeric::Q16;
+        use crate::signals::noise::Rng;
+        use crate::signals::Window;
+
+        fn max_error(float: &mut dyn Filter, fixed: &mut dyn Filter<Q16>, input: &[f32]) -> f32 {
+            input
+                .iter()
+                .map(|&x| {
+                    let x = Q16::from_f32(x);
+                    (float.update(x.to_f32()) - fixed.update(x).to_f32()).abs()
+                })
+                .fold(0.0, f32::max)
+        }
+
+        // Seno de 5 Hz com ruído, amostrado a 1 kHz
+        let mut rng = Rng::new(5);
+        let input: Vec<f32> = (0..2000)
+            .map(|k| (std::f32::consts::TAU * 5.0 * k as f32 / 1000.0).sin() + 0.2 * rng.gaussian())
+            .collect();
+
+        let fir = Fir::<31>::lowpass(40.0, 1000.0, Window::Hamming);
+        let butterworth = Cascade::<2>::lowpass(40.0, 1000.0);
+        let notch = Biquad::notch(50.0, 5.0, 1000.0);
+        let exponential = Exponential::from_cutoff(10.0, 1000.0);
+
+        // Os IIR acumulam o arredondamento pela realimentação, então erram mais que os FIR
+        let errors = [
+            (max_error(&mut fir.clone(), &mut fir.cast(), &input), 2e-4),
+            (
+                max_error(&mut butterworth.clone(), &mut butterworth.cast(), &input),
+                1e-3,
+            ),
+            (
+                max_error(&mut notch.clone(), &mut notch.cast(), &input),
+                1e-3,
+            ),
+            (
+                max_error(&mut exponential.clone(), &mut exponential.cast(), &input),
+                1e-4,
+            ),
+            (
+                max_error(
+                    &mut MovingAverage::<16>::new(),
+                    &mut MovingAverage::<16, Q16>::new(),
+                    &input,
+                ),
+                2e-5,
+            ),
+            // A mediana escolhe uma das entradas, que já estão em Q16, ou a média de duas
+            (
+                max_error(
+                    &mut Median::<5>::new(),
+                    &mut Median::<5, Q16>::new(),
+                    &input,
+                ),
+                1e-5,
+            ),
+        ];
+        for (k, (error, bound)) in errors.iter().enumerate() {
+            assert!(error < bound, "filter {k}: {error}");
+        }
+    }
+}
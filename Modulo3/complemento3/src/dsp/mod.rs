@@ -0,0 +1,15 @@
+//! Processamento digital de sinais amostrados.
+//!
+//! !!! Sem a feature `std` ficam só os filtros e as janelas, que não alocam e podem ir para o
+//! firmware.
+
+#[cfg(feature = "std")]
+pub mod fft;
+pub mod filter;
+#[cfg(feature = "std")]
+pub mod response;
+#[cfg(feature = "std")]
+pub mod spectrum;
+pub mod window;
+
+pub use window::Window;
@@ -0,0 +1,143 @@
+//! Resposta em frequência de um filtro linear, medida pela resposta ao impulso.
+
+use super::fft::{rfft, Complex};
+use super::filter::Filter;
+use crate::plot::{Figure, Panel, Series};
+use std::error::Error;
+use std::path::Path;
+
+/// Ganho complexo do filtro nas frequências de 0 até a metade da taxa de amostragem.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrequencyResponse {
+    bins: Vec<Complex>,
+    sample_rate: f32,
+    /// Tamanho da resposta ao impulso (e da FFT).
+    size: usize,
+}
+
+impl FrequencyResponse {
+    /// Zera o filtro, passa um impulso unitário e tira a FFT das primeiras `len` saídas
+    /// (arredondado para cima para potência de 2). O filtro é zerado de novo no fim.
+    ///
+    /// !!! Só faz sentido para filtros lineares, e nos IIR `len` precisa ser longo o bastante
+    /// para a resposta ao impulso já ter morrido. Quanto maior, mais fina a resolução.
+    pub fn measure<F: Filter + ?Sized>(filter: &mut F, sample_rate: f32, len: usize) -> Self {
+        let size = len.max(2).next_power_of_two();
+        filter.reset();
+        let impulse: Vec<f32> = (0..size)
+            .map(|k| filter.update(if k == 0 { 1.0 } else { 0.0 }))
+            .collect();
+        filter.reset();
+
+        Self {
+            bins: rfft(&impulse),
+            sample_rate,
+            size,
+        }
+    }
+
+    /// Distância entre duas frequências da FFT, em Hz.
+    pub fn resolution(&self) -> f32 {
+        self.sample_rate / self.size as f32
+    }
+
+    pub fn frequencies(&self) -> Vec<f32> {
+        (0..self.bins.len())
+            .map(|k| k as f32 * self.resolution())
+            .collect()
+    }
+
+    /// Ganho complexo na frequência da FFT mais próxima de `frequency` Hz.
+    pub fn at(&self, frequency: f32) -> Complex {
+        let k = (frequency / self.resolution()).round().max(0.0) as usize;
+        self.bins[k.min(self.bins.len() - 1)]
+    }
+
+    /// Ganho em amplitude em `frequency` Hz.
+    pub fn gain(&self, frequency: f32) -> f32 {
+        self.at(frequency).norm()
+    }
+
+    pub fn gain_db(&self, frequency: f32) -> f32 {
+        20.0 * self.gain(frequency).log10()
+    }
+
+    pub fn magnitude(&self) -> Vec<f32> {
+        self.bins.iter().map(Complex::norm).collect()
+    }
+
+    pub fn magnitude_db(&self) -> Vec<f32> {
+        self.bins
+            .iter()
+            .map(|bin| 20.0 * bin.norm().log10())
+            .collect()
+    }
+
+    /// Fase em radianos, em `(-π, π]`.
+    pub fn phase(&self) -> Vec<f32> {
+        self.bins.iter().map(Complex::arg).collect()
+    }
+
+    /// Ganho (dB) em função da frequência, para comparar filtros no mesmo gráfico.
+    pub fn series(&self, name: &str) -> Series {
+        Series::from_xy(name, &self.frequencies(), &self.magnitude_db())
+    }
+
+    /// Gráfico do ganho (dB) e da fase em função da frequência.
+    pub fn plot<P: AsRef<Path>>(&self, caption: &str, path: P) -> Result<(), Box<dyn Error>> {
+        let frequencies = self.frequencies();
+        // Os zeros de transmissão iriam a -∞
+        let db: Vec<f32> = self.magnitude_db().iter().map(|d| d.max(-120.0)).collect();
+
+        Figure::new()
+            .size(800, 600)
+            .caption(caption)
+            .panel(Panel::new().y_label("ganho (dB)").series(Series::from_xy(
+                "",
+                &frequencies,
+                &db,
+            )))
+            .panel(
+                Panel::new()
+                    .x_label("f (Hz)")
+                    .y_label("fase (rad)")
+                    .y_range(-3.2, 3.2)
+                    .series(Series::from_xy("", &frequencies, &self.phase())),
+            )
+            .save(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsp::filter::{Cascade, Fir};
+
+    #[test]
+    fn test_measure() {
+        // Atraso de uma amostra: ganho 1 e fase -ω
+        let mut delay = Fir::new([0.0, 1.0]);
+        let response = FrequencyResponse::measure(&mut delay, 1000.0, 1000);
+
+        assert_eq!(response.resolution(), 1000.0 / 1024.0);
+        assert_eq!(response.frequencies().len(), 513);
+        assert!(response.magnitude().iter().all(|g| (g - 1.0).abs() < 1e-5));
+        let f = 10.0 * response.resolution();
+        assert!((response.at(f).arg() + std::f32::consts::TAU * f / 1000.0).abs() < 1e-4);
+
+        // O filtro volta zerado
+        assert_eq!(delay.update(5.0), 0.0);
+    }
+
+    #[test]
+    fn test_plot() {
+        let response =
+            FrequencyResponse::measure(&mut Cascade::<2>::lowpass(50.0, 1000.0), 1000.0, 2048);
+        let path = std::env::temp_dir().join("complemento3_response.svg");
+
+        assert!(response.plot("butterworth", &path).is_ok());
+        assert!(std::fs::read_to_string(&path)
+            .unwrap()
+            .contains("ganho (dB)"));
+    }
+}
@@ -0,0 +1,389 @@
+//! Espectro de um sinal amostrado: amplitude e fase por frequência, distorção harmônica e
+//! relação sinal-ruído.
+//!
+//! As amplitudes são corrigidas pelo ganho da janela: uma senoide de amplitude `A` que cai em
+//! cima de uma frequência da FFT aparece com `A`. Quando ela cai entre duas, a energia se
+//! espalha pelo lóbulo principal da janela, então [`Spectrum::amplitude`] soma a potência do
+//! lóbulo em vez de olhar só o pico.
+
+use super::fft::{rfft, Complex};
+use crate::plot::{Figure, Panel, Series};
+use crate::signals::{Samples, Window};
+use std::error::Error;
+use std::path::Path;
+
+/// Meia largura do lóbulo principal, em frequências da FFT (sem zeros de preenchimento).
+fn main_lobe(window: Window) -> usize {
+    match window {
+        Window::Rectangular => 1,
+        Window::Hann | Window::Hamming => 2,
+        Window::Blackman => 3,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spectrum {
+    bins: Vec<Complex>,
+    sample_rate: f32,
+    window: Window,
+    /// Tamanho da FFT, com os zeros.
+    size: usize,
+    /// Soma dos pesos da janela, que é o ganho para uma senoide.
+    coherent_gain: f32,
+    /// Soma dos quadrados dos pesos, que é o ganho para potência (ruído).
+    power_gain: f32,
+    lobe: usize,
+}
+
+impl Spectrum {
+    /// Aplica a janela e calcula a FFT. Se o tamanho não for potência de 2, completa com
+    /// zeros, o que só deixa o espectro mais "liso" (não aumenta a resolução real).
+    pub fn new(samples: &[f32], sample_rate: f32, window: Window) -> Self {
+        assert!(samples.len() >= 2, "spectrum needs at least two samples");
+        let weights = window.weights(samples.len());
+        let size = samples.len().next_power_of_two();
+
+        let mut input: Vec<f32> = samples.iter().zip(&weights).map(|(x, w)| x * w).collect();
+        input.resize(size, 0.0);
+
+        Self {
+            bins: rfft(&input),
+            sample_rate,
+            window,
+            size,
+            coherent_gain: weights.iter().sum(),
+            power_gain: weights.iter().map(|w| w * w).sum(),
+            lobe: main_lobe(window) * size / samples.len() + usize::from(size != samples.len()),
+        }
+    }
+
+    /// Com a taxa de amostragem tirada dos instantes das amostras.
+    pub fn from_samples(samples: &Samples, window: Window) -> Self {
+        let span = samples.time[samples.len() - 1] - samples.time[0];
+        let sample_rate = (samples.len() - 1) as f32 / span;
+
+        Self::new(&samples.values, sample_rate, window)
+    }
+
+    pub fn window(&self) -> Window {
+        self.window
+    }
+
+    /// Distância entre duas frequências da FFT, em Hz.
+    pub fn resolution(&self) -> f32 {
+        self.sample_rate / self.size as f32
+    }
+
+    pub fn len(&self) -> usize {
+        self.bins.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bins.is_empty()
+    }
+
+    pub fn bins(&self) -> &[Complex] {
+        &self.bins
+    }
+
+    /// Frequência de cada posição, de 0 até a metade da taxa de amostragem.
+    pub fn frequencies(&self) -> Vec<f32> {
+        (0..self.len())
+            .map(|k| k as f32 * self.resolution())
+            .collect()
+    }
+
+    /// Posição mais perto de `frequency`.
+    pub fn bin(&self, frequency: f32) -> usize {
+        ((frequency / self.resolution()).round() as usize).min(self.len() - 1)
+    }
+
+    // A componente contínua e a de Nyquist não têm par com frequência negativa
+    fn one_sided(&self, k: usize) -> f32 {
+        if k == 0 || k == self.len() - 1 {
+            1.0
+        } else {
+            2.0
+        }
+    }
+
+    /// Amplitude de cada frequência.
+    pub fn magnitude(&self) -> Vec<f32> {
+        self.bins
+            .iter()
+            .enumerate()
+            .map(|(k, bin)| bin.norm() * self.one_sided(k) / self.coherent_gain)
+            .collect()
+    }
+
+    /// Amplitude em dB (`20 log10`), com piso em -200 dB para não dar `-inf`.
+    pub fn magnitude_db(&self) -> Vec<f32> {
+        self.magnitude()
+            .iter()
+            .map(|m| 20.0 * m.max(1e-10).log10())
+            .collect()
+    }
+
+    /// Fase de cada frequência, em radianos, em relação a um cosseno.
+    pub fn phase(&self) -> Vec<f32> {
+        self.bins.iter().map(Complex::arg).collect()
+    }
+
+    /// Potência média de cada frequência. A soma de todas é a potência média do sinal
+    /// (teorema de Parseval), independente da janela.
+    pub fn power(&self) -> Vec<f32> {
+        let scale = self.size as f32 * self.power_gain;
+        self.bins
+            .iter()
+            .enumerate()
+            .map(|(k, bin)| bin.norm_sqr() * self.one_sided(k) / scale)
+            .collect()
+    }
+
+    /// Frequência e amplitude do maior pico, ignorando a componente contínua.
+    pub fn peak(&self) -> (f32, f32) {
+        let magnitude = self.magnitude();
+        let (k, amplitude) = magnitude
+            .iter()
+            .copied()
+            .enumerate()
+            .skip(self.lobe + 1)
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .unwrap_or((0, 0.0));
+
+        (k as f32 * self.resolution(), amplitude)
+    }
+
+    /// Posições do lóbulo principal em volta de `frequency`.
+    fn lobe_bins(&self, frequency: f32) -> std::ops::RangeInclusive<usize> {
+        let center = self.bin(frequency);
+        center.saturating_sub(self.lobe)..=(center + self.lobe).min(self.len() - 1)
+    }
+
+    /// Amplitude da senoide em `frequency`, pela potência do lóbulo principal.
+    pub fn amplitude(&self, frequency: f32) -> f32 {
+        let power: f32 = self.power()[self.lobe_bins(frequency)].iter().sum();
+        // Potência de uma senoide: A²/2
+        (2.0 * power).sqrt()
+    }
+
+    /// Amplitudes da fundamental e das harmônicas até a de ordem `count` (índice 0 é a
+    /// fundamental). Harmônicas acima de Nyquist ficam de fora.
+    pub fn harmonics(&self, fundamental: f32, count: usize) -> Vec<f32> {
+        let nyquist = self.sample_rate / 2.0;
+        (1..=count)
+            .map(|n| n as f32 * fundamental)
+            .take_while(|&f| f < nyquist)
+            .map(|f| self.amplitude(f))
+            .collect()
+    }
+
+    /// Distorção harmônica total: `√(A2² + A3² + ... + An²) / A1`, com `n = count`.
+    ///
+    /// `None` se não há fundamental para comparar (`count == 0` ou `fundamental` acima de
+    /// Nyquist).
+    pub fn thd(&self, fundamental: f32, count: usize) -> Option<f32> {
+        let harmonics = self.harmonics(fundamental, count);
+        let (first, rest) = harmonics.split_first()?;
+        let distortion: f32 = rest.iter().map(|a| a * a).sum();
+
+        Some(distortion.sqrt() / first)
+    }
+
+    /// Relação sinal-ruído em dB: potência da fundamental sobre a de todo o resto, tirando a
+    /// componente contínua e as harmônicas até a ordem `harmonics` (que são distorção, não
+    /// ruído).
+    pub fn snr(&self, fundamental: f32, harmonics: usize) -> f32 {
+        let power = self.power();
+        let mut excluded = vec![false; power.len()];
+        excluded[self.lobe_bins(0.0)].fill(true);
+
+        let nyquist = self.sample_rate / 2.0;
+        for n in 1..=harmonics.max(1) {
+            let frequency = n as f32 * fundamental;
+            if frequency < nyquist {
+                excluded[self.lobe_bins(frequency)].fill(true);
+            }
+        }
+
+        let signal: f32 = power[self.lobe_bins(fundamental)].iter().sum();
+        let noise: f32 = power
+            .iter()
+            .zip(&excluded)
+            .filter(|(_, &excluded)| !excluded)
+            .map(|(p, _)| p)
+            .sum();
+
+        10.0 * (signal / noise).log10()
+    }
+
+    /// Amplitude em dB por frequência, para um `Panel`.
+    pub fn series(&self, name: &str) -> Series {
+        Series::from_xy(name, &self.frequencies(), &self.magnitude_db())
+    }
+
+    /// Gráfico da amplitude (dB) e da fase em função da frequência.
+    pub fn plot<P: AsRef<Path>>(&self, caption: &str, path: P) -> Result<(), Box<dyn Error>> {
+        let frequencies = self.frequencies();
+        let db = self.magnitude_db();
+        // Abaixo de -120 dB é ruído numérico
+        let floor = db.iter().copied().fold(f32::NEG_INFINITY, f32::max) - 120.0;
+
+        Figure::new()
+            .size(800, 600)
+            .caption(caption)
+            .panel(
+                Panel::new()
+                    .y_label("amplitude (dB)")
+                    .series(Series::from_xy(
+                        "",
+                        &frequencies,
+                        &db.iter().map(|d| d.max(floor)).collect::<Vec<_>>(),
+                    )),
+            )
+            .panel(
+                Panel::new()
+                    .x_label("f (Hz)")
+                    .y_label("fase (rad)")
+                    .y_range(-3.2, 3.2)
+                    .series(Series::from_xy("", &frequencies, &self.phase())),
+            )
+            .save(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signals::noise::Noise;
+    use crate::signals::sine::Sine;
+    use crate::signals::square::SquareWave;
+    use crate::signals::{Sampler, Wave};
+    use std::f32::consts::PI;
+
+    const RATE: f32 = 4096.0;
+    const N: usize = 4096;
+
+    /// Meia amostra de atraso, para nenhuma amostra cair exatamente na borda da onda quadrada.
+    fn sample<W: Wave>(wave: &W) -> Vec<f32> {
+        Sampler::with_count(RATE, N)
+            .starting_at(0.5 / RATE)
+            .samples(wave)
+            .values
+    }
+
+    /// Onda quadrada de 0 a `A`: componente contínua `A/2` e só harmônicas ímpares, com
+    /// amplitude `2A / (nπ)`.
+    #[test]
+    fn test_square_wave_harmonics() {
+        let (frequency, amplitude) = (16.0, 1.0);
+        let square = SquareWave::new(1.0 / frequency, amplitude, 0.0);
+        let spectrum = Spectrum::new(&sample(&square), RATE, Window::Rectangular);
+        let magnitude = spectrum.magnitude();
+
+        assert!((magnitude[0] - amplitude / 2.0).abs() < 1e-3);
+
+        let harmonics = spectrum.harmonics(frequency, 9);
+        for (i, measured) in harmonics.iter().enumerate() {
+            let n = i + 1;
+            if n % 2 == 0 {
+                assert!(*measured < 1e-3, "harmonic {n}: {measured}");
+            } else {
+                // A amostragem dobra as harmônicas acima de Nyquist por cima destas, o que
+                // aumenta um pouco as de ordem alta (0,2% na 9ª com 256 amostras por período)
+                let expected = 2.0 * amplitude / (n as f32 * PI);
+                assert!(
+                    (measured - expected).abs() < 0.02 * expected,
+                    "harmonic {n}: {measured} != {expected}"
+                );
+            }
+        }
+
+        // THD até a 7ª: √(1/9 + 1/25 + 1/49)
+        let expected = (1.0f32 / 9.0 + 1.0 / 25.0 + 1.0 / 49.0).sqrt();
+        let thd = spectrum.thd(frequency, 7).unwrap();
+        assert!(
+            (thd - expected).abs() < 0.01 * expected,
+            "{thd} != {expected}"
+        );
+    }
+
+    #[test]
+    fn test_thd_without_fundamental() {
+        let spectrum = Spectrum::new(
+            &sample(&Sine::new(1.0 / 16.0, 1.0, 0.0)),
+            RATE,
+            Window::Hann,
+        );
+
+        assert_eq!(spectrum.thd(16.0, 0), None);
+        assert_eq!(spectrum.thd(RATE / 2.0, 3), None);
+        assert_eq!(spectrum.thd(RATE, 3), None);
+        // Só a fundamental abaixo de Nyquist: sem distorção
+        assert_eq!(spectrum.thd(RATE / 3.0, 3), Some(0.0));
+    }
+
+    /// Fora de uma frequência da FFT a janela espalha a energia, mas a soma do lóbulo recupera
+    /// a amplitude.
+    #[test]
+    fn test_windows_recover_amplitude() {
+        let sine = Sine::new(1.0 / 100.3, 0.8, 0.0);
+        let samples = sample(&sine);
+
+        for window in [Window::Hann, Window::Hamming, Window::Blackman] {
+            let spectrum = Spectrum::new(&samples, RATE, window);
+            let amplitude = spectrum.amplitude(100.3);
+            assert!((amplitude - 0.8).abs() < 0.01, "{window:?}: {amplitude}");
+
+            let (frequency, _) = spectrum.peak();
+            assert!((frequency - 100.3).abs() <= spectrum.resolution());
+        }
+    }
+
+    #[test]
+    fn test_phase() {
+        // Seno é um cosseno atrasado de 90°
+        let sine = Sine::new(1.0 / 64.0, 1.0, 0.0);
+        let samples: Vec<f32> = Sampler::with_count(RATE, N).samples(&sine).values;
+        let spectrum = Spectrum::new(&samples, RATE, Window::Rectangular);
+
+        assert!((spectrum.phase()[spectrum.bin(64.0)] + PI / 2.0).abs() < 1e-3);
+    }
+
+    /// Senoide com ruído gaussiano: SNR = (A²/2) / σ².
+    #[test]
+    fn test_snr() {
+        let (amplitude, sigma) = (1.0, 0.05);
+        let signal = Sine::new(1.0 / 200.0, amplitude, 0.0).add(Noise::gaussian(sigma, RATE, 9));
+        let spectrum = Spectrum::new(&sample(&signal), RATE, Window::Hann);
+
+        let expected = 10.0 * ((amplitude * amplitude / 2.0) / (sigma * sigma)).log10();
+        let snr = spectrum.snr(200.0, 5);
+        assert!((snr - expected).abs() < 0.5, "{snr} != {expected}");
+
+        // Parseval: a soma das potências é a potência média do sinal janelado
+        let total: f32 = spectrum.power().iter().sum();
+        let expected = amplitude * amplitude / 2.0 + sigma * sigma;
+        assert!((total - expected).abs() < 0.02 * expected);
+    }
+
+    #[test]
+    fn test_zero_padding() {
+        let samples = Sampler::with_count(1000.0, 1000).samples(&Sine::new(0.02, 1.0, 0.0));
+        let spectrum = Spectrum::from_samples(&samples, Window::Hann);
+
+        assert_eq!(spectrum.len(), 513);
+        assert!((spectrum.amplitude(50.0) - 1.0).abs() < 0.02);
+    }
+
+    #[test]
+    fn test_plot() {
+        let square = SquareWave::new(1.0 / 64.0, 1.0, 0.0);
+        let spectrum = Spectrum::new(&sample(&square), RATE, Window::Hann);
+        let path = std::env::temp_dir().join("complemento3_spectrum.svg");
+
+        spectrum.plot("quadrada", &path).unwrap();
+        assert!(std::fs::read_to_string(&path).unwrap().contains("f (Hz)"));
+        assert_eq!(spectrum.series("dB").points().len(), spectrum.len());
+    }
+}
@@ -0,0 +1,52 @@
+//! Funções de janela, usadas para cortar trechos das amostras e no projeto dos filtros FIR.
+//!
+//! !!! Como os filtros, usa só `core` e o `libm`.
+
+use core::f32::consts::TAU;
+
+/// Janela para cortar um trecho do sinal sem criar bordas abruptas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Window {
+    Rectangular,
+    Hann,
+    Hamming,
+    Blackman,
+}
+
+impl Window {
+    /// Peso da amostra `k` de uma janela com `len` amostras.
+    pub fn weight(&self, k: usize, len: usize) -> f32 {
+        if len < 2 {
+            return 1.0;
+        }
+        let x = TAU * k as f32 / (len - 1) as f32;
+
+        match self {
+            Window::Rectangular => 1.0,
+            Window::Hann => 0.5 - 0.5 * libm::cosf(x),
+            Window::Hamming => 0.54 - 0.46 * libm::cosf(x),
+            Window::Blackman => 0.42 - 0.5 * libm::cosf(x) + 0.08 * libm::cosf(2.0 * x),
+        }
+    }
+
+    /// Todos os pesos de uma janela com `len` amostras.
+    #[cfg(feature = "std")]
+    pub fn weights(&self, len: usize) -> Vec<f32> {
+        (0..len).map(|k| self.weight(k, len)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_weights() {
+        let hann = Window::Hann.weights(5);
+        assert_eq!(hann[0], 0.0);
+        assert_eq!(hann[2], 1.0);
+        assert!((Window::Hamming.weight(0, 5) - 0.08).abs() < 1e-6);
+        assert!(Window::Blackman.weight(0, 5).abs() < 1e-6);
+        assert_eq!(Window::Blackman.weight(0, 1), 1.0);
+    }
+}
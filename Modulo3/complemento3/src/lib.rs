@@ -1,12 +1,42 @@
+//! Sinais, plantas e processamento de sinais simulados no host.
+//!
+//! !!! Sem a feature `std` (ligada por padrão) o crate é `no_std` e sem alocação, e exporta só o
+//! que serve no firmware: os filtros de [`dsp`], o ponto fixo de [`numeric`], as estatísticas
+//! de [`statistics`], o PID de [`physical::controller`] e os sinais de [`signals`] que não
+//! guardam amostras (periódicos, degrau, rampa e as combinações):
+//!
+//! ```toml
+//! complemento3 = { path = "../complemento3", default-features = false }
+//! ```
+//!
+//! Os testes precisam da feature `std`.
+
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
 #![allow(unused)]
 
-mod physical;
-mod signals;
-
+pub mod dsp;
+pub mod numeric;
+pub mod physical;
+#[cfg(feature = "std")]
+mod plot;
+#[cfg(feature = "std")]
+mod record;
+pub mod signals;
+pub mod statistics;
+
+#[cfg(test)]
+use crate::plot::{Figure, Panel, Series};
+#[cfg(test)]
 use crate::signals::sine::Sine;
+#[cfg(test)]
 use crate::signals::square::SquareWave;
+#[cfg(test)]
 use crate::signals::SawTooth;
+#[cfg(test)]
+use plotters::style::colors::{BLACK, BLUE, RED};
+#[cfg(test)]
 use signals::*;
+#[cfg(test)]
 use std::io::Error as Err;
 
 #[test]
@@ -18,51 +48,24 @@ fn test_graphs() {
     let saw_tooth = SawTooth::new(x_max, 1.0, 0.0);
     let square = SquareWave::new(x_max, 1.0, 0.0);
 
-    assert!(plot_serie(
-        (0..total)
-            .map(|t| {
-                let t = t as f32 * (x_max / (total as f32 - 1.0));
-                // 0.0
-                sin.compute(t)
-            })
-            .collect(),
-        "sin",
-        total as f32,
-        (-1.0, 1.0),
-        x_max,
-        "sin.png",
-    )
-    .is_ok());
-    assert!(plot_serie(
-        (0..total)
-            .map(|t| {
-                let t = t as f32 * (x_max / (total as f32 - 1.0));
-                // 0.0
-                saw_tooth.compute(t)
-            })
-            .collect(),
-        "sawtooth",
-        total as f32,
-        (0.0, 1.1),
-        x_max,
-        "sawtooth.png",
-    )
-    .is_ok());
-    assert!(plot_serie(
-        (0..total)
-            .map(|t| {
-                let t = t as f32 * (x_max / (total as f32 - 1.0));
-                // 0.0
-                square.compute(t)
-            })
-            .collect(),
-        "square",
-        total as f32,
-        (0.0, 1.1),
-        x_max,
-        "square.png",
-    )
-    .is_ok());
+    let waves: [(&str, &dyn Wave, (f32, f32)); 3] = [
+        ("sin", &sin, (-1.0, 1.0)),
+        ("sawtooth", &saw_tooth, (0.0, 1.1)),
+        ("square", &square, (0.0, 1.1)),
+    ];
+
+    for (name, wave, (min, max)) in waves {
+        let figure = Figure::new().caption(name).panel(
+            Panel::new().y_range(min, max).series(Series::from_wave(
+                name,
+                wave,
+                (0.0, x_max),
+                total,
+            )),
+        );
+
+        assert!(figure.save(format!("{name}.png")).is_ok());
+    }
 }
 
 #[test]
@@ -70,64 +73,153 @@ fn test_system() {
     let total = 1_000;
     let x_max = 1.5;
 
-    let mut output = vec![];
-    let mut system = physical::System::default();
+    let system = physical::System::default();
+    let output = Sampler::over(0.0, x_max, total)
+        .times()
+        .map(|t| (t, system.sensor(t)))
+        .collect();
+
+    let figure = Figure::new().caption("system").panel(
+        Panel::new()
+            .y_range(0.0, 1.5)
+            .series(Series::new("system", output)),
+    );
 
-    for t in 0..total {
-        let t = t as f32 * (x_max / (total as f32 - 1.0));
-        output.push(system.sensor(t));
+    assert!(figure.save("system.png").is_ok());
+}
+
+#[test]
+fn test_closed_loop() {
+    use physical::controller::{Pid, P};
+    use physical::metrics::Report;
+    use physical::simulation::ClosedLoop;
+    use signals::step::Step;
+
+    let simulation = ClosedLoop::new(0.001, 1.5);
+    let mut report = Report::new();
+
+    for kp in [1.0, P, 20.0] {
+        let mut system = physical::System::default();
+        let run = simulation.run(&mut system, &mut Pid::p(kp), &Step::unit());
+        report.add(&format!("P = {kp}"), &run);
+
+        // Referência e saída em cima, esforço de controle embaixo
+        let figure = Figure::new()
+            .size(640, 640)
+            .caption(&format!("P = {kp}"))
+            .panel(
+                Panel::new()
+                    .y_label("saída")
+                    .y_range(0.0, 1.5)
+                    .series(
+                        Series::from_xy("referência", &run.time, &run.setpoint).with_color(BLACK),
+                    )
+                    .series(Series::from_xy("saída", &run.time, &run.output).with_color(RED)),
+            )
+            .panel(
+                Panel::new()
+                    .x_label("t (s)")
+                    .y_label("u")
+                    .series(Series::from_xy("u", &run.time, &run.control).with_color(BLUE)),
+            );
+
+        // Gráfico e números são regerados a cada execução, então vão para o diretório temporário
+        // e não para o repositório
+        let dir = std::env::temp_dir();
+        assert!(figure
+            .save(dir.join(format!("complemento3_closed_loop_p{kp}.png")))
+            .is_ok());
+
+        // Os números também, para comparar com outras ferramentas
+        let recording = record::Recording::from_run(&format!("P = {kp}"), &run)
+            .with_parameter("kp", f64::from(kp))
+            .with_parameter("tau", 0.1);
+        let path = dir.join(format!("complemento3_closed_loop_p{kp}.csv"));
+        assert!(recording.save(path).is_ok());
     }
 
-    assert!(plot_serie(
-        output,
-        "system",
-        total as f32,
-        (0.0, 1.5),
-        x_max,
-        "system.png",
-    )
-    .is_ok());
+    // Planta de primeira ordem com P: a malha fechada também é de primeira ordem, com
+    // constante de tempo `tau / (1 + kp)`, sem sobressinal e com erro `1 / (1 + kp)`
+    let metrics = report.get(&format!("P = {P}")).unwrap();
+    let tau = 0.1 / (1.0 + P);
+    assert!((metrics.rise_time.unwrap() - tau * 9f32.ln()).abs() < 2e-3);
+    assert!((metrics.settling_time_2.unwrap() - tau * 50f32.ln()).abs() < 2e-3);
+    assert!(metrics.overshoot.abs() < 0.1);
+    assert!((metrics.steady_state_error - 1.0 / (1.0 + P)).abs() < 1e-3);
+
+    // Mais ganho, resposta mais rápida
+    let rise = |kp: f32| report.get(&format!("P = {kp}")).unwrap().rise_time.unwrap();
+    assert!(rise(1.0) > rise(P) && rise(P) > rise(20.0));
 }
 
-fn plot_serie(
-    serie: Vec<f32>,
-    caption: &str,
-    total: f32,
-    y_lim: (f32, f32),
-    x_max: f32,
-    file_output: &str,
-) -> Result<(), Box<dyn std::error::Error>> {
-    use plotters::prelude::*;
-
-    let root = BitMapBackend::new(file_output, (640, 480)).into_drawing_area();
-    root.fill(&WHITE)?;
-    let mut chart = ChartBuilder::on(&root)
-        .caption(caption, ("sans-serif", 50).into_font())
-        .margin(5)
-        .x_label_area_size(30)
-        .y_label_area_size(30)
-        .build_cartesian_2d(0f32..x_max, y_lim.0..y_lim.1)?;
-
-    chart.configure_mesh().draw()?;
-
-    chart
-        .draw_series(LineSeries::new(
-            serie
-                .iter()
-                .enumerate()
-                .map(|(x, y)| (x as f32 * (x_max / (total - 1.0)), *y)),
-            &RED,
-        ))?
-        .label(caption)
-        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &RED));
-
-    chart
-        .configure_series_labels()
-        .background_style(&WHITE.mix(0.8))
-        .border_style(&BLACK)
-        .draw()?;
-
-    root.present()?;
-
-    Ok(())
+#[test]
+fn test_spectrum() {
+    use dsp::spectrum::Spectrum;
+
+    // 64 Hz amostrada a 4096 Hz: harmônicas ímpares caindo em 1/n
+    let square = SquareWave::new(1.0 / 64.0, 1.0, 0.0);
+    let samples = Sampler::with_count(4096.0, 4096).samples(&square);
+    let spectrum = Spectrum::from_samples(&samples, Window::Hann);
+
+    let path = std::env::temp_dir().join("complemento3_square_spectrum.png");
+    assert!(spectrum.plot("square", path).is_ok());
+}
+
+#[test]
+fn test_filters() {
+    use dsp::filter::{Cascade, Filter, Fir, Median, MovingAverage};
+    use dsp::response::FrequencyResponse;
+    use plotters::style::colors::GREEN;
+    use signals::noise::Noise;
+
+    let dir = std::env::temp_dir();
+    let rate = 1000.0;
+    let mut fir = Fir::<63>::lowpass(20.0, rate, Window::Hamming);
+    let mut butterworth = Cascade::<2>::lowpass(20.0, rate);
+    let mut average = MovingAverage::<25>::new();
+
+    // Resposta em frequência dos três passa-baixas
+    let responses = [
+        ("FIR 63", &mut fir as &mut dyn Filter, BLUE),
+        ("Butterworth 4", &mut butterworth, RED),
+        ("média 25", &mut average, GREEN),
+    ]
+    .into_iter()
+    .fold(
+        Panel::new()
+            .x_label("f (Hz)")
+            .y_label("ganho (dB)")
+            .y_range(-80.0, 5.0),
+        |panel, (name, filter, color)| {
+            let response = FrequencyResponse::measure(filter, rate, 4096);
+            panel.series(response.series(name).with_color(color))
+        },
+    );
+    let figure = Figure::new()
+        .size(640, 480)
+        .x_range(0.0, 200.0)
+        .panel(responses);
+    assert!(figure
+        .save(dir.join("complemento3_filter_response.png"))
+        .is_ok());
+
+    // Seno de 2 Hz com ruído e picos de leitura errada
+    let wave = Sine::new(0.5, 1.0, 0.0)
+        .add(Noise::gaussian(0.1, rate, 1))
+        .add(signals::pwm::Pwm::new(0.13, 0.01, 3.0, 0.0));
+    let samples = Sampler::new(rate, 1.0).samples(&wave);
+    let clean = samples.filter(&mut Median::<9>::new().then(Cascade::<2>::lowpass(20.0, rate)));
+
+    let figure = Figure::new().size(640, 480).panel(
+        Panel::new()
+            .x_label("t (s)")
+            .series(Series::from_xy("medido", &samples.time, &samples.values).with_color(BLACK))
+            .series(
+                Series::from_xy("mediana + Butterworth", &clean.time, &clean.values)
+                    .with_color(RED),
+            ),
+    );
+    assert!(figure
+        .save(dir.join("complemento3_filtered_signal.png"))
+        .is_ok());
 }
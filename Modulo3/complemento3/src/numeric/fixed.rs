@@ -0,0 +1,267 @@
+//! Ponto fixo no formato Q: um `i32` em que os `FRAC` bits de baixo são a parte fracionária.
+//!
+//! `Fixed<16>` ([`Q16`]) vai de -32768 a 32768 com passo de `2⁻¹⁶ ≈ 1,5e-5`, e serve para
+//! tempo, sinais e ganhos. `Fixed<31>` ([`Q31`]) só representa `[-1, 1)`, com passo de
+//! `2⁻³¹`, e serve para amostras normalizadas (como as de um ADC).
+//!
+//! Todas as operações saturam: o que passaria do maior valor fica no maior valor, em vez de
+//! dar a volta para o negativo como o `i32` faria. A divisão por zero dá o extremo com o
+//! sinal do dividendo.
+
+use core::fmt::{Debug, Display, Formatter};
+use core::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+use super::Number;
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Hash)]
+pub struct Fixed<const FRAC: u32> {
+    bits: i32,
+}
+
+/// 15 bits inteiros e 16 fracionários.
+pub type Q16 = Fixed<16>;
+
+/// Só a parte fracionária: `[-1, 1)`.
+pub type Q31 = Fixed<31>;
+
+impl<const FRAC: u32> Fixed<FRAC> {
+    pub const ZERO: Self = Self::from_bits(0);
+    /// `1`, que no [`Q31`] não cabe e fica no maior valor.
+    pub const ONE: Self = Self::saturate(1 << FRAC);
+    pub const MIN: Self = Self::from_bits(i32::MIN);
+    pub const MAX: Self = Self::from_bits(i32::MAX);
+    /// O menor passo, `2^-FRAC`.
+    pub const EPSILON: Self = Self::from_bits(1);
+
+    const SCALE: f32 = (1u64 << FRAC) as f32;
+
+    pub const fn from_bits(bits: i32) -> Self {
+        assert!(
+            FRAC > 0 && FRAC < 32,
+            "fixed point needs 1 to 31 fractional bits"
+        );
+        Self { bits }
+    }
+
+    pub const fn to_bits(self) -> i32 {
+        self.bits
+    }
+
+    /// Limita à faixa do `i32`.
+    pub(crate) const fn saturate(bits: i64) -> Self {
+        if bits > i32::MAX as i64 {
+            Self::MAX
+        } else if bits < i32::MIN as i64 {
+            Self::MIN
+        } else {
+            Self::from_bits(bits as i32)
+        }
+    }
+
+    /// O valor representável mais próximo de `x`. Fora da faixa satura, e `NaN` vira zero.
+    pub fn from_f32(x: f32) -> Self {
+        // A conversão com `as` já satura
+        Self::from_bits(libm::roundf(x * Self::SCALE) as i32)
+    }
+
+    pub fn to_f32(self) -> f32 {
+        self.bits as f32 / Self::SCALE
+    }
+
+    pub const fn from_int(n: i32) -> Self {
+        Self::saturate((n as i64) << FRAC)
+    }
+}
+
+impl<const FRAC: u32> Add for Fixed<FRAC> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::from_bits(self.bits.saturating_add(rhs.bits))
+    }
+}
+
+impl<const FRAC: u32> Sub for Fixed<FRAC> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::from_bits(self.bits.saturating_sub(rhs.bits))
+    }
+}
+
+impl<const FRAC: u32> Mul for Fixed<FRAC> {
+    type Output = Self;
+
+    /// Arredonda para o mais próximo.
+    fn mul(self, rhs: Self) -> Self {
+        let product = self.bits as i64 * rhs.bits as i64;
+        Self::saturate((product + (1 << (FRAC - 1))) >> FRAC)
+    }
+}
+
+impl<const FRAC: u32> Div for Fixed<FRAC> {
+    type Output = Self;
+
+    /// Arredonda para o mais próximo.
+    fn div(self, rhs: Self) -> Self {
+        match (self.bits, rhs.bits) {
+            (0, _) => Self::ZERO,
+            (a, 0) if a > 0 => Self::MAX,
+            (_, 0) => Self::MIN,
+            (a, b) => {
+                let (n, d) = ((a as i64) << FRAC, b as i64);
+                let quotient = (n.abs() + d.abs() / 2) / d.abs();
+                Self::saturate(if (n < 0) == (d < 0) {
+                    quotient
+                } else {
+                    -quotient
+                })
+            }
+        }
+    }
+}
+
+impl<const FRAC: u32> Neg for Fixed<FRAC> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::from_bits(self.bits.saturating_neg())
+    }
+}
+
+impl<const FRAC: u32> AddAssign for Fixed<FRAC> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const FRAC: u32> SubAssign for Fixed<FRAC> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const FRAC: u32> Debug for Fixed<FRAC> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Q{}({})", FRAC, self.to_f32())
+    }
+}
+
+impl<const FRAC: u32> Display for Fixed<FRAC> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(&self.to_f32(), f)
+    }
+}
+
+impl<const FRAC: u32> Number for Fixed<FRAC> {
+    const ZERO: Self = Self::ZERO;
+    const ONE: Self = Self::ONE;
+    const MIN: Self = Self::MIN;
+    const MAX: Self = Self::MAX;
+    const TAU: Self = Self::saturate((core::f64::consts::TAU * (1u64 << FRAC) as f64) as i64);
+
+    fn from_f32(x: f32) -> Self {
+        Self::from_f32(x)
+    }
+
+    fn to_f32(self) -> f32 {
+        self.to_f32()
+    }
+
+    fn from_int(n: i32) -> Self {
+        Self::from_int(n)
+    }
+
+    /// Zero quando `rhs` é zero, em vez de entrar em pânico como a divisão de inteiros.
+    fn rem_euclid(self, rhs: Self) -> Self {
+        // `checked_` também cobre `MIN % -EPSILON`, que estoura no `i32` mas tem resto zero
+        Self::from_bits(self.bits.checked_rem_euclid(rhs.bits).unwrap_or(0))
+    }
+
+    fn sqrt(self) -> Self {
+        self.sqrt()
+    }
+
+    fn sin(self) -> Self {
+        self.sin()
+    }
+
+    fn cos(self) -> Self {
+        self.cos()
+    }
+
+    fn exp(self) -> Self {
+        self.exp()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn q16(x: f32) -> Q16 {
+        Q16::from_f32(x)
+    }
+
+    #[test]
+    fn test_conversion() {
+        assert_eq!(Q16::ONE.to_bits(), 1 << 16);
+        assert_eq!(q16(1.5).to_bits(), 0x18000);
+        assert_eq!(q16(-0.25).to_f32(), -0.25);
+        assert_eq!(Q16::from_int(-3).to_f32(), -3.0);
+        assert_eq!(q16(1e6), Q16::MAX);
+        assert_eq!(q16(f32::NAN), Q16::ZERO);
+        assert_eq!(Q16::from_int(100_000), Q16::MAX);
+
+        // Em Q31 o 1 não cabe
+        assert_eq!(Q31::ONE, Q31::MAX);
+        assert_eq!(Q31::from_f32(-1.0), Q31::MIN);
+        assert_eq!(Q31::from_f32(0.5).to_bits(), 1 << 30);
+
+        assert_eq!(format!("{}", q16(2.5)), "2.5");
+        assert_eq!(format!("{:?}", q16(2.5)), "Q16(2.5)");
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(q16(1.5) + q16(2.25), q16(3.75));
+        assert_eq!(q16(1.5) - q16(2.25), q16(-0.75));
+        assert_eq!(q16(1.5) * q16(-2.25), q16(-3.375));
+        assert_eq!(q16(3.0) / q16(-4.0), q16(-0.75));
+        // A divisão também arredonda para o mais próximo: 2/3 = 0,AAAA|A...
+        assert_eq!((q16(2.0) / q16(3.0)).to_bits(), 0xAAAB);
+        assert_eq!((q16(-2.0) / q16(3.0)).to_bits(), -0xAAAB);
+        assert_eq!(-q16(2.0), q16(-2.0));
+
+        let mut x = q16(1.0);
+        x += q16(0.5);
+        x -= q16(2.0);
+        assert_eq!(x, q16(-0.5));
+
+        // O produto arredonda para o mais próximo
+        assert_eq!((Q16::EPSILON * q16(0.5)).to_bits(), 1);
+        assert_eq!((Q16::EPSILON * q16(0.49)).to_bits(), 0);
+
+        let half = Q31::from_f32(0.5);
+        assert_eq!(half * half, Q31::from_f32(0.25));
+        assert_eq!(Q31::from_f32(0.25) / half, half);
+    }
+
+    #[test]
+    fn test_saturation() {
+        assert_eq!(Q16::MAX + Q16::ONE, Q16::MAX);
+        assert_eq!(Q16::MIN - Q16::ONE, Q16::MIN);
+        assert_eq!(q16(300.0) * q16(300.0), Q16::MAX);
+        assert_eq!(q16(300.0) * q16(-300.0), Q16::MIN);
+        assert_eq!(q16(1000.0) / q16(0.01), Q16::MAX);
+        assert_eq!(-Q16::MIN, Q16::MAX);
+
+        assert_eq!(q16(1.0) / Q16::ZERO, Q16::MAX);
+        assert_eq!(q16(-1.0) / Q16::ZERO, Q16::MIN);
+        assert_eq!(Q16::ZERO / Q16::ZERO, Q16::ZERO);
+
+        let half = Q31::from_f32(0.5);
+        assert_eq!(half + half, Q31::MAX);
+        assert_eq!(half / Q31::from_f32(0.25), Q31::MAX);
+    }
+}
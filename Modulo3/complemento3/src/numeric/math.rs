@@ -0,0 +1,189 @@
+//! Raiz quadrada, seno, cosseno e exponencial em ponto fixo, só com inteiros.
+//!
+//! São as contrapartes das aproximações do `micromath` usadas em `aula5_stm32` (`sqrt`,
+//! `sin`, `cos`, `exp`). As contas intermediárias usam `i64` com 30 bits fracionários (Q30),
+//! então o erro fica em poucos passos do formato de saída, qualquer que seja `FRAC`.
+
+use super::fixed::Fixed;
+
+const FRAC_Q30: u32 = 30;
+const ONE_Q30: i64 = 1 << FRAC_Q30;
+const PI_Q30: i64 = (core::f64::consts::PI * ONE_Q30 as f64) as i64;
+const HALF_PI_Q30: i64 = PI_Q30 / 2;
+const TAU_Q30: i64 = 2 * PI_Q30;
+const LN_2_Q30: i64 = (core::f64::consts::LN_2 * ONE_Q30 as f64) as i64;
+
+/// Produto em Q30, arredondado.
+///
+/// !!! Só não estoura com `|a · b| < 8`.
+fn mul(a: i64, b: i64) -> i64 {
+    (a * b + (1 << (FRAC_Q30 - 1))) >> FRAC_Q30
+}
+
+/// `x / 2^shift`, arredondado.
+fn shift_right(x: i64, shift: u32) -> i64 {
+    if shift == 0 {
+        x
+    } else {
+        (x + (1 << (shift - 1))) >> shift
+    }
+}
+
+/// `sin(x)` para `x` em Q30, em qualquer faixa.
+fn sin_q30(x: i64) -> i64 {
+    // Reduz para (-π, π] e depois, por simetria, para [-π/2, π/2]
+    let mut x = x.rem_euclid(TAU_Q30);
+    if x > PI_Q30 {
+        x -= TAU_Q30;
+    }
+    if x > HALF_PI_Q30 {
+        x = PI_Q30 - x;
+    } else if x < -HALF_PI_Q30 {
+        x = -PI_Q30 - x;
+    }
+
+    // Série de Taylor até x¹³ pelo método de Horner:
+    // x (1 - x²/(2·3) (1 - x²/(4·5) (1 - ...)))
+    // O próximo termo, (π/2)¹⁵/15!, é menor que 1e-9.
+    let x2 = mul(x, x);
+    let series = [156, 110, 72, 42, 20, 6]
+        .iter()
+        .fold(ONE_Q30, |acc, &d| ONE_Q30 - mul(x2, acc) / d);
+    mul(x, series)
+}
+
+impl<const FRAC: u32> Fixed<FRAC> {
+    fn to_q30(self) -> i64 {
+        let bits = self.to_bits() as i64;
+        if FRAC <= FRAC_Q30 {
+            bits << (FRAC_Q30 - FRAC)
+        } else {
+            shift_right(bits, FRAC - FRAC_Q30)
+        }
+    }
+
+    fn from_q30(x: i64) -> Self {
+        if FRAC <= FRAC_Q30 {
+            Self::saturate(shift_right(x, FRAC_Q30 - FRAC))
+        } else {
+            Self::saturate(x << (FRAC - FRAC_Q30))
+        }
+    }
+
+    /// Raiz quadrada, com erro menor que um passo. Negativos dão zero.
+    pub fn sqrt(self) -> Self {
+        if self.to_bits() <= 0 {
+            return Self::ZERO;
+        }
+        // √(bits / 2^FRAC) · 2^FRAC = √(bits · 2^FRAC)
+        Self::saturate(((self.to_bits() as u64) << FRAC).isqrt() as i64)
+    }
+
+    /// Seno de um ângulo em radianos.
+    ///
+    /// !!! Em [`Q31`](super::Q31) o ângulo fica em `[-1, 1)` rad.
+    pub fn sin(self) -> Self {
+        Self::from_q30(sin_q30(self.to_q30()))
+    }
+
+    /// Cosseno de um ângulo em radianos.
+    pub fn cos(self) -> Self {
+        Self::from_q30(sin_q30(self.to_q30() + HALF_PI_Q30))
+    }
+
+    /// `e^x`. Satura quando o resultado não cabe e vai a zero quando fica menor que um passo.
+    pub fn exp(self) -> Self {
+        // x = k·ln 2 + r, com |r| <= ln 2 / 2, e e^x = 2^k · e^r
+        let x = self.to_q30();
+        let k = (x + LN_2_Q30 / 2).div_euclid(LN_2_Q30);
+        let r = x - k * LN_2_Q30;
+
+        // Taylor até r¹⁰: 1 + r (1 + r/2 (1 + r/3 (...)))
+        let series = (1..=10)
+            .rev()
+            .fold(ONE_Q30, |acc, n| ONE_Q30 + mul(r, acc) / n);
+
+        // e^r fica em [0,7; 1,42], então desloca de `k` e troca de Q30 para `FRAC`
+        let shift = k + FRAC as i64 - FRAC_Q30 as i64;
+        if shift >= 0 {
+            if shift > 32 {
+                Self::MAX
+            } else {
+                Self::saturate(series << shift)
+            }
+        } else if shift < -62 {
+            Self::ZERO
+        } else {
+            Self::saturate(shift_right(series, (-shift) as u32))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::numeric::{Q16, Q31};
+
+    /// Maior erro, em passos do formato, contra a função em `f64`.
+    fn max_error<const FRAC: u32>(
+        inputs: impl Iterator<Item = f64>,
+        fixed: fn(Fixed<FRAC>) -> Fixed<FRAC>,
+        reference: fn(f64) -> f64,
+    ) -> f64 {
+        let scale = (1u64 << FRAC) as f64;
+        inputs
+            .map(|x| {
+                // O erro é contra o valor que a entrada realmente tem em ponto fixo
+                let input = Fixed::<FRAC>::from_bits((x * scale).round() as i32);
+                let exact = reference(input.to_bits() as f64 / scale);
+                (fixed(input).to_bits() as f64 - exact * scale).abs()
+            })
+            .fold(0.0, f64::max)
+    }
+
+    fn range(start: f64, end: f64, count: usize) -> impl Iterator<Item = f64> {
+        (0..count).map(move |k| start + (end - start) * k as f64 / (count - 1) as f64)
+    }
+
+    #[test]
+    fn test_sqrt() {
+        assert_eq!(Q16::from_int(4).sqrt(), Q16::from_int(2));
+        assert_eq!(Q16::from_int(-4).sqrt(), Q16::ZERO);
+        assert_eq!(Q31::from_f32(0.25).sqrt(), Q31::from_f32(0.5));
+
+        assert!(max_error::<16>(range(0.0, 30000.0, 10_000), Q16::sqrt, f64::sqrt) <= 1.0);
+        assert!(max_error::<31>(range(0.0, 0.999, 10_000), Q31::sqrt, f64::sqrt) <= 1.0);
+    }
+
+    #[test]
+    fn test_sin_and_cos() {
+        assert!(max_error::<16>(range(-100.0, 100.0, 10_000), Q16::sin, f64::sin) <= 1.0);
+        assert!(max_error::<16>(range(-100.0, 100.0, 10_000), Q16::cos, f64::cos) <= 1.0);
+
+        // Em Q31 o passo é 4,7e-10, então alguns passos de erro ainda são 1e-9
+        assert!(max_error::<31>(range(-1.0, 0.999, 10_000), Q31::sin, f64::sin) <= 4.0);
+        assert_eq!(Q31::ZERO.cos(), Q31::MAX);
+    }
+
+    #[test]
+    fn test_exp() {
+        assert_eq!(Q16::ZERO.exp(), Q16::ONE);
+        assert_eq!(Q16::from_int(11).exp(), Q16::MAX);
+        assert_eq!(Q16::from_int(-12).exp(), Q16::ZERO);
+        assert_eq!(Q16::MIN.exp(), Q16::ZERO);
+
+        assert!(max_error::<16>(range(-10.0, 5.0, 10_000), Q16::exp, f64::exp) <= 1.0);
+
+        // Perto de e^10 um passo de Q16 é muito menor que a precisão da série: erro relativo
+        let relative = range(5.0, 10.0, 10_000)
+            .map(|x| {
+                let input = Q16::from_f32(x as f32);
+                let exact = (input.to_f32() as f64).exp();
+                ((input.exp().to_f32() as f64 - exact) / exact).abs()
+            })
+            .fold(0.0, f64::max);
+        assert!(relative < 1e-6, "{relative}");
+
+        assert!(max_error::<31>(range(-1.0, -0.01, 1000), Q31::exp, f64::exp) <= 4.0);
+    }
+}
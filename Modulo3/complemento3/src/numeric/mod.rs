@@ -0,0 +1,162 @@
+//! Tipos numéricos para os sinais, filtros e controladores: `f32` ou ponto fixo.
+//!
+//! Em microcontroladores sem FPU cada operação com `f32` vira uma chamada de biblioteca. Com
+//! [`Fixed`] as contas são com inteiros, saturando em vez de estourar. O código genérico usa
+//! [`Number`], e os tipos que recebem o parâmetro usam `f32` por padrão:
+//!
+//! ```ignore
+//! let sine: Sine = Sine::new(1.0, 1.0, 0.0);
+//! let fixed = Sine::new(Q16::from_f32(1.0), Q16::ONE, Q16::ZERO);
+//! let y: Q16 = fixed.compute(Q16::from_f32(0.25));
+//! ```
+//!
+//! !!! Como os filtros, só usa `core` e o `libm`.
+
+pub mod fixed;
+mod math;
+
+pub use fixed::{Fixed, Q16, Q31};
+
+use core::fmt::Debug;
+use core::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+/// Operações que os sinais, filtros e controladores precisam.
+pub trait Number:
+    Copy
+    + Debug
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+    + AddAssign
+    + SubAssign
+{
+    const ZERO: Self;
+    /// `1`, ou o maior valor representável se não couber (em [`Q31`]).
+    const ONE: Self;
+    /// Menor valor representável; `-∞` no `f32`.
+    const MIN: Self;
+    /// Maior valor representável; `∞` no `f32`.
+    const MAX: Self;
+    /// `2π`, ou o maior valor representável se não couber.
+    const TAU: Self;
+
+    /// O valor mais próximo de `x`, saturando fora da faixa.
+    fn from_f32(x: f32) -> Self;
+
+    fn to_f32(self) -> f32;
+
+    /// Inteiro, saturando fora da faixa.
+    fn from_int(n: i32) -> Self;
+
+    fn abs(self) -> Self {
+        if self < Self::ZERO {
+            -self
+        } else {
+            self
+        }
+    }
+
+    fn min(self, other: Self) -> Self {
+        if other < self {
+            other
+        } else {
+            self
+        }
+    }
+
+    fn max(self, other: Self) -> Self {
+        if other > self {
+            other
+        } else {
+            self
+        }
+    }
+
+    fn clamp(self, min: Self, max: Self) -> Self {
+        self.max(min).min(max)
+    }
+
+    /// Resto sempre em `[0, |rhs|)`. Com `rhs` zero, `f32` dá `NaN` e [`Fixed`], que não tem
+    /// `NaN`, dá zero.
+    fn rem_euclid(self, rhs: Self) -> Self;
+
+    fn sqrt(self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn exp(self) -> Self;
+}
+
+impl Number for f32 {
+    const ZERO: f32 = 0.0;
+    const ONE: f32 = 1.0;
+    const MIN: f32 = f32::NEG_INFINITY;
+    const MAX: f32 = f32::INFINITY;
+    const TAU: f32 = core::f32::consts::TAU;
+
+    fn from_f32(x: f32) -> f32 {
+        x
+    }
+
+    fn to_f32(self) -> f32 {
+        self
+    }
+
+    fn from_int(n: i32) -> f32 {
+        n as f32
+    }
+
+    fn rem_euclid(self, rhs: f32) -> f32 {
+        let r = self % rhs;
+        if r < 0.0 {
+            r + rhs.abs()
+        } else {
+            r
+        }
+    }
+
+    fn sqrt(self) -> f32 {
+        libm::sqrtf(self)
+    }
+
+    fn sin(self) -> f32 {
+        libm::sinf(self)
+    }
+
+    fn cos(self) -> f32 {
+        libm::cosf(self)
+    }
+
+    fn exp(self) -> f32 {
+        libm::expf(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// O mesmo código genérico nos dois tipos.
+    fn hypot<T: Number>(a: T, b: T) -> T {
+        (a * a + b * b).sqrt()
+    }
+
+    #[test]
+    fn test_generic() {
+        assert_eq!(hypot(3.0f32, 4.0), 5.0);
+        assert_eq!(hypot(Q16::from_int(3), Q16::from_int(4)), Q16::from_int(5));
+
+        assert_eq!(Number::rem_euclid(-0.25f32, 1.0), 0.75);
+        assert_eq!(
+            Q16::from_f32(-0.25).rem_euclid(Q16::ONE),
+            Q16::from_f32(0.75)
+        );
+        assert!(Number::rem_euclid(1.0f32, 0.0).is_nan());
+        assert_eq!(Q16::ONE.rem_euclid(Q16::ZERO), Q16::ZERO);
+        assert_eq!(Q16::MIN.rem_euclid(-Q16::EPSILON), Q16::ZERO);
+        assert_eq!(Q16::from_f32(-2.5).clamp(-Q16::ONE, Q16::ONE), -Q16::ONE);
+        assert_eq!(Number::clamp(2.5f32, -1.0, 1.0), 1.0);
+    }
+}
@@ -0,0 +1,251 @@
+//! Controladores para a simulação em malha fechada.
+
+use crate::numeric::Number;
+
+/// Ganho proporcional padrão usado nos exemplos.
+pub const P: f32 = 5.0;
+
+/// Algo que calcula o esforço de controle a partir da referência e da medida. `T` é o tipo
+/// dos valores, `f32` por padrão.
+pub trait Controller<T: Number = f32> {
+    /// Calcula a saída para o passo atual. `dt` é o tempo desde a chamada anterior.
+    fn update(&mut self, setpoint: T, measurement: T, dt: T) -> T;
+
+    /// Volta ao estado inicial, sem mudar a sintonia.
+    fn reset(&mut self) {}
+}
+
+/// Como evitar que o integrador continue acumulando enquanto a saída está saturada.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AntiWindup<T = f32> {
+    /// O integrador acumula sempre. Com saturação, causa sobressinal grande e lento.
+    None,
+    /// Integração condicional: o integrador para quando a saída está saturada e o erro empurra
+    /// para o mesmo lado da saturação.
+    Clamping,
+    /// O integrador é corrigido pela diferença entre a saída saturada e a calculada, com
+    /// constante de tempo `tracking` (costuma ficar entre `Ti` e `Td`).
+    BackCalculation { tracking: T },
+}
+
+/// Controlador PID paralelo: `u = kp * e + ki * ∫e dt - kd * dy/dt`.
+///
+/// A derivada é da medida e não do erro, para que um degrau na referência não gere um pico na
+/// saída (derivative kick). Com `ki` e `kd` zerados vira um controlador P; com `kd` zerado, PI.
+///
+/// Em ponto fixo, sem limites a saída satura nos extremos do tipo.
+#[derive(Debug, Clone)]
+pub struct Pid<T = f32> {
+    kp: T,
+    ki: T,
+    kd: T,
+    limits: (T, T),
+    anti_windup: AntiWindup<T>,
+    // Constante de tempo do filtro da derivada, em segundos. Zero desliga o filtro.
+    derivative_filter: T,
+    integral: T,
+    derivative: T,
+    last_measurement: Option<T>,
+}
+
+impl<T: Number> Pid<T> {
+    pub fn new(kp: T, ki: T, kd: T) -> Self {
+        Self {
+            kp,
+            ki,
+            kd,
+            limits: (T::MIN, T::MAX),
+            anti_windup: AntiWindup::Clamping,
+            derivative_filter: T::ZERO,
+            integral: T::ZERO,
+            derivative: T::ZERO,
+            last_measurement: None,
+        }
+    }
+
+    pub fn p(kp: T) -> Self {
+        Self::new(kp, T::ZERO, T::ZERO)
+    }
+
+    pub fn pi(kp: T, ki: T) -> Self {
+        Self::new(kp, ki, T::ZERO)
+    }
+
+    /// Satura a saída em `[min, max]`, como um atuador real.
+    pub fn with_limits(self, min: T, max: T) -> Self {
+        Self {
+            limits: (min, max),
+            ..self
+        }
+    }
+
+    pub fn with_anti_windup(self, anti_windup: AntiWindup<T>) -> Self {
+        Self {
+            anti_windup,
+            ..self
+        }
+    }
+
+    /// Passa a derivada por um passa-baixas com constante de tempo `tau`, para não amplificar
+    /// o ruído da medida.
+    pub fn with_derivative_filter(self, tau: T) -> Self {
+        Self {
+            derivative_filter: tau,
+            ..self
+        }
+    }
+
+    pub fn gains(&self) -> (T, T, T) {
+        (self.kp, self.ki, self.kd)
+    }
+
+    /// Valor atual do termo integral (já multiplicado por `ki`).
+    pub fn integral(&self) -> T {
+        self.integral
+    }
+}
+
+impl<T: Number> Controller<T> for Pid<T> {
+    fn update(&mut self, setpoint: T, measurement: T, dt: T) -> T {
+        let error = setpoint - measurement;
+
+        if let Some(last) = self.last_measurement {
+            let raw = -self.kd * (measurement - last) / dt;
+            let alpha = dt / (self.derivative_filter + dt);
+            self.derivative += alpha * (raw - self.derivative);
+        }
+        self.last_measurement = Some(measurement);
+
+        let unsaturated = self.kp * error + self.integral + self.derivative;
+        let (min, max) = self.limits;
+        let output = unsaturated.clamp(min, max);
+
+        match self.anti_windup {
+            AntiWindup::None => self.integral += self.ki * error * dt,
+            AntiWindup::Clamping => {
+                let winding_up = (unsaturated > max && error > T::ZERO)
+                    || (unsaturated < min && error < T::ZERO);
+                if !winding_up {
+                    self.integral += self.ki * error * dt;
+                }
+            }
+            AntiWindup::BackCalculation { tracking } => {
+                self.integral += (self.ki * error + (output - unsaturated) / tracking) * dt;
+            }
+        }
+
+        output
+    }
+
+    fn reset(&mut self) {
+        self.integral = T::ZERO;
+        self.derivative = T::ZERO;
+        self.last_measurement = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_proportional() {
+        let mut pid = Pid::p(P);
+
+        assert_eq!(pid.update(1.0, 0.2, 0.01), 4.0);
+        assert_eq!(pid.update(1.0, 1.5, 0.01), -2.5);
+    }
+
+    #[test]
+    fn test_integral_accumulates() {
+        let mut pid = Pid::pi(0.0, 2.0);
+
+        assert_eq!(pid.update(1.0, 0.0, 0.5), 0.0);
+        assert_eq!(pid.update(1.0, 0.0, 0.5), 1.0);
+        assert_eq!(pid.update(1.0, 0.0, 0.5), 2.0);
+        assert_eq!(pid.integral(), 3.0);
+
+        pid.reset();
+        assert_eq!(pid.integral(), 0.0);
+    }
+
+    #[test]
+    fn test_derivative_on_measurement() {
+        let mut pid = Pid::new(0.0, 0.0, 1.0);
+
+        // Um degrau na referência não passa pela derivada
+        assert_eq!(pid.update(0.0, 0.0, 0.1), 0.0);
+        assert_eq!(pid.update(10.0, 0.0, 0.1), 0.0);
+        // Medida subindo 0,5 em 0,1 s: derivada -5
+        assert_eq!(pid.update(10.0, 0.5, 0.1), -5.0);
+    }
+
+    #[test]
+    fn test_saturation_and_clamping() {
+        let mut pid = Pid::pi(1.0, 1.0).with_limits(-1.0, 1.0);
+
+        for _ in 0..100 {
+            assert_eq!(pid.update(10.0, 0.0, 0.1), 1.0);
+        }
+        // Saturado com erro positivo, o integrador não acumulou
+        assert_eq!(pid.integral(), 0.0);
+
+        let mut windup = Pid::pi(1.0, 1.0)
+            .with_limits(-1.0, 1.0)
+            .with_anti_windup(AntiWindup::None);
+        for _ in 0..100 {
+            windup.update(10.0, 0.0, 0.1);
+        }
+        assert!((windup.integral() - 100.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_back_calculation_tracks_limit() {
+        let mut pid = Pid::pi(0.25, 1.0)
+            .with_limits(-1.0, 1.0)
+            .with_anti_windup(AntiWindup::BackCalculation { tracking: 0.5 });
+
+        for _ in 0..1000 {
+            pid.update(2.0, 0.0, 0.01);
+        }
+        // Em regime: ki * e = (u - v) / tracking, com v = kp * e + I
+        // 2 = -(1 - (0,5 + I)) / 0,5  =>  I = 1,5
+        assert!((pid.integral() - 1.5).abs() < 0.01, "{}", pid.integral());
+    }
+
+    /// O mesmo PID em Q16 segue o de `f32` em malha fechada com uma planta de primeira ordem.
+    #[test]
+    fn test_fixed_point() {
+        use crate::numeric::{Number, Q16};
+
+        fn run<T: Number>(mut pid: Pid<T>) -> Vec<f32> {
+            // Planta `tau dy/dt = u - y` por Euler, com tau = 0,1 s
+            let (dt, alpha) = (T::from_f32(0.001), T::from_f32(0.01));
+            let mut y = T::ZERO;
+            (0..1000)
+                .map(|_| {
+                    let u = pid.update(T::ONE, y, dt);
+                    y += alpha * (u - y);
+                    y.to_f32()
+                })
+                .collect()
+        }
+
+        let q = Q16::from_f32;
+        let float = run(Pid::new(2.0, 20.0, 0.01)
+            .with_limits(-5.0, 5.0)
+            .with_derivative_filter(0.005));
+        let fixed = run(Pid::new(q(2.0), q(20.0), q(0.01))
+            .with_limits(q(-5.0), q(5.0))
+            .with_derivative_filter(q(0.005)));
+
+        let error = float
+            .iter()
+            .zip(&fixed)
+            .map(|(a, b)| (a - b).abs())
+            .fold(0.0, f32::max);
+        // Quase todo o erro vem de `dt`: 0,001 s em Q16 são 65,5 passos, arredondados para 66
+        assert!(error < 5e-3, "{error}");
+        assert!((fixed[999] - 1.0).abs() < 1e-3);
+    }
+}
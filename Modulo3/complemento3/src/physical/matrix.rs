@@ -0,0 +1,319 @@
+//! Matriz densa pequena, só com o necessário para os modelos em espaço de estados.
+//!
+//! As contas são em `f64`: a exponencial de matriz e o polinômio característico perdem
+//! precisão rápido em `f32`, e aqui o custo não importa porque tudo roda no host.
+
+use std::ops::{Add, Index, IndexMut, Mul, Sub};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Matrix {
+    rows: usize,
+    cols: usize,
+    data: Vec<f64>,
+}
+
+impl Matrix {
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        Self {
+            rows,
+            cols,
+            data: vec![0.0; rows * cols],
+        }
+    }
+
+    pub fn identity(n: usize) -> Self {
+        let mut m = Self::zeros(n, n);
+        for i in 0..n {
+            m[(i, i)] = 1.0;
+        }
+        m
+    }
+
+    /// Cria a matriz a partir das linhas. Todas precisam ter o mesmo tamanho.
+    pub fn from_rows<R: AsRef<[f64]>>(rows: &[R]) -> Self {
+        let cols = rows.first().map_or(0, |row| row.as_ref().len());
+        assert!(
+            rows.iter().all(|row| row.as_ref().len() == cols),
+            "rows with different sizes"
+        );
+
+        Self {
+            rows: rows.len(),
+            cols,
+            data: rows.iter().flat_map(|row| row.as_ref().to_vec()).collect(),
+        }
+    }
+
+    /// Vetor coluna.
+    pub fn column(values: &[f64]) -> Self {
+        Self {
+            rows: values.len(),
+            cols: 1,
+            data: values.to_vec(),
+        }
+    }
+
+    /// Vetor linha.
+    pub fn row(values: &[f64]) -> Self {
+        Self {
+            rows: 1,
+            cols: values.len(),
+            data: values.to_vec(),
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn is_square(&self) -> bool {
+        self.rows == self.cols
+    }
+
+    pub fn scale(&self, k: f64) -> Self {
+        Self {
+            data: self.data.iter().map(|v| v * k).collect(),
+            ..self.clone()
+        }
+    }
+
+    pub fn trace(&self) -> f64 {
+        (0..self.rows.min(self.cols)).map(|i| self[(i, i)]).sum()
+    }
+
+    /// Maior soma dos módulos de uma linha (norma infinito).
+    pub fn norm(&self) -> f64 {
+        (0..self.rows)
+            .map(|i| (0..self.cols).map(|j| self[(i, j)].abs()).sum::<f64>())
+            .fold(0.0, f64::max)
+    }
+
+    /// Inversa por eliminação de Gauss-Jordan com pivotamento parcial. `None` se a matriz for
+    /// singular.
+    pub fn inverse(&self) -> Option<Self> {
+        assert!(self.is_square(), "inverse of a non-square matrix");
+        let n = self.rows;
+        let mut a = self.clone();
+        let mut inv = Self::identity(n);
+
+        for col in 0..n {
+            let pivot = (col..n)
+                .max_by(|&i, &j| a[(i, col)].abs().total_cmp(&a[(j, col)].abs()))
+                .unwrap();
+            if a[(pivot, col)].abs() < 1e-12 {
+                return None;
+            }
+            a.swap_rows(col, pivot);
+            inv.swap_rows(col, pivot);
+
+            let p = a[(col, col)];
+            for j in 0..n {
+                a[(col, j)] /= p;
+                inv[(col, j)] /= p;
+            }
+
+            for i in (0..n).filter(|&i| i != col) {
+                let factor = a[(i, col)];
+                for j in 0..n {
+                    a[(i, j)] -= factor * a[(col, j)];
+                    inv[(i, j)] -= factor * inv[(col, j)];
+                }
+            }
+        }
+
+        Some(inv)
+    }
+
+    /// Exponencial de matriz, por escalonamento e quadratura com série de Taylor.
+    pub fn exp(&self) -> Self {
+        assert!(self.is_square(), "exponential of a non-square matrix");
+
+        // Divide por 2^s até a norma ficar menor que 0,5, onde a série converge rápido
+        let norm = self.norm();
+        let squarings = if norm > 0.5 {
+            (norm / 0.5).log2().ceil() as u32
+        } else {
+            0
+        };
+        let a = self.scale(1.0 / f64::from(2u32.pow(squarings)));
+
+        let mut result = Self::identity(self.rows);
+        let mut term = Self::identity(self.rows);
+        for k in 1..=16 {
+            term = (&term * &a).scale(1.0 / k as f64);
+            result = &result + &term;
+        }
+
+        for _ in 0..squarings {
+            result = &result * &result;
+        }
+        result
+    }
+
+    /// Coeficientes do polinômio característico `det(zI - A)`, em potências decrescentes e com
+    /// o primeiro coeficiente igual a 1 (algoritmo de Faddeev-LeVerrier).
+    pub fn characteristic_polynomial(&self) -> Vec<f64> {
+        assert!(
+            self.is_square(),
+            "characteristic polynomial of a non-square matrix"
+        );
+        let n = self.rows;
+        let mut coefficients = vec![1.0];
+        let mut m = Self::zeros(n, n);
+
+        for k in 1..=n {
+            let c = *coefficients.last().unwrap();
+            m = &(self * &m) + &Self::identity(n).scale(c);
+            let next = -(self * &m).trace() / k as f64;
+            coefficients.push(next);
+        }
+
+        coefficients
+    }
+
+    /// Submatriz com as linhas `rows` e as colunas `cols`.
+    pub fn block(&self, rows: std::ops::Range<usize>, cols: std::ops::Range<usize>) -> Self {
+        let mut m = Self::zeros(rows.len(), cols.len());
+        for (i, row) in rows.clone().enumerate() {
+            for (j, col) in cols.clone().enumerate() {
+                m[(i, j)] = self[(row, col)];
+            }
+        }
+        m
+    }
+
+    /// Copia `other` para a posição (`row`, `col`).
+    pub fn set_block(&mut self, row: usize, col: usize, other: &Self) {
+        for i in 0..other.rows {
+            for j in 0..other.cols {
+                self[(row + i, col + j)] = other[(i, j)];
+            }
+        }
+    }
+
+    fn swap_rows(&mut self, a: usize, b: usize) {
+        if a != b {
+            for j in 0..self.cols {
+                self.data.swap(a * self.cols + j, b * self.cols + j);
+            }
+        }
+    }
+}
+
+impl Index<(usize, usize)> for Matrix {
+    type Output = f64;
+
+    fn index(&self, (i, j): (usize, usize)) -> &f64 {
+        &self.data[i * self.cols + j]
+    }
+}
+
+impl IndexMut<(usize, usize)> for Matrix {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut f64 {
+        &mut self.data[i * self.cols + j]
+    }
+}
+
+impl Add for &Matrix {
+    type Output = Matrix;
+
+    fn add(self, rhs: Self) -> Matrix {
+        assert_eq!((self.rows, self.cols), (rhs.rows, rhs.cols));
+        Matrix {
+            data: self
+                .data
+                .iter()
+                .zip(&rhs.data)
+                .map(|(a, b)| a + b)
+                .collect(),
+            ..self.clone()
+        }
+    }
+}
+
+impl Sub for &Matrix {
+    type Output = Matrix;
+
+    fn sub(self, rhs: Self) -> Matrix {
+        assert_eq!((self.rows, self.cols), (rhs.rows, rhs.cols));
+        Matrix {
+            data: self
+                .data
+                .iter()
+                .zip(&rhs.data)
+                .map(|(a, b)| a - b)
+                .collect(),
+            ..self.clone()
+        }
+    }
+}
+
+impl Mul for &Matrix {
+    type Output = Matrix;
+
+    fn mul(self, rhs: Self) -> Matrix {
+        assert_eq!(self.cols, rhs.rows, "incompatible sizes");
+        let mut m = Matrix::zeros(self.rows, rhs.cols);
+        for i in 0..self.rows {
+            for k in 0..self.cols {
+                let a = self[(i, k)];
+                for j in 0..rhs.cols {
+                    m[(i, j)] += a * rhs[(k, j)];
+                }
+            }
+        }
+        m
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: &Matrix, b: &Matrix) {
+        let diff = (a - b).norm();
+        assert!(diff < 1e-9, "{a:?} != {b:?}");
+    }
+
+    #[test]
+    fn test_inverse() {
+        let a = Matrix::from_rows(&[[0.0, 2.0], [1.0, 3.0]]);
+        let inv = a.inverse().unwrap();
+
+        assert_close(&(&a * &inv), &Matrix::identity(2));
+        assert!(Matrix::from_rows(&[[1.0, 2.0], [2.0, 4.0]])
+            .inverse()
+            .is_none());
+    }
+
+    #[test]
+    fn test_exp() {
+        // Diagonal: exponencial de cada elemento
+        let a = Matrix::from_rows(&[[-1.0, 0.0], [0.0, 2.0]]);
+        let expected = Matrix::from_rows(&[[(-1f64).exp(), 0.0], [0.0, 2f64.exp()]]);
+        assert_close(&a.exp(), &expected);
+
+        // Rotação: exp([[0, -θ], [θ, 0]]) = [[cos θ, -sin θ], [sin θ, cos θ]]
+        let theta: f64 = 3.0;
+        let a = Matrix::from_rows(&[[0.0, -theta], [theta, 0.0]]);
+        let expected =
+            Matrix::from_rows(&[[theta.cos(), -theta.sin()], [theta.sin(), theta.cos()]]);
+        assert_close(&a.exp(), &expected);
+    }
+
+    #[test]
+    fn test_characteristic_polynomial() {
+        // Autovalores 2 e 3: z² - 5z + 6
+        let a = Matrix::from_rows(&[[2.0, 1.0], [0.0, 3.0]]);
+        let p = a.characteristic_polynomial();
+
+        assert_eq!(p.len(), 3);
+        for (c, expected) in p.iter().zip([1.0, -5.0, 6.0]) {
+            assert!((c - expected).abs() < 1e-12);
+        }
+    }
+}
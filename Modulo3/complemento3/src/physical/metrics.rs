@@ -0,0 +1,352 @@
+//! Índices de desempenho da resposta ao degrau, calculados a partir de uma simulação.
+//!
+//! Tempos de subida e de acomodação e o sobressinal são medidos em relação ao valor final da
+//! saída, e não da referência: assim funcionam também para um controlador P, que deixa erro
+//! em regime. Esse erro aparece separado em `steady_state_error`.
+
+use super::simulation::Run;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Metrics {
+    /// De 10% a 90% da variação da saída, em segundos.
+    pub rise_time: Option<f32>,
+    /// Tempo até a saída ficar dentro de ±2% da variação em torno do valor final, em segundos.
+    pub settling_time_2: Option<f32>,
+    /// O mesmo com ±5%.
+    pub settling_time_5: Option<f32>,
+    /// Quanto o pico passa do valor final, em % da variação.
+    pub overshoot: f32,
+    /// Referência menos saída no fim da simulação.
+    pub steady_state_error: f32,
+    /// `∫|e| dt`
+    pub iae: f32,
+    /// `∫e² dt`
+    pub ise: f32,
+    /// `∫t |e| dt`: pesa mais o erro que demora a sumir.
+    pub itae: f32,
+    /// Energia do controle, `∫u² dt`.
+    pub effort: f32,
+    /// Maior `|u|`, para comparar com o limite do atuador.
+    pub peak_control: f32,
+}
+
+impl Metrics {
+    pub fn new(run: &Run) -> Self {
+        let dt = match run.time.as_slice() {
+            [first, second, ..] => second - first,
+            _ => 0.0,
+        };
+        let start = run.time.first().copied().unwrap_or(0.0);
+
+        let integral = |f: &dyn Fn(usize) -> f32| (0..run.len()).map(f).sum::<f32>() * dt;
+        let iae = integral(&|k| run.error[k].abs());
+        let ise = integral(&|k| run.error[k] * run.error[k]);
+        let itae = integral(&|k| (run.time[k] - start) * run.error[k].abs());
+        let effort = integral(&|k| run.control[k] * run.control[k]);
+        let peak_control = run.control.iter().fold(0.0, |max, u| u.abs().max(max));
+
+        let (initial, last) = match (run.output.first(), run.output.last()) {
+            (Some(&initial), Some(&last)) => (initial, last),
+            _ => (0.0, 0.0),
+        };
+        let span = last - initial;
+        // Saída normalizada: 0 no início e 1 no valor final
+        let normalized: Vec<f32> = run.output.iter().map(|y| (y - initial) / span).collect();
+        let moved = span.abs() > f32::EPSILON;
+
+        let crossing = |level: f32| {
+            normalized
+                .iter()
+                .position(|&y| y >= level)
+                .map(|k| run.time[k])
+        };
+        let rise_time = match (crossing(0.1), crossing(0.9)) {
+            (Some(low), Some(high)) if moved => Some(high - low),
+            _ => None,
+        };
+
+        let settling_time = |band: f32| {
+            if !moved {
+                return None;
+            }
+            let outside = normalized.iter().rposition(|y| (y - 1.0).abs() > band);
+            Some(outside.map_or(start, |k| run.time[(k + 1).min(run.len() - 1)]) - start)
+        };
+
+        let overshoot = if moved {
+            normalized.iter().fold(0.0f32, |max, &y| max.max(y - 1.0)) * 100.0
+        } else {
+            0.0
+        };
+
+        Self {
+            rise_time,
+            settling_time_2: settling_time(0.02),
+            settling_time_5: settling_time(0.05),
+            overshoot,
+            steady_state_error: run.error.last().copied().unwrap_or(0.0),
+            iae,
+            ise,
+            itae,
+            effort,
+            peak_control,
+        }
+    }
+
+    /// Compara com outro resultado, com tolerância relativa `tolerance` em cada índice.
+    pub fn approx_eq(&self, other: &Self, tolerance: f32) -> bool {
+        let close = |a: f32, b: f32| (a - b).abs() <= tolerance * a.abs().max(b.abs()).max(1e-6);
+        let close_option = |a: Option<f32>, b: Option<f32>| match (a, b) {
+            (Some(a), Some(b)) => close(a, b),
+            (None, None) => true,
+            _ => false,
+        };
+
+        close_option(self.rise_time, other.rise_time)
+            && close_option(self.settling_time_2, other.settling_time_2)
+            && close_option(self.settling_time_5, other.settling_time_5)
+            && close(self.overshoot, other.overshoot)
+            && close(self.steady_state_error, other.steady_state_error)
+            && close(self.iae, other.iae)
+            && close(self.ise, other.ise)
+            && close(self.itae, other.itae)
+            && close(self.effort, other.effort)
+            && close(self.peak_control, other.peak_control)
+    }
+}
+
+impl From<&Run> for Metrics {
+    fn from(run: &Run) -> Self {
+        Metrics::new(run)
+    }
+}
+
+/// Tabela com os índices de várias simulações, uma por linha.
+///
+/// ```ignore
+/// let mut report = Report::new();
+/// report.add("P = 5", &run);
+/// println!("{report}");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Report {
+    rows: Vec<(String, Metrics)>,
+}
+
+impl Report {
+    const HEADER: [&'static str; 11] = [
+        "",
+        "tr (s)",
+        "ts 2% (s)",
+        "ts 5% (s)",
+        "Mp (%)",
+        "ess",
+        "IAE",
+        "ISE",
+        "ITAE",
+        "∫u²",
+        "max |u|",
+    ];
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, name: &str, run: &Run) -> &Metrics {
+        self.rows.push((name.to_string(), Metrics::new(run)));
+        &self.rows.last().unwrap().1
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Metrics> {
+        self.rows
+            .iter()
+            .find(|(row, _)| row == name)
+            .map(|(_, metrics)| metrics)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = (&str, &Metrics)> {
+        self.rows
+            .iter()
+            .map(|(name, metrics)| (name.as_str(), metrics))
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let time = |t: Option<f32>| t.map_or("-".to_string(), |t| format!("{t:.3}"));
+        let cells: Vec<Vec<String>> = self
+            .rows
+            .iter()
+            .map(|(name, m)| {
+                vec![
+                    name.clone(),
+                    time(m.rise_time),
+                    time(m.settling_time_2),
+                    time(m.settling_time_5),
+                    format!("{:.1}", m.overshoot),
+                    format!("{:.4}", m.steady_state_error),
+                    format!("{:.4}", m.iae),
+                    format!("{:.4}", m.ise),
+                    format!("{:.4}", m.itae),
+                    format!("{:.3}", m.effort),
+                    format!("{:.3}", m.peak_control),
+                ]
+            })
+            .collect();
+
+        let widths: Vec<usize> = (0..Self::HEADER.len())
+            .map(|i| {
+                cells
+                    .iter()
+                    .map(|row| row[i].chars().count())
+                    .chain([Self::HEADER[i].chars().count()])
+                    .max()
+                    .unwrap()
+            })
+            .collect();
+
+        let line = |f: &mut Formatter<'_>, row: &[&str]| {
+            for (i, (cell, width)) in row.iter().zip(&widths).enumerate() {
+                // Nome alinhado à esquerda, números à direita
+                let padding = " ".repeat(width - cell.chars().count());
+                if i == 0 {
+                    write!(f, "{cell}{padding}")?;
+                } else {
+                    write!(f, " | {padding}{cell}")?;
+                }
+            }
+            writeln!(f)
+        };
+
+        line(f, &Self::HEADER)?;
+        let separator: Vec<String> = widths.iter().map(|&w| "-".repeat(w)).collect();
+        line(f, &separator.iter().map(String::as_str).collect::<Vec<_>>())?;
+        for row in &cells {
+            line(f, &row.iter().map(String::as_str).collect::<Vec<_>>())?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physical::controller::{Controller, Pid, P};
+    use crate::physical::plant::SecondOrder;
+    use crate::physical::simulation::ClosedLoop;
+    use crate::physical::System;
+    use crate::signals::step::Step;
+
+    const DT: f32 = 0.001;
+
+    /// Entrada fixa, para medir a resposta em malha aberta.
+    struct OpenLoop(f32);
+
+    impl Controller for OpenLoop {
+        fn update(&mut self, _setpoint: f32, _measurement: f32, _dt: f32) -> f32 {
+            self.0
+        }
+    }
+
+    /// Série montada à mão, para conferir as definições.
+    fn run(output: &[f32]) -> Run {
+        let time: Vec<f32> = (0..output.len()).map(|k| k as f32).collect();
+        Run {
+            setpoint: vec![1.0; output.len()],
+            error: output.iter().map(|y| 1.0 - y).collect(),
+            control: vec![2.0; output.len()],
+            output: output.to_vec(),
+            time,
+        }
+    }
+
+    #[test]
+    fn test_definitions() {
+        let metrics = Metrics::new(&run(&[0.0, 0.05, 0.5, 0.95, 1.2, 1.03, 0.99, 1.0, 1.0]));
+
+        // 10% em t = 2, 90% em t = 3
+        assert_eq!(metrics.rise_time, Some(1.0));
+        // Última amostra fora da faixa de 2% em t = 5, de 5% em t = 4
+        assert_eq!(metrics.settling_time_2, Some(6.0));
+        assert_eq!(metrics.settling_time_5, Some(5.0));
+        assert!((metrics.overshoot - 20.0).abs() < 1e-4);
+        assert_eq!(metrics.steady_state_error, 0.0);
+        assert!((metrics.iae - 2.74).abs() < 1e-5);
+        assert_eq!(metrics.effort, 4.0 * 9.0);
+        assert_eq!(metrics.peak_control, 2.0);
+    }
+
+    #[test]
+    fn test_flat_output() {
+        let metrics = Metrics::new(&run(&[0.0; 10]));
+
+        assert_eq!(metrics.rise_time, None);
+        assert_eq!(metrics.settling_time_2, None);
+        assert_eq!(metrics.overshoot, 0.0);
+        assert_eq!(metrics.steady_state_error, 1.0);
+    }
+
+    /// Primeira ordem em malha aberta: `tr = τ ln 9` e `ts(2%) = τ ln 50`.
+    #[test]
+    fn test_first_order_analytic() {
+        let tau = 0.1;
+        let run = ClosedLoop::new(DT, 1.0).run(
+            &mut System::new(tau, 1.0),
+            &mut OpenLoop(1.0),
+            &Step::unit(),
+        );
+        let metrics = Metrics::new(&run);
+
+        // O valor final ainda não é exatamente 1, o que encurta um pouco os tempos
+        assert!((metrics.rise_time.unwrap() - tau * 9f32.ln()).abs() < 2e-3);
+        assert!((metrics.settling_time_2.unwrap() - tau * 50f32.ln()).abs() < 5e-3);
+        assert!((metrics.settling_time_5.unwrap() - tau * 20f32.ln()).abs() < 5e-3);
+        assert_eq!(metrics.overshoot, 0.0);
+        // ∫(1 - y) dt = τ
+        assert!((metrics.iae - tau).abs() < 2e-3);
+    }
+
+    /// Sobressinal do segundo ordem subamortecido: `e^(-πζ/√(1-ζ²))`.
+    #[test]
+    fn test_second_order_overshoot() {
+        let zeta: f32 = 0.5;
+        let run = ClosedLoop::new(DT, 5.0).run(
+            &mut SecondOrder::new(1.0, 10.0, zeta),
+            &mut OpenLoop(1.0),
+            &Step::unit(),
+        );
+        let expected = (-std::f32::consts::PI * zeta / (1.0 - zeta * zeta).sqrt()).exp() * 100.0;
+
+        assert!((Metrics::new(&run).overshoot - expected).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_report() {
+        let simulation = ClosedLoop::new(DT, 1.0);
+        let mut report = Report::new();
+        for (name, kp) in [("P = 1", 1.0), ("P = 5", P), ("P = 20", 20.0)] {
+            let run = simulation.run(&mut System::default(), &mut Pid::p(kp), &Step::unit());
+            report.add(name, &run);
+        }
+
+        // Ganho maior: mais rápido e com menos erro em regime
+        let slow = report.get("P = 1").unwrap();
+        let fast = report.get("P = 20").unwrap();
+        assert!(fast.rise_time < slow.rise_time);
+        assert!(fast.steady_state_error < slow.steady_state_error);
+        assert!(fast.approx_eq(fast, 0.0));
+        assert!(!fast.approx_eq(slow, 0.1));
+
+        let table = report.to_string();
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert!(lines[0].contains("tr (s)"));
+        assert!(lines[4].starts_with("P = 20"));
+        // Colunas alinhadas
+        assert!(lines
+            .iter()
+            .all(|line| line.chars().count() == lines[0].chars().count()));
+    }
+}
@@ -0,0 +1,104 @@
+pub mod controller;
+#[cfg(feature = "std")]
+pub mod matrix;
+#[cfg(feature = "std")]
+pub mod metrics;
+#[cfg(feature = "std")]
+pub mod plant;
+#[cfg(feature = "std")]
+pub mod simulation;
+#[cfg(feature = "std")]
+pub mod state_space;
+#[cfg(feature = "std")]
+pub mod transfer;
+#[cfg(feature = "std")]
+pub mod tuning;
+
+#[cfg(feature = "std")]
+use plant::Plant;
+
+/// Sistema de primeira ordem: `tau * dy/dt + y = gain * u`.
+#[derive(Debug, Clone)]
+pub struct System {
+    tau: f32,
+    gain: f32,
+    output: f32,
+}
+
+impl Default for System {
+    fn default() -> Self {
+        const TAU: f32 = 0.1;
+
+        Self::new(TAU, 1.0)
+    }
+}
+
+impl System {
+    pub fn new(tau: f32, gain: f32) -> Self {
+        Self {
+            tau,
+            gain,
+            output: 0.0,
+        }
+    }
+
+    /// Resposta em malha aberta a um degrau unitário aplicado em `t = 0`.
+    pub fn sensor(&self, t: f32) -> f32 {
+        self.gain * (1.0 - libm::expf(-t / self.tau))
+    }
+
+    /// Avança a simulação em `dt` segundos com a entrada `u` constante durante o passo e
+    /// devolve a nova saída.
+    ///
+    /// A discretização é exata para entrada constante no passo (segurador de ordem zero), então
+    /// o resultado não depende do tamanho de `dt`.
+    pub fn step(&mut self, u: f32, dt: f32) -> f32 {
+        let alpha = 1.0 - libm::expf(-dt / self.tau);
+        self.output += alpha * (self.gain * u - self.output);
+
+        self.output
+    }
+
+    pub fn output(&self) -> f32 {
+        self.output
+    }
+
+    pub fn reset(&mut self) {
+        self.output = 0.0;
+    }
+}
+
+#[cfg(feature = "std")]
+impl Plant for System {
+    fn step(&mut self, u: f32, dt: f32) -> f32 {
+        System::step(self, u, dt)
+    }
+
+    fn output(&self) -> f32 {
+        System::output(self)
+    }
+
+    fn reset(&mut self) {
+        System::reset(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_step_matches_open_loop_response() {
+        let dt = 0.01;
+        let mut system = System::new(0.1, 2.0);
+
+        for k in 1..=50 {
+            let y = system.step(1.0, dt);
+            let expected = system.sensor(k as f32 * dt);
+            assert!((y - expected).abs() < 1e-4, "{y} != {expected}");
+        }
+
+        system.reset();
+        assert_eq!(system.output(), 0.0);
+    }
+}
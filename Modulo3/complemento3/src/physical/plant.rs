@@ -0,0 +1,229 @@
+//! Modelos de sistemas (plantas) para simular em malha aberta ou fechada.
+
+use super::matrix::Matrix;
+use super::state_space::{Discretization, StateSpace};
+use std::collections::VecDeque;
+use std::fmt::{Display, Formatter};
+
+/// Sistema com uma entrada e uma saída, simulado em passos de tempo.
+pub trait Plant {
+    /// Aplica a entrada `u`, constante durante `dt` segundos, e devolve a saída no fim do
+    /// passo.
+    fn step(&mut self, u: f32, dt: f32) -> f32;
+
+    /// Saída atual, sem avançar o tempo.
+    fn output(&self) -> f32;
+
+    /// Volta ao repouso (estado zero).
+    fn reset(&mut self);
+}
+
+impl<P: Plant + ?Sized> Plant for Box<P> {
+    fn step(&mut self, u: f32, dt: f32) -> f32 {
+        (**self).step(u, dt)
+    }
+
+    fn output(&self) -> f32 {
+        (**self).output()
+    }
+
+    fn reset(&mut self) {
+        (**self).reset()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModelError {
+    /// As matrizes do espaço de estados não têm tamanhos compatíveis.
+    Dimensions,
+    /// Denominador vazio ou com o primeiro coeficiente zero.
+    Denominator,
+    /// Numerador com grau maior que o denominador (sistema não causal).
+    Improper,
+}
+
+impl Display for ModelError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModelError::Dimensions => write!(f, "incompatible matrix dimensions"),
+            ModelError::Denominator => write!(f, "invalid denominator"),
+            ModelError::Improper => write!(f, "numerator degree greater than denominator"),
+        }
+    }
+}
+
+impl std::error::Error for ModelError {}
+
+/// Sistema de segunda ordem: `ÿ + 2ζωn ẏ + ωn² y = gain ωn² u`.
+///
+/// Com `zeta < 1` a resposta ao degrau oscila (subamortecido), com `zeta = 1` é a mais rápida
+/// sem sobressinal e com `zeta > 1` fica parecida com dois sistemas de primeira ordem em série.
+pub struct SecondOrder {
+    model: StateSpace,
+}
+
+impl SecondOrder {
+    /// `natural_frequency` em rad/s.
+    pub fn new(gain: f32, natural_frequency: f32, damping: f32) -> Self {
+        let wn = f64::from(natural_frequency);
+        let zeta = f64::from(damping);
+
+        let model = StateSpace::new(
+            Matrix::from_rows(&[[0.0, 1.0], [-wn * wn, -2.0 * zeta * wn]]),
+            Matrix::column(&[0.0, f64::from(gain) * wn * wn]),
+            Matrix::row(&[1.0, 0.0]),
+            Matrix::zeros(1, 1),
+        )
+        .expect("valid second order model");
+
+        Self { model }
+    }
+
+    pub fn with_method(self, method: Discretization) -> Self {
+        Self {
+            model: self.model.with_method(method),
+        }
+    }
+
+    pub fn model(&self) -> &StateSpace {
+        &self.model
+    }
+}
+
+impl Plant for SecondOrder {
+    fn step(&mut self, u: f32, dt: f32) -> f32 {
+        self.model.step(u, dt)
+    }
+
+    fn output(&self) -> f32 {
+        self.model.output()
+    }
+
+    fn reset(&mut self) {
+        self.model.reset()
+    }
+}
+
+/// Atraso puro (tempo morto) na entrada de outra planta, como o transporte de um fluido até o
+/// sensor.
+///
+/// O atraso é arredondado para um número inteiro de passos de simulação. Antes de passar o
+/// atraso a planta recebe entrada zero.
+pub struct DeadTime<P> {
+    plant: P,
+    delay: f32,
+    buffer: VecDeque<f32>,
+}
+
+impl<P: Plant> DeadTime<P> {
+    pub fn new(plant: P, delay: f32) -> Self {
+        Self {
+            plant,
+            delay,
+            buffer: VecDeque::new(),
+        }
+    }
+
+    pub fn delay(&self) -> f32 {
+        self.delay
+    }
+
+    pub fn inner(&self) -> &P {
+        &self.plant
+    }
+}
+
+impl<P: Plant> Plant for DeadTime<P> {
+    fn step(&mut self, u: f32, dt: f32) -> f32 {
+        let samples = (self.delay / dt).round() as usize;
+        self.buffer.push_back(u);
+
+        let delayed = if self.buffer.len() > samples {
+            self.buffer.pop_front().unwrap_or(0.0)
+        } else {
+            0.0
+        };
+
+        self.plant.step(delayed, dt)
+    }
+
+    fn output(&self) -> f32 {
+        self.plant.output()
+    }
+
+    fn reset(&mut self) {
+        self.buffer.clear();
+        self.plant.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physical::System;
+
+    const DT: f32 = 0.001;
+
+    fn step_response<P: Plant>(plant: &mut P, duration: f32) -> Vec<f32> {
+        let steps = (duration / DT).round() as usize;
+        (0..steps).map(|_| plant.step(1.0, DT)).collect()
+    }
+
+    /// Resposta ao degrau do sistema subamortecido:
+    /// `1 - e^(-ζωn t) (cos ωd t + ζ/√(1-ζ²) sin ωd t)`
+    #[test]
+    fn test_second_order_step_response() {
+        let (wn, zeta) = (10.0f32, 0.3f32);
+        let mut plant = SecondOrder::new(1.0, wn, zeta);
+        let response = step_response(&mut plant, 2.0);
+
+        let wd = wn * (1.0 - zeta * zeta).sqrt();
+        for (k, y) in response.iter().enumerate() {
+            let t = (k + 1) as f32 * DT;
+            let expected = 1.0
+                - (-zeta * wn * t).exp()
+                    * ((wd * t).cos() + zeta / (1.0 - zeta * zeta).sqrt() * (wd * t).sin());
+            assert!((y - expected).abs() < 1e-4, "t = {t}: {y} != {expected}");
+        }
+
+        // Sobressinal teórico: e^(-πζ/√(1-ζ²)) ≈ 37%
+        let peak = response.iter().copied().fold(0.0, f32::max);
+        assert!((peak - 1.372).abs() < 0.005);
+    }
+
+    #[test]
+    fn test_dead_time() {
+        let mut delayed = DeadTime::new(System::default(), 0.1);
+        let mut plant = System::default();
+
+        let delayed = step_response(&mut delayed, 0.5);
+        let direct = step_response(&mut plant, 0.5);
+
+        assert!(delayed[..100].iter().all(|&y| y == 0.0));
+        assert_eq!(delayed[100..], direct[..400]);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut plant = DeadTime::new(SecondOrder::new(2.0, 5.0, 0.7), 0.05);
+        step_response(&mut plant, 1.0);
+        assert!(plant.output() > 1.0);
+
+        plant.reset();
+        assert_eq!(plant.output(), 0.0);
+        assert_eq!(plant.step(1.0, DT), 0.0);
+    }
+
+    #[test]
+    fn test_boxed_plants() {
+        let mut plants: Vec<Box<dyn Plant>> = vec![
+            Box::new(System::default()),
+            Box::new(SecondOrder::new(1.0, 20.0, 1.0)),
+        ];
+
+        for plant in &mut plants {
+            let response = step_response(plant, 2.0);
+            assert!((response.last().unwrap() - 1.0).abs() < 1e-3);
+        }
+    }
+}
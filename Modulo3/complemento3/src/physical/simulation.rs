@@ -0,0 +1,164 @@
+//! Simulação em malha fechada com passo fixo.
+//!
+//! ```text
+//!  referência    erro   ┌─────────────┐  u   ┌─────────┐  saída
+//! ──────────►(+)──────► │ controlador │ ───► │ sistema │ ──┬──►
+//!             ▲ -       └─────────────┘      └─────────┘   │
+//!             └────────────────────────────────────────────┘
+//! ```
+
+use super::controller::Controller;
+use super::plant::Plant;
+use crate::signals::Wave;
+
+/// Séries geradas por uma simulação, todas com o mesmo tamanho, uma amostra por passo.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Run {
+    pub time: Vec<f32>,
+    pub setpoint: Vec<f32>,
+    pub output: Vec<f32>,
+    pub error: Vec<f32>,
+    pub control: Vec<f32>,
+}
+
+impl Run {
+    pub fn len(&self) -> usize {
+        self.time.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.time.is_empty()
+    }
+
+    /// Saída no fim da simulação.
+    pub fn final_output(&self) -> Option<f32> {
+        self.output.last().copied()
+    }
+
+    /// Maior valor da saída.
+    pub fn peak(&self) -> Option<f32> {
+        self.output.iter().copied().reduce(f32::max)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ClosedLoop {
+    /// Passo da simulação, que também é o período de amostragem do controlador, em segundos.
+    pub dt: f32,
+    pub duration: f32,
+}
+
+impl ClosedLoop {
+    pub fn new(dt: f32, duration: f32) -> Self {
+        Self { dt, duration }
+    }
+
+    /// Quantidade de passos da simulação.
+    pub fn steps(&self) -> usize {
+        (self.duration / self.dt).round() as usize + 1
+    }
+
+    /// Roda a simulação a partir do estado atual do sistema e do controlador.
+    ///
+    /// Em cada passo o controlador lê a saída atual do sistema e a entrada calculada fica
+    /// constante até o próximo passo, como em um controlador digital.
+    pub fn run<P: Plant + ?Sized, C: Controller, W: Wave>(
+        &self,
+        plant: &mut P,
+        controller: &mut C,
+        setpoint: &W,
+    ) -> Run {
+        let steps = self.steps();
+        let mut run = Run {
+            time: Vec::with_capacity(steps),
+            setpoint: Vec::with_capacity(steps),
+            output: Vec::with_capacity(steps),
+            error: Vec::with_capacity(steps),
+            control: Vec::with_capacity(steps),
+        };
+
+        for k in 0..steps {
+            let t = k as f32 * self.dt;
+            let reference = setpoint.compute(t);
+            let output = plant.output();
+            let u = controller.update(reference, output, self.dt);
+
+            run.time.push(t);
+            run.setpoint.push(reference);
+            run.output.push(output);
+            run.error.push(reference - output);
+            run.control.push(u);
+
+            plant.step(u, self.dt);
+        }
+
+        run
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physical::controller::{AntiWindup, Pid, P};
+    use crate::physical::System;
+    use crate::signals::step::Step;
+
+    const DT: f32 = 0.001;
+
+    fn run(controller: &mut Pid, duration: f32) -> Run {
+        ClosedLoop::new(DT, duration).run(&mut System::default(), controller, &Step::unit())
+    }
+
+    #[test]
+    fn test_series_are_aligned() {
+        let run = run(&mut Pid::p(P), 1.0);
+
+        assert_eq!(run.len(), 1001);
+        assert_eq!(run.time[1000], 1.0);
+        for k in 0..run.len() {
+            assert_eq!(run.error[k], run.setpoint[k] - run.output[k]);
+        }
+    }
+
+    /// Só com o proporcional sobra erro em regime: `1 / (1 + kp)` para ganho unitário.
+    #[test]
+    fn test_proportional_steady_state_error() {
+        for kp in [1.0, P, 20.0] {
+            let run = run(&mut Pid::p(kp), 2.0);
+            let expected = kp / (1.0 + kp);
+
+            assert!((run.final_output().unwrap() - expected).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_integral_removes_error() {
+        let run = run(&mut Pid::pi(P, 50.0), 2.0);
+
+        assert!((run.final_output().unwrap() - 1.0).abs() < 1e-3);
+    }
+
+    /// Com o atuador saturado, sem anti-windup o integrador acumula durante a subida e a saída
+    /// passa muito da referência.
+    #[test]
+    fn test_anti_windup_reduces_overshoot() {
+        let controller = Pid::pi(2.0, 40.0).with_limits(0.0, 1.2);
+
+        let windup = run(
+            &mut controller.clone().with_anti_windup(AntiWindup::None),
+            3.0,
+        );
+        let clamping = run(&mut controller.clone(), 3.0);
+        let back = run(
+            &mut controller.with_anti_windup(AntiWindup::BackCalculation { tracking: 0.05 }),
+            3.0,
+        );
+
+        assert!(windup.control.iter().all(|u| (0.0..=1.2).contains(u)));
+        let overshoot = |run: &Run| run.peak().unwrap() - 1.0;
+        assert!(overshoot(&windup) > 0.1, "{}", overshoot(&windup));
+        assert!(overshoot(&clamping) < overshoot(&windup) / 2.0);
+        assert!(overshoot(&back) < overshoot(&windup) / 2.0);
+        assert!((clamping.final_output().unwrap() - 1.0).abs() < 1e-3);
+    }
+}
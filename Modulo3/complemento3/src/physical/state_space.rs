@@ -0,0 +1,329 @@
+//! Modelos em espaço de estados e a conversão de contínuo para discreto.
+//!
+//! Contínuo:  `ẋ = A x + B u`,  `y = C x + D u`
+//!
+//! Discreto:  `x[k+1] = Ad x[k] + Bd u[k]`,  `y[k] = Cd x[k] + Dd u[k]`
+//!
+//! Só sistemas com uma entrada e uma saída: `B` é uma coluna, `C` uma linha e `D` 1×1.
+
+use super::matrix::Matrix;
+use super::plant::{ModelError, Plant};
+use super::transfer::DiscreteTransferFunction;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Discretization {
+    /// Segurador de ordem zero: exato quando a entrada é constante durante o passo, que é o
+    /// caso de um controlador digital acionando um DAC ou PWM.
+    #[default]
+    ZeroOrderHold,
+    /// Transformação bilinear (`s = 2/T (z - 1)/(z + 1)`). Preserva a estabilidade e é a
+    /// conversão usual para projetar filtros e controladores digitais.
+    Tustin,
+}
+
+fn check_dimensions(a: &Matrix, b: &Matrix, c: &Matrix, d: &Matrix) -> Result<(), ModelError> {
+    let n = a.rows();
+    let valid = a.is_square()
+        && (b.rows(), b.cols()) == (n, 1)
+        && (c.rows(), c.cols()) == (1, n)
+        && (d.rows(), d.cols()) == (1, 1);
+
+    if valid {
+        Ok(())
+    } else {
+        Err(ModelError::Dimensions)
+    }
+}
+
+/// Modelo contínuo.
+///
+/// Como [`Plant`], é discretizado com o método escolhido em [`StateSpace::with_method`] para o
+/// `dt` de cada passo. A discretização fica guardada enquanto `dt` não mudar.
+#[derive(Debug, Clone)]
+pub struct StateSpace {
+    a: Matrix,
+    b: Matrix,
+    c: Matrix,
+    d: Matrix,
+    method: Discretization,
+    discrete: Option<DiscreteStateSpace>,
+    // Estado fora da discretização, para quando ainda não houve nenhum passo
+    state: Matrix,
+}
+
+impl StateSpace {
+    pub fn new(a: Matrix, b: Matrix, c: Matrix, d: Matrix) -> Result<Self, ModelError> {
+        check_dimensions(&a, &b, &c, &d)?;
+        let n = a.rows();
+
+        Ok(Self {
+            a,
+            b,
+            c,
+            d,
+            method: Discretization::default(),
+            discrete: None,
+            state: Matrix::zeros(n, 1),
+        })
+    }
+
+    pub fn with_method(self, method: Discretization) -> Self {
+        Self {
+            method,
+            discrete: None,
+            ..self
+        }
+    }
+
+    /// Quantidade de estados.
+    pub fn order(&self) -> usize {
+        self.a.rows()
+    }
+
+    /// Ganho em regime para entrada constante: `D - C A⁻¹ B`. `None` se `A` for singular
+    /// (sistema com integrador).
+    pub fn dc_gain(&self) -> Option<f32> {
+        let inv = self.a.inverse()?;
+        let gain = self.d[(0, 0)] - (&(&self.c * &inv) * &self.b)[(0, 0)];
+        Some(gain as f32)
+    }
+
+    pub fn discretize(&self, dt: f32, method: Discretization) -> DiscreteStateSpace {
+        let n = self.order();
+        let t = f64::from(dt);
+
+        let (a, b, c, d) = match method {
+            Discretization::ZeroOrderHold => {
+                // exp([[A, B], [0, 0]] T) = [[Ad, Bd], [0, I]]
+                let mut augmented = Matrix::zeros(n + 1, n + 1);
+                augmented.set_block(0, 0, &self.a);
+                augmented.set_block(0, n, &self.b);
+                let exp = augmented.scale(t).exp();
+
+                (
+                    exp.block(0..n, 0..n),
+                    exp.block(0..n, n..n + 1),
+                    self.c.clone(),
+                    self.d.clone(),
+                )
+            }
+            Discretization::Tustin => {
+                let half = self.a.scale(t / 2.0);
+                let identity = Matrix::identity(n);
+                let m = (&identity - &half)
+                    .inverse()
+                    .expect("I - A T/2 singular: sample time too large for Tustin");
+                let mb = &m * &self.b;
+
+                (
+                    &m * &(&identity + &half),
+                    mb.scale(t),
+                    &self.c * &m,
+                    &self.d + &(&self.c * &mb).scale(t / 2.0),
+                )
+            }
+        };
+
+        DiscreteStateSpace {
+            a,
+            b,
+            c,
+            d,
+            dt,
+            state: Matrix::zeros(n, 1),
+            output: 0.0,
+        }
+    }
+}
+
+impl Plant for StateSpace {
+    fn step(&mut self, u: f32, dt: f32) -> f32 {
+        let outdated = self.discrete.as_ref().is_none_or(|d| d.dt != dt);
+        if outdated {
+            let mut discrete = self.discretize(dt, self.method);
+            if let Some(previous) = &self.discrete {
+                self.state = previous.state.clone();
+            }
+            // Com Tustin o estado discreto não é o mesmo que o contínuo, mas para trocar de
+            // `dt` no meio da simulação a aproximação é suficiente
+            discrete.state = self.state.clone();
+            self.discrete = Some(discrete);
+        }
+
+        self.discrete.as_mut().unwrap().step(u, dt)
+    }
+
+    fn output(&self) -> f32 {
+        self.discrete.as_ref().map_or(0.0, |d| d.output)
+    }
+
+    fn reset(&mut self) {
+        self.state = Matrix::zeros(self.order(), 1);
+        if let Some(discrete) = &mut self.discrete {
+            discrete.reset();
+        }
+    }
+}
+
+/// Modelo discreto, com período de amostragem `dt` fixo.
+#[derive(Debug, Clone)]
+pub struct DiscreteStateSpace {
+    a: Matrix,
+    b: Matrix,
+    c: Matrix,
+    d: Matrix,
+    dt: f32,
+    state: Matrix,
+    output: f32,
+}
+
+impl DiscreteStateSpace {
+    pub fn new(a: Matrix, b: Matrix, c: Matrix, d: Matrix, dt: f32) -> Result<Self, ModelError> {
+        check_dimensions(&a, &b, &c, &d)?;
+        let n = a.rows();
+
+        Ok(Self {
+            a,
+            b,
+            c,
+            d,
+            dt,
+            state: Matrix::zeros(n, 1),
+            output: 0.0,
+        })
+    }
+
+    pub fn matrices(&self) -> (&Matrix, &Matrix, &Matrix, &Matrix) {
+        (&self.a, &self.b, &self.c, &self.d)
+    }
+
+    pub fn sample_time(&self) -> f32 {
+        self.dt
+    }
+
+    /// Função de transferência equivalente: `C (zI - A)⁻¹ B + D`.
+    ///
+    /// O numerador sai de `C adj(zI - A) B = det(zI - A + BC) - det(zI - A)`, evitando
+    /// calcular a inversa simbolicamente.
+    pub fn to_transfer_function(&self) -> DiscreteTransferFunction {
+        let den = self.a.characteristic_polynomial();
+        let closed = (&self.a - &(&self.b * &self.c)).characteristic_polynomial();
+        let d = self.d[(0, 0)];
+
+        let num: Vec<f64> = closed
+            .iter()
+            .zip(&den)
+            .map(|(closed, den)| closed - den + d * den)
+            .collect();
+
+        DiscreteTransferFunction::new(&num, &den, self.dt).expect("monic denominator")
+    }
+}
+
+impl Plant for DiscreteStateSpace {
+    /// !!! `dt` tem que ser o período de amostragem do modelo.
+    fn step(&mut self, u: f32, dt: f32) -> f32 {
+        debug_assert!(
+            (dt - self.dt).abs() <= 1e-6 * self.dt,
+            "discrete model with sample time {} stepped with {dt}",
+            self.dt
+        );
+        let u = f64::from(u);
+
+        self.state = &(&self.a * &self.state) + &self.b.scale(u);
+        self.output = ((&self.c * &self.state)[(0, 0)] + self.d[(0, 0)] * u) as f32;
+
+        self.output
+    }
+
+    fn output(&self) -> f32 {
+        self.output
+    }
+
+    fn reset(&mut self) {
+        self.state = Matrix::zeros(self.a.rows(), 1);
+        self.output = 0.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physical::transfer::TransferFunction;
+    use crate::physical::System;
+
+    fn first_order(tau: f64) -> StateSpace {
+        StateSpace::new(
+            Matrix::from_rows(&[[-1.0 / tau]]),
+            Matrix::column(&[1.0 / tau]),
+            Matrix::row(&[1.0]),
+            Matrix::zeros(1, 1),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_dimensions() {
+        let result = StateSpace::new(
+            Matrix::identity(2),
+            Matrix::column(&[1.0]),
+            Matrix::row(&[1.0, 0.0]),
+            Matrix::zeros(1, 1),
+        );
+
+        assert_eq!(result.err(), Some(ModelError::Dimensions));
+    }
+
+    /// ZOH é exato para entrada constante: igual à solução analítica do `System`.
+    #[test]
+    fn test_zoh_matches_first_order() {
+        let dt = 0.01;
+        let mut model = first_order(0.1);
+        let mut system = System::default();
+
+        assert_eq!(model.dc_gain(), Some(1.0));
+        for _ in 0..100 {
+            let u = 1.0;
+            assert!((model.step(u, dt) - system.step(u, dt)).abs() < 1e-6);
+        }
+    }
+
+    /// Tustin de `1 / (τs + 1)`: `T (z + 1) / ((2τ + T) z + (T - 2τ))`.
+    #[test]
+    fn test_tustin_transfer_function() {
+        let (tau, dt) = (0.1, 0.02f32);
+        let discrete = first_order(tau).discretize(dt, Discretization::Tustin);
+        let dt = f64::from(dt);
+        let tf = discrete.to_transfer_function();
+
+        let a0 = 2.0 * tau + dt;
+        let expected_num = [dt / a0, dt / a0];
+        let expected_den = [1.0, (dt - 2.0 * tau) / a0];
+        for (got, expected) in tf.numerator().iter().zip(expected_num) {
+            assert!((got - expected).abs() < 1e-9, "{got} != {expected}");
+        }
+        for (got, expected) in tf.denominator().iter().zip(expected_den) {
+            assert!((got - expected).abs() < 1e-9, "{got} != {expected}");
+        }
+    }
+
+    /// Espaço de estados e função de transferência discretizados do mesmo sistema dão a mesma
+    /// resposta.
+    #[test]
+    fn test_state_space_and_transfer_function_agree() {
+        // 5 / (s² + 2s + 5)
+        let tf = TransferFunction::new(&[5.0], &[1.0, 2.0, 5.0]).unwrap();
+        let dt = 0.01;
+
+        for method in [Discretization::ZeroOrderHold, Discretization::Tustin] {
+            let mut ss = tf.to_state_space().discretize(dt, method);
+            let mut discrete_tf = tf.discretize(dt, method);
+
+            for k in 0..500 {
+                let u = if k < 250 { 1.0 } else { -0.5 };
+                let (a, b) = (ss.step(u, dt), discrete_tf.step(u, dt));
+                assert!((a - b).abs() < 1e-4, "{method:?} k = {k}: {a} != {b}");
+            }
+        }
+    }
+}
@@ -0,0 +1,285 @@
+//! Funções de transferência, com os coeficientes em potências decrescentes de `s` ou `z`.
+//!
+//! `(s + 2) / (s² + 3s + 2)` fica `TransferFunction::new(&[1.0, 2.0], &[1.0, 3.0, 2.0])`.
+
+use super::matrix::Matrix;
+use super::plant::{ModelError, Plant};
+use super::state_space::{Discretization, StateSpace};
+use std::collections::VecDeque;
+
+/// Tira os zeros à esquerda do numerador, confere o denominador e normaliza os dois para o
+/// denominador começar com 1. O numerador volta com o mesmo tamanho do denominador.
+fn normalize(num: &[f64], den: &[f64]) -> Result<(Vec<f64>, Vec<f64>), ModelError> {
+    let a0 = match den.first() {
+        Some(&a0) if a0 != 0.0 => a0,
+        _ => return Err(ModelError::Denominator),
+    };
+
+    let start = num.iter().position(|&b| b != 0.0).unwrap_or(num.len());
+    let num = &num[start..];
+    if num.len() > den.len() {
+        return Err(ModelError::Improper);
+    }
+
+    let mut padded = vec![0.0; den.len() - num.len()];
+    padded.extend(num.iter().map(|b| b / a0));
+
+    Ok((padded, den.iter().map(|a| a / a0).collect()))
+}
+
+/// Função de transferência contínua `num(s) / den(s)`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransferFunction {
+    num: Vec<f64>,
+    den: Vec<f64>,
+}
+
+impl TransferFunction {
+    pub fn new(num: &[f64], den: &[f64]) -> Result<Self, ModelError> {
+        let (num, den) = normalize(num, den)?;
+        Ok(Self { num, den })
+    }
+
+    /// Primeira ordem `gain / (tau s + 1)`, o mesmo modelo do [`System`](super::System).
+    pub fn first_order(tau: f64, gain: f64) -> Self {
+        Self::new(&[gain], &[tau, 1.0]).expect("valid first order model")
+    }
+
+    pub fn numerator(&self) -> &[f64] {
+        &self.num
+    }
+
+    pub fn denominator(&self) -> &[f64] {
+        &self.den
+    }
+
+    /// Ganho em regime para entrada constante (`s = 0`). `None` se houver integrador.
+    pub fn dc_gain(&self) -> Option<f32> {
+        let den = *self.den.last().unwrap();
+        (den != 0.0).then(|| (self.num.last().unwrap() / den) as f32)
+    }
+
+    /// Realização na forma canônica controlável.
+    pub fn to_state_space(&self) -> StateSpace {
+        let n = self.den.len() - 1;
+        let d = self.num[0];
+
+        let mut a = Matrix::zeros(n, n);
+        for j in 0..n {
+            a[(0, j)] = -self.den[j + 1];
+        }
+        for i in 1..n {
+            a[(i, i - 1)] = 1.0;
+        }
+
+        let mut b = Matrix::zeros(n, 1);
+        if n > 0 {
+            b[(0, 0)] = 1.0;
+        }
+
+        // Tira a parte direta (`d`) para o resto ficar estritamente próprio
+        let c: Vec<f64> = (1..=n).map(|i| self.num[i] - d * self.den[i]).collect();
+
+        StateSpace::new(a, b, Matrix::row(&c), Matrix::from_rows(&[[d]]))
+            .expect("controllable canonical form")
+    }
+
+    pub fn discretize(&self, dt: f32, method: Discretization) -> DiscreteTransferFunction {
+        self.to_state_space()
+            .discretize(dt, method)
+            .to_transfer_function()
+    }
+}
+
+/// Função de transferência discreta `num(z) / den(z)`, com período de amostragem `dt`.
+///
+/// Vira a equação de diferenças
+/// `y[k] = b0 u[k] + b1 u[k-1] + ... - a1 y[k-1] - a2 y[k-2] - ...`.
+///
+/// !!! Como [`Plant`], `step(u[k])` devolve a saída no fim do passo, `y[k+1]`, com a entrada
+/// mantida (`u[k+1] = u[k]`). É o que dá a discretização ZOH de uma planta contínua. Para
+/// usar os coeficientes de um filtro do firmware, onde `y[k]` sai junto com `u[k]`, a saída
+/// fica adiantada uma amostra.
+#[derive(Debug, Clone)]
+pub struct DiscreteTransferFunction {
+    num: Vec<f64>,
+    den: Vec<f64>,
+    dt: f32,
+    // Entradas anteriores, a mais recente primeiro
+    inputs: VecDeque<f64>,
+    // Saídas anteriores só da parte estritamente própria, `num / den - b0`. A parte direta
+    // `b0 u` é somada no fim: com a entrada mantida ela não pode entrar na realimentação
+    outputs: VecDeque<f64>,
+    output: f64,
+}
+
+impl DiscreteTransferFunction {
+    pub fn new(num: &[f64], den: &[f64], dt: f32) -> Result<Self, ModelError> {
+        let (num, den) = normalize(num, den)?;
+        let order = den.len() - 1;
+
+        Ok(Self {
+            num,
+            den,
+            dt,
+            inputs: VecDeque::from(vec![0.0; order]),
+            outputs: VecDeque::from(vec![0.0; order]),
+            output: 0.0,
+        })
+    }
+
+    pub fn numerator(&self) -> &[f64] {
+        &self.num
+    }
+
+    pub fn denominator(&self) -> &[f64] {
+        &self.den
+    }
+
+    pub fn sample_time(&self) -> f32 {
+        self.dt
+    }
+
+    /// Ganho em regime (`z = 1`). `None` se houver integrador.
+    pub fn dc_gain(&self) -> Option<f32> {
+        let den: f64 = self.den.iter().sum();
+        (den.abs() > 1e-12).then(|| (self.num.iter().sum::<f64>() / den) as f32)
+    }
+}
+
+impl Plant for DiscreteTransferFunction {
+    /// !!! `dt` tem que ser o período de amostragem do modelo.
+    fn step(&mut self, u: f32, dt: f32) -> f32 {
+        debug_assert!(
+            (dt - self.dt).abs() <= 1e-6 * self.dt,
+            "discrete model with sample time {} stepped with {dt}",
+            self.dt
+        );
+        let u = f64::from(u);
+
+        if !self.inputs.is_empty() {
+            self.inputs.pop_back();
+            self.inputs.push_front(u);
+        }
+
+        let b0 = self.num[0];
+        let forward: f64 = self.num[1..]
+            .iter()
+            .zip(&self.den[1..])
+            .zip(&self.inputs)
+            .map(|((b, a), x)| (b - b0 * a) * x)
+            .sum();
+        let feedback: f64 = self.den[1..]
+            .iter()
+            .zip(&self.outputs)
+            .map(|(a, y)| a * y)
+            .sum();
+        let strict = forward - feedback;
+
+        if !self.outputs.is_empty() {
+            self.outputs.pop_back();
+            self.outputs.push_front(strict);
+        }
+        self.output = strict + b0 * u;
+        let y = self.output;
+
+        y as f32
+    }
+
+    fn output(&self) -> f32 {
+        self.output as f32
+    }
+
+    fn reset(&mut self) {
+        self.inputs.iter_mut().for_each(|x| *x = 0.0);
+        self.outputs.iter_mut().for_each(|y| *y = 0.0);
+        self.output = 0.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physical::System;
+
+    #[test]
+    fn test_invalid_coefficients() {
+        assert_eq!(
+            TransferFunction::new(&[1.0], &[0.0, 1.0]),
+            Err(ModelError::Denominator)
+        );
+        assert_eq!(
+            TransferFunction::new(&[1.0, 0.0, 0.0], &[1.0, 1.0]),
+            Err(ModelError::Improper)
+        );
+        // Zeros à esquerda do numerador não contam no grau
+        assert!(TransferFunction::new(&[0.0, 0.0, 2.0], &[1.0, 1.0]).is_ok());
+    }
+
+    /// Com ZOH, `1 / (τs + 1)` vira `(1 - a) / (z - a)` com `a = e^(-T/τ)`, igual ao `System`.
+    #[test]
+    fn test_zoh_first_order() {
+        let (tau, dt) = (0.1, 0.01f32);
+        let mut tf =
+            TransferFunction::first_order(tau, 1.0).discretize(dt, Discretization::ZeroOrderHold);
+        let a = (-f64::from(dt) / tau).exp();
+
+        assert!((tf.denominator()[1] + a).abs() < 1e-9);
+        assert!((tf.numerator()[1] - (1.0 - a)).abs() < 1e-9);
+        assert!(tf.numerator()[0].abs() < 1e-9);
+
+        let mut system = System::new(tau as f32, 1.0);
+        for _ in 0..100 {
+            assert!((tf.step(1.0, dt) - system.step(1.0, dt)).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_dc_gain() {
+        let tf = TransferFunction::new(&[3.0, 6.0], &[1.0, 3.0, 2.0]).unwrap();
+        assert_eq!(tf.dc_gain(), Some(3.0));
+        assert_eq!(tf.to_state_space().dc_gain(), Some(3.0));
+
+        for method in [Discretization::ZeroOrderHold, Discretization::Tustin] {
+            let gain = tf.discretize(0.05, method).dc_gain().unwrap();
+            assert!((gain - 3.0).abs() < 1e-4, "{method:?}: {gain}");
+        }
+
+        // Integrador puro
+        assert_eq!(
+            TransferFunction::new(&[1.0], &[1.0, 0.0])
+                .unwrap()
+                .dc_gain(),
+            None
+        );
+    }
+
+    /// `1 / (z - 0.5)`: `y[k+1] = 0.5 y[k] + u[k]`.
+    #[test]
+    fn test_difference_equation() {
+        let mut tf = DiscreteTransferFunction::new(&[1.0], &[1.0, -0.5], 1.0).unwrap();
+        let outputs: Vec<f32> = [1.0, 1.0, 1.0, 0.0]
+            .iter()
+            .map(|&u| tf.step(u, 1.0))
+            .collect();
+        assert_eq!(outputs, [1.0, 1.5, 1.75, 0.875]);
+
+        tf.reset();
+        assert_eq!(tf.output(), 0.0);
+        assert_eq!(tf.step(2.0, 1.0), 2.0);
+    }
+
+    /// A parte direta usa a entrada mantida e não entra na realimentação.
+    #[test]
+    fn test_direct_feedthrough() {
+        // (z + 1) / z: y[k+1] = u[k+1] + u[k], que com a entrada mantida fica 2 u[k]
+        let mut tf = DiscreteTransferFunction::new(&[1.0, 1.0], &[1.0, 0.0], 1.0).unwrap();
+        let outputs: Vec<f32> = [2.0, 4.0, 0.0].iter().map(|&u| tf.step(u, 1.0)).collect();
+        assert_eq!(outputs, [4.0, 8.0, 0.0]);
+
+        // Ganho puro
+        let mut gain = DiscreteTransferFunction::new(&[3.0], &[2.0], 1.0).unwrap();
+        assert_eq!(gain.step(2.0, 1.0), 3.0);
+        assert_eq!(gain.output(), 3.0);
+    }
+}
@@ -0,0 +1,297 @@
+//! Sintonia automática de PID a partir do ponto crítico da planta.
+//!
+//! O ponto crítico é o ganho `Ku` que, com um controlador só proporcional, deixa a malha
+//! oscilando com amplitude constante, e o período `Tu` dessa oscilação. Há dois jeitos de
+//! achá-lo:
+//!
+//! - [`ultimate_gain`]: aumenta o ganho até a malha ficar no limite da estabilidade, como no
+//!   método original de Ziegler–Nichols. Na planta real é arriscado.
+//! - [`Relay`]: troca o controlador por um relé (Åström–Hägglund). A oscilação fica limitada
+//!   pela amplitude do relé e `Ku` sai da função descritiva, `4d / (πa)`.
+//!
+//! Com `Ku` e `Tu`, [`Ultimate::pid`] aplica as regras de Ziegler–Nichols.
+
+use super::controller::{Controller, Pid};
+use super::plant::Plant;
+use super::simulation::{ClosedLoop, Run};
+use crate::signals::combinators::Constant;
+use crate::signals::step::Step;
+use std::f32::consts::PI;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rule {
+    P,
+    Pi,
+    Pid,
+}
+
+/// Ponto crítico da malha.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ultimate {
+    /// Ganho crítico `Ku`.
+    pub gain: f32,
+    /// Período de oscilação `Tu`, em segundos.
+    pub period: f32,
+}
+
+impl Ultimate {
+    /// Regras de Ziegler–Nichols em malha fechada.
+    ///
+    /// | regra | kp        | Ti       | Td       |
+    /// |-------|-----------|----------|----------|
+    /// | P     | 0,5 Ku    |          |          |
+    /// | PI    | 0,45 Ku   | Tu / 1,2 |          |
+    /// | PID   | 0,6 Ku    | Tu / 2   | Tu / 8   |
+    pub fn pid(&self, rule: Rule) -> Pid {
+        let (ku, tu) = (self.gain, self.period);
+
+        match rule {
+            Rule::P => Pid::p(0.5 * ku),
+            Rule::Pi => {
+                let kp = 0.45 * ku;
+                Pid::pi(kp, kp / (tu / 1.2))
+            }
+            Rule::Pid => {
+                let kp = 0.6 * ku;
+                Pid::new(kp, kp / (tu / 2.0), kp * tu / 8.0)
+            }
+        }
+    }
+}
+
+/// Amplitude (metade do pico a pico) e período da oscilação na segunda metade da série.
+///
+/// O período é a distância média entre as passagens da saída pela média, subindo. `None` se
+/// houver menos de duas passagens.
+fn oscillation(output: &[f32], dt: f32) -> Option<(f32, f32)> {
+    let tail = &output[output.len() / 2..];
+    let mean = tail.iter().sum::<f32>() / tail.len() as f32;
+    let (min, max) = tail
+        .iter()
+        .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), &y| {
+            (min.min(y), max.max(y))
+        });
+
+    let crossings: Vec<usize> = tail
+        .windows(2)
+        .enumerate()
+        .filter(|(_, pair)| pair[0] < mean && pair[1] >= mean)
+        .map(|(k, _)| k)
+        .collect();
+    let (first, last) = (crossings.first()?, crossings.last()?);
+    if first == last {
+        return None;
+    }
+
+    let period = (last - first) as f32 * dt / (crossings.len() - 1) as f32;
+    Some(((max - min) / 2.0, period))
+}
+
+/// Procura o ganho crítico por bisseção em `(0, max_gain]`, simulando a resposta ao degrau com
+/// um controlador P.
+///
+/// A malha é considerada instável quando a oscilação no último terço da simulação é maior que
+/// no terço do meio. `simulation.duration` precisa cobrir vários períodos da oscilação.
+/// Devolve `None` se a malha continuar estável com `max_gain` (plantas de primeira e segunda
+/// ordem, por exemplo, nunca ficam instáveis).
+///
+/// A planta é zerada antes de cada simulação.
+pub fn ultimate_gain<P: Plant + ?Sized>(
+    plant: &mut P,
+    simulation: &ClosedLoop,
+    max_gain: f32,
+) -> Option<Ultimate> {
+    let mut simulate = |kp: f32| {
+        plant.reset();
+        simulation.run(plant, &mut Pid::p(kp), &Step::unit())
+    };
+    let unstable = |run: &Run| {
+        if run.output.iter().any(|y| !y.is_finite()) {
+            return true;
+        }
+        let third = run.len() / 3;
+        let amplitude = |window: &[f32]| {
+            let (min, max) = window
+                .iter()
+                .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), &y| {
+                    (min.min(y), max.max(y))
+                });
+            max - min
+        };
+        amplitude(&run.output[2 * third..]) > amplitude(&run.output[third..2 * third])
+    };
+
+    if !unstable(&simulate(max_gain)) {
+        return None;
+    }
+
+    let (mut stable, mut diverging) = (0.0, max_gain);
+    while diverging - stable > 1e-3 * diverging {
+        let kp = (stable + diverging) / 2.0;
+        if unstable(&simulate(kp)) {
+            diverging = kp;
+        } else {
+            stable = kp;
+        }
+    }
+
+    let gain = (stable + diverging) / 2.0;
+    let (_, period) = oscillation(&simulate(gain).output, simulation.dt)?;
+    plant.reset();
+
+    Some(Ultimate { gain, period })
+}
+
+/// Relé com histerese, usado no lugar do controlador para provocar uma oscilação controlada.
+#[derive(Debug, Clone)]
+pub struct Relay {
+    amplitude: f32,
+    hysteresis: f32,
+    high: bool,
+}
+
+impl Relay {
+    /// Saída `±amplitude` em torno de zero.
+    pub fn new(amplitude: f32) -> Self {
+        Self {
+            amplitude,
+            hysteresis: 0.0,
+            high: true,
+        }
+    }
+
+    /// O relé só troca de lado quando o erro passa de `±hysteresis`, para o ruído da medida
+    /// não fazer a saída ficar trocando.
+    pub fn with_hysteresis(self, hysteresis: f32) -> Self {
+        Self { hysteresis, ..self }
+    }
+
+    /// Roda o experimento em volta de referência zero e estima o ponto crítico pela
+    /// oscilação na segunda metade da simulação.
+    ///
+    /// A planta é zerada antes e depois. `None` se a saída não chegar a oscilar.
+    pub fn tune<P: Plant + ?Sized>(
+        &mut self,
+        plant: &mut P,
+        simulation: &ClosedLoop,
+    ) -> Option<Ultimate> {
+        plant.reset();
+        self.reset();
+        let run = simulation.run(plant, self, &Constant(0.0));
+        plant.reset();
+
+        let (amplitude, period) = oscillation(&run.output, simulation.dt)?;
+        // Com histerese o relé troca atrasado, o que a função descritiva compensa assim
+        let effective = (amplitude * amplitude - self.hysteresis * self.hysteresis).sqrt();
+        let gain = 4.0 * self.amplitude / (PI * effective);
+
+        Some(Ultimate { gain, period })
+    }
+}
+
+impl Controller for Relay {
+    fn update(&mut self, setpoint: f32, measurement: f32, _dt: f32) -> f32 {
+        let error = setpoint - measurement;
+        if error > self.hysteresis {
+            self.high = true;
+        } else if error < -self.hysteresis {
+            self.high = false;
+        }
+
+        if self.high {
+            self.amplitude
+        } else {
+            -self.amplitude
+        }
+    }
+
+    fn reset(&mut self) {
+        self.high = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physical::metrics::Metrics;
+    use crate::physical::state_space::StateSpace;
+    use crate::physical::transfer::TransferFunction;
+    use crate::physical::System;
+
+    const DT: f32 = 0.01;
+
+    /// `1 / (s + 1)³`: fase de -180° em `ω = √3`, onde o ganho é 1/8. Então `Ku = 8` e
+    /// `Tu = 2π/√3 ≈ 3,63 s`.
+    fn third_order() -> StateSpace {
+        TransferFunction::new(&[1.0], &[1.0, 3.0, 3.0, 1.0])
+            .unwrap()
+            .to_state_space()
+    }
+
+    const KU: f32 = 8.0;
+    const TU: f32 = 2.0 * PI / 1.732_050_8;
+
+    #[test]
+    fn test_ultimate_gain() {
+        let ultimate = ultimate_gain(&mut third_order(), &ClosedLoop::new(DT, 60.0), 50.0).unwrap();
+
+        // O controlador discreto lê a saída um passo atrasada, o que baixa um pouco o ganho
+        // crítico
+        assert!((ultimate.gain - KU).abs() < 0.2, "{ultimate:?}");
+        assert!((ultimate.period - TU).abs() < 0.05, "{ultimate:?}");
+    }
+
+    #[test]
+    fn test_stable_plant_has_no_ultimate_gain() {
+        assert_eq!(
+            ultimate_gain(&mut System::default(), &ClosedLoop::new(0.001, 2.0), 100.0),
+            None
+        );
+    }
+
+    /// A função descritiva considera só a harmônica fundamental da onda quadrada do relé, então
+    /// o `Ku` estimado erra em uns 10% nessa planta.
+    #[test]
+    fn test_relay() {
+        let simulation = ClosedLoop::new(DT, 60.0);
+        let mut plant = third_order();
+
+        for hysteresis in [0.0, 0.01] {
+            let ultimate = Relay::new(1.0)
+                .with_hysteresis(hysteresis)
+                .tune(&mut plant, &simulation)
+                .unwrap();
+
+            assert!((ultimate.gain - KU).abs() < 0.15 * KU, "{ultimate:?}");
+            assert!((ultimate.period - TU).abs() < 0.1 * TU, "{ultimate:?}");
+        }
+        assert_eq!(plant.output(), 0.0);
+    }
+
+    /// A sintonia de Ziegler–Nichols zera o erro em regime e fica estável, com o sobressinal
+    /// alto típico do método.
+    #[test]
+    fn test_ziegler_nichols_pid() {
+        let ultimate = Relay::new(1.0)
+            .tune(&mut third_order(), &ClosedLoop::new(DT, 60.0))
+            .unwrap();
+        let simulation = ClosedLoop::new(DT, 40.0);
+
+        let p = simulation.run(
+            &mut third_order(),
+            &mut ultimate.pid(Rule::P),
+            &Step::unit(),
+        );
+        let pid = simulation.run(
+            &mut third_order(),
+            &mut ultimate.pid(Rule::Pid),
+            &Step::unit(),
+        );
+        let (p, pid) = (Metrics::new(&p), Metrics::new(&pid));
+
+        assert!(p.steady_state_error > 0.1);
+        assert!(pid.steady_state_error.abs() < 1e-3, "{pid:?}");
+        assert!(pid.overshoot > 10.0 && pid.overshoot < 80.0, "{pid:?}");
+        assert!(pid.settling_time_2.unwrap() < 30.0);
+    }
+}
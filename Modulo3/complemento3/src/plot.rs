@@ -0,0 +1,413 @@
+//! Gráficos de várias séries `(t, y)`, em PNG ou SVG.
+//!
+//! Uma [`Figure`] tem um ou mais [`Panel`]s empilhados, que compartilham o eixo x; cada painel
+//! tem as suas [`Series`]. Os limites dos eixos são calculados a partir dos dados, a não ser
+//! que sejam definidos.
+//!
+//! ```ignore
+//! Figure::new()
+//!     .caption("P = 5")
+//!     .panel(
+//!         Panel::new()
+//!             .y_label("saída")
+//!             .series(Series::from_xy("referência", &run.time, &run.setpoint))
+//!             .series(Series::from_xy("saída", &run.time, &run.output)),
+//!     )
+//!     .panel(
+//!         Panel::new()
+//!             .x_label("t (s)")
+//!             .series(Series::from_xy("u", &run.time, &run.control)),
+//!     )
+//!     .save("closed_loop.svg")?;
+//! ```
+
+use crate::signals::{Sampler, Wave};
+use plotters::coord::Shift;
+use plotters::prelude::*;
+use std::error::Error;
+use std::ops::Range;
+use std::path::Path;
+
+/// Cores usadas, em ordem, nas séries sem cor definida.
+pub const PALETTE: [RGBColor; 6] = [
+    RED,
+    BLUE,
+    RGBColor(0, 150, 0),
+    MAGENTA,
+    RGBColor(255, 140, 0),
+    BLACK,
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Series {
+    name: String,
+    points: Vec<(f32, f32)>,
+    color: Option<RGBColor>,
+    width: u32,
+}
+
+impl Series {
+    pub fn new(name: &str, points: Vec<(f32, f32)>) -> Self {
+        Self {
+            name: name.to_string(),
+            points,
+            color: None,
+            width: 1,
+        }
+    }
+
+    /// Junta os vetores de x e de y, como os de um [`Run`](crate::physical::simulation::Run).
+    pub fn from_xy(name: &str, x: &[f32], y: &[f32]) -> Self {
+        Self::new(name, x.iter().copied().zip(y.iter().copied()).collect())
+    }
+
+    /// `samples` pontos de `wave` igualmente espaçados em `[start, end]`.
+    pub fn from_wave<W: Wave + ?Sized>(
+        name: &str,
+        wave: &W,
+        (start, end): (f32, f32),
+        samples: usize,
+    ) -> Self {
+        Self::new(
+            name,
+            Sampler::over(start, end, samples).sample(wave).collect(),
+        )
+    }
+
+    pub fn with_color(self, color: RGBColor) -> Self {
+        Self {
+            color: Some(color),
+            ..self
+        }
+    }
+
+    /// Espessura da linha, em pixels.
+    pub fn with_width(self, width: u32) -> Self {
+        Self { width, ..self }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn points(&self) -> &[(f32, f32)] {
+        &self.points
+    }
+}
+
+/// Um gráfico dentro da figura.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Panel {
+    title: Option<String>,
+    x_label: Option<String>,
+    y_label: Option<String>,
+    y_range: Option<(f32, f32)>,
+    series: Vec<Series>,
+}
+
+impl Panel {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn title(self, title: &str) -> Self {
+        Self {
+            title: Some(title.to_string()),
+            ..self
+        }
+    }
+
+    pub fn x_label(self, label: &str) -> Self {
+        Self {
+            x_label: Some(label.to_string()),
+            ..self
+        }
+    }
+
+    pub fn y_label(self, label: &str) -> Self {
+        Self {
+            y_label: Some(label.to_string()),
+            ..self
+        }
+    }
+
+    /// Limites fixos do eixo y, em vez de calcular pelos dados.
+    pub fn y_range(self, min: f32, max: f32) -> Self {
+        Self {
+            y_range: Some((min, max)),
+            ..self
+        }
+    }
+
+    pub fn series(mut self, series: Series) -> Self {
+        self.series.push(series);
+        self
+    }
+
+    /// Limites do eixo y: os definidos em [`Panel::y_range`] ou os dos dados com 5% de folga.
+    pub fn y_limits(&self) -> Range<f32> {
+        match self.y_range {
+            Some((min, max)) => min..max,
+            None => autoscale(self.values(|&(_, y)| y), 0.05),
+        }
+    }
+
+    fn values<'a>(&'a self, f: impl Fn(&(f32, f32)) -> f32 + 'a) -> impl Iterator<Item = f32> + 'a {
+        self.series
+            .iter()
+            .flat_map(|series| series.points.iter())
+            .map(f)
+    }
+}
+
+/// Limites que cobrem todos os `values` finitos, com `margin` (fração do intervalo) de cada
+/// lado. Um intervalo vazio vira `[v - 1, v + 1]`, e sem valores fica `[0, 1]`.
+fn autoscale(values: impl Iterator<Item = f32>, margin: f32) -> Range<f32> {
+    let (min, max) = values
+        .filter(|v| v.is_finite())
+        .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), v| {
+            (min.min(v), max.max(v))
+        });
+
+    if min > max {
+        0.0..1.0
+    } else if min == max {
+        min - 1.0..max + 1.0
+    } else {
+        let margin = (max - min) * margin;
+        min - margin..max + margin
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Png,
+    Svg,
+}
+
+impl Format {
+    /// Pela extensão do arquivo.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "png" => Some(Format::Png),
+            "svg" => Some(Format::Svg),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Figure {
+    size: (u32, u32),
+    caption: Option<String>,
+    x_range: Option<(f32, f32)>,
+    panels: Vec<Panel>,
+}
+
+impl Default for Figure {
+    fn default() -> Self {
+        Self {
+            size: (640, 480),
+            caption: None,
+            x_range: None,
+            panels: Vec::new(),
+        }
+    }
+}
+
+impl Figure {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Largura e altura, em pixels.
+    pub fn size(self, width: u32, height: u32) -> Self {
+        Self {
+            size: (width, height),
+            ..self
+        }
+    }
+
+    /// Título da figura, acima de todos os painéis.
+    pub fn caption(self, caption: &str) -> Self {
+        Self {
+            caption: Some(caption.to_string()),
+            ..self
+        }
+    }
+
+    /// Limites fixos do eixo x, em vez de calcular pelos dados.
+    pub fn x_range(self, min: f32, max: f32) -> Self {
+        Self {
+            x_range: Some((min, max)),
+            ..self
+        }
+    }
+
+    /// Adiciona um painel embaixo dos anteriores.
+    pub fn panel(mut self, panel: Panel) -> Self {
+        self.panels.push(panel);
+        self
+    }
+
+    /// Limites do eixo x, comuns a todos os painéis. Sem folga: o gráfico começa e termina
+    /// nos dados.
+    pub fn x_limits(&self) -> Range<f32> {
+        match self.x_range {
+            Some((min, max)) => min..max,
+            None => autoscale(self.panels.iter().flat_map(|p| p.values(|&(x, _)| x)), 0.0),
+        }
+    }
+
+    /// Salva no formato indicado pela extensão de `path` (`.png` ou `.svg`).
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), Box<dyn Error>> {
+        let path = path.as_ref();
+        let format = Format::from_path(path)
+            .ok_or_else(|| format!("unsupported plot format: {}", path.display()))?;
+
+        self.save_as(path, format)
+    }
+
+    pub fn save_as<P: AsRef<Path>>(&self, path: P, format: Format) -> Result<(), Box<dyn Error>> {
+        match format {
+            Format::Png => {
+                self.draw(BitMapBackend::new(path.as_ref(), self.size).into_drawing_area())
+            }
+            Format::Svg => self.draw(SVGBackend::new(path.as_ref(), self.size).into_drawing_area()),
+        }
+    }
+
+    fn draw<DB: DrawingBackend>(&self, root: DrawingArea<DB, Shift>) -> Result<(), Box<dyn Error>>
+    where
+        DB::ErrorType: 'static,
+    {
+        root.fill(&WHITE)?;
+        let area = match &self.caption {
+            Some(caption) => root.titled(caption, ("sans-serif", 30))?,
+            None => root.clone(),
+        };
+        let x = self.x_limits();
+
+        for (panel, area) in self
+            .panels
+            .iter()
+            .zip(area.split_evenly((self.panels.len().max(1), 1)))
+        {
+            let mut builder = ChartBuilder::on(&area);
+            builder
+                .margin(10)
+                .x_label_area_size(35)
+                .y_label_area_size(50);
+            if let Some(title) = &panel.title {
+                builder.caption(title, ("sans-serif", 20));
+            }
+            let mut chart = builder.build_cartesian_2d(x.clone(), panel.y_limits())?;
+
+            let mut mesh = chart.configure_mesh();
+            if let Some(label) = &panel.x_label {
+                mesh.x_desc(label);
+            }
+            if let Some(label) = &panel.y_label {
+                mesh.y_desc(label);
+            }
+            mesh.draw()?;
+
+            for (i, series) in panel.series.iter().enumerate() {
+                let color = series.color.unwrap_or(PALETTE[i % PALETTE.len()]);
+                chart
+                    .draw_series(LineSeries::new(
+                        series.points.iter().copied(),
+                        color.stroke_width(series.width),
+                    ))?
+                    .label(&series.name)
+                    .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color));
+            }
+
+            if panel.series.iter().any(|series| !series.name.is_empty()) {
+                chart
+                    .configure_series_labels()
+                    .background_style(WHITE.mix(0.8))
+                    .border_style(BLACK)
+                    .draw()?;
+            }
+        }
+
+        root.present()?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signals::sine::Sine;
+
+    #[test]
+    fn test_autoscale() {
+        assert_eq!(autoscale([1.0, -1.0, 3.0].into_iter(), 0.0), -1.0..3.0);
+        assert_eq!(autoscale([0.0, 10.0].into_iter(), 0.05), -0.5..10.5);
+        assert_eq!(autoscale([2.0, 2.0].into_iter(), 0.05), 1.0..3.0);
+        assert_eq!(autoscale([f32::NAN, 1.0, 3.0].into_iter(), 0.0), 1.0..3.0);
+        assert_eq!(autoscale(std::iter::empty(), 0.05), 0.0..1.0);
+    }
+
+    #[test]
+    fn test_limits() {
+        let figure = Figure::new()
+            .panel(Panel::new().series(Series::new("a", vec![(0.0, 0.0), (2.0, 20.0)])))
+            .panel(
+                Panel::new()
+                    .y_range(-5.0, 5.0)
+                    .series(Series::new("b", vec![(-1.0, 0.0), (1.0, 0.0)])),
+            );
+
+        // O eixo x é comum e cobre as séries de todos os painéis
+        assert_eq!(figure.x_limits(), -1.0..2.0);
+        assert_eq!(figure.panels[0].y_limits(), -1.0..21.0);
+        assert_eq!(figure.panels[1].y_limits(), -5.0..5.0);
+        assert_eq!(figure.clone().x_range(0.0, 1.0).x_limits(), 0.0..1.0);
+    }
+
+    #[test]
+    fn test_from_wave() {
+        let series = Series::from_wave("sin", &Sine::new(1.0, 1.0, 0.0), (0.0, 1.0), 5);
+
+        assert_eq!(series.points().len(), 5);
+        assert_eq!(series.points()[2].0, 0.5);
+        assert!((series.points()[1].1 - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_save() {
+        let dir = std::env::temp_dir();
+        let figure = Figure::new()
+            .size(400, 300)
+            .caption("teste")
+            .panel(
+                Panel::new()
+                    .y_label("y")
+                    .series(Series::new("subida", vec![(0.0, 0.0), (1.0, 1.0)]))
+                    .series(Series::new("descida", vec![(0.0, 1.0), (1.0, 0.0)]).with_color(BLACK)),
+            )
+            .panel(
+                Panel::new()
+                    .x_label("t (s)")
+                    .series(Series::new("", vec![(0.0, 0.5)])),
+            );
+
+        let svg = dir.join("complemento3_plot_test.svg");
+        figure.save(&svg).unwrap();
+        let content = std::fs::read_to_string(&svg).unwrap();
+        assert!(
+            content.contains("<svg") && content.contains("subida") && content.contains("t (s)")
+        );
+
+        let png = dir.join("complemento3_plot_test.png");
+        figure.save(&png).unwrap();
+        assert_eq!(&std::fs::read(&png).unwrap()[1..4], b"PNG");
+
+        assert!(figure.save(dir.join("complemento3_plot_test.jpg")).is_err());
+    }
+}
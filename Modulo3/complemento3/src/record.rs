@@ -0,0 +1,472 @@
+//! Gravação de séries amostradas em CSV ou JSON, com metadados, e leitura de volta.
+//!
+//! Serve para comparar com resultados do MATLAB/Python e para usar medidas reais de um sensor
+//! como entrada das simulações, via [`Recording::replay`].
+//!
+//! O CSV tem os metadados em comentários no começo, o cabeçalho com as unidades entre
+//! colchetes e a primeira coluna sempre é o tempo, em segundos:
+//!
+//! ```text
+//! # name: closed loop
+//! # sample_rate: 1000
+//! # kp: 5
+//! t,setpoint,output [V],control
+//! 0,1,0,5
+//! 0.001,1,0.04877058,4.756147
+//! ```
+
+use crate::physical::simulation::Run;
+use crate::plot::Series;
+use crate::signals::{Interpolation, Replay, Sampler, Wave};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+#[derive(Debug)]
+pub enum RecordError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    /// Erro no CSV, com o número da linha (começando em 1).
+    Parse {
+        line: usize,
+        message: String,
+    },
+    /// Coluna com quantidade de valores diferente da de tempos.
+    Length {
+        column: String,
+    },
+    /// Extensão de arquivo que não é `.csv` nem `.json`.
+    Format(String),
+}
+
+impl Display for RecordError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RecordError::Io(error) => write!(f, "{error}"),
+            RecordError::Json(error) => write!(f, "{error}"),
+            RecordError::Parse { line, message } => write!(f, "line {line}: {message}"),
+            RecordError::Length { column } => {
+                write!(f, "column {column} doesn't have one value per sample")
+            }
+            RecordError::Format(path) => write!(f, "unsupported record format: {path}"),
+        }
+    }
+}
+
+impl std::error::Error for RecordError {}
+
+impl From<std::io::Error> for RecordError {
+    fn from(error: std::io::Error) -> Self {
+        RecordError::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for RecordError {
+    fn from(error: serde_json::Error) -> Self {
+        RecordError::Json(error)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Metadata {
+    pub name: String,
+    /// Taxa de amostragem em Hz, se as amostras forem igualmente espaçadas.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sample_rate: Option<f32>,
+    /// Parâmetros da simulação ou do experimento, como ganhos e constantes de tempo.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub parameters: BTreeMap<String, f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Column {
+    /// !!! Sem vírgulas nem colchetes, que quebram o cabeçalho do CSV.
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+    pub values: Vec<f32>,
+}
+
+/// Várias séries amostradas nos mesmos instantes.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Recording {
+    pub metadata: Metadata,
+    pub time: Vec<f32>,
+    pub columns: Vec<Column>,
+}
+
+impl Recording {
+    pub fn new(name: &str, time: Vec<f32>) -> Self {
+        Self {
+            metadata: Metadata {
+                name: name.to_string(),
+                ..Metadata::default()
+            },
+            time,
+            columns: Vec::new(),
+        }
+    }
+
+    /// Amostra `wave` de `0` a `duration` segundos, incluindo as duas pontas, numa coluna com
+    /// o mesmo nome da gravação.
+    pub fn from_wave<W: Wave + ?Sized>(
+        name: &str,
+        wave: &W,
+        sample_rate: f32,
+        duration: f32,
+    ) -> Self {
+        let samples = Sampler::new(sample_rate, duration).samples(wave);
+
+        let mut recording = Self::new(name, samples.time).with_column(name, samples.values);
+        recording.metadata.sample_rate = Some(sample_rate);
+        recording
+    }
+
+    /// Todas as séries de uma simulação em malha fechada.
+    pub fn from_run(name: &str, run: &Run) -> Self {
+        let mut recording = Self::new(name, run.time.clone())
+            .with_column("setpoint", run.setpoint.clone())
+            .with_column("output", run.output.clone())
+            .with_column("error", run.error.clone())
+            .with_column("control", run.control.clone());
+
+        if let [first, second, ..] = run.time[..] {
+            recording.metadata.sample_rate = Some(1.0 / (second - first));
+        }
+        recording
+    }
+
+    pub fn with_column(mut self, name: &str, values: Vec<f32>) -> Self {
+        self.columns.push(Column {
+            name: name.to_string(),
+            unit: None,
+            values,
+        });
+        self
+    }
+
+    /// Unidade da coluna `column`. Não faz nada se a coluna não existir.
+    pub fn with_unit(mut self, column: &str, unit: &str) -> Self {
+        if let Some(column) = self.columns.iter_mut().find(|c| c.name == column) {
+            column.unit = Some(unit.to_string());
+        }
+        self
+    }
+
+    pub fn with_parameter(mut self, name: &str, value: f64) -> Self {
+        self.metadata.parameters.insert(name.to_string(), value);
+        self
+    }
+
+    /// Quantidade de amostras.
+    pub fn len(&self) -> usize {
+        self.time.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.time.is_empty()
+    }
+
+    pub fn column(&self, name: &str) -> Option<&Column> {
+        self.columns.iter().find(|column| column.name == name)
+    }
+
+    /// A coluna `name` como sinal, para usar de entrada em outra simulação.
+    pub fn replay(&self, name: &str, interpolation: Interpolation) -> Option<Replay> {
+        let column = self.column(name)?;
+        if self.is_empty() {
+            return None;
+        }
+
+        Some(Replay::new(
+            self.time.clone(),
+            column.values.clone(),
+            interpolation,
+        ))
+    }
+
+    /// A coluna `name` pronta para um gráfico.
+    pub fn series(&self, name: &str) -> Option<Series> {
+        let column = self.column(name)?;
+        Some(Series::from_xy(&column.name, &self.time, &column.values))
+    }
+
+    fn validate(self) -> Result<Self, RecordError> {
+        match self
+            .columns
+            .iter()
+            .find(|c| c.values.len() != self.time.len())
+        {
+            Some(column) => Err(RecordError::Length {
+                column: column.name.clone(),
+            }),
+            None => Ok(self),
+        }
+    }
+
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> std::io::Result<()> {
+        writeln!(writer, "# name: {}", self.metadata.name)?;
+        if let Some(rate) = self.metadata.sample_rate {
+            writeln!(writer, "# sample_rate: {rate}")?;
+        }
+        for (name, value) in &self.metadata.parameters {
+            writeln!(writer, "# {name}: {value}")?;
+        }
+
+        write!(writer, "t")?;
+        for column in &self.columns {
+            match &column.unit {
+                Some(unit) => write!(writer, ",{} [{unit}]", column.name)?,
+                None => write!(writer, ",{}", column.name)?,
+            }
+        }
+        writeln!(writer)?;
+
+        for (k, t) in self.time.iter().enumerate() {
+            write!(writer, "{t}")?;
+            for column in &self.columns {
+                write!(writer, ",{}", column.values[k])?;
+            }
+            writeln!(writer)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn read_csv<R: BufRead>(reader: R) -> Result<Self, RecordError> {
+        let mut recording = Recording::default();
+        let mut header = false;
+
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            let error = |message: String| RecordError::Parse {
+                line: index + 1,
+                message,
+            };
+            let line = line.trim();
+
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(comment) = line.strip_prefix('#') {
+                let Some((key, value)) = comment.split_once(':') else {
+                    continue;
+                };
+                let (key, value) = (key.trim(), value.trim());
+                match key {
+                    "name" => recording.metadata.name = value.to_string(),
+                    "sample_rate" => {
+                        let rate = value
+                            .parse()
+                            .map_err(|_| error(format!("invalid sample rate {value}")))?;
+                        recording.metadata.sample_rate = Some(rate);
+                    }
+                    _ => {
+                        let value = value
+                            .parse()
+                            .map_err(|_| error(format!("invalid value for {key}: {value}")))?;
+                        recording.metadata.parameters.insert(key.to_string(), value);
+                    }
+                }
+                continue;
+            }
+
+            if !header {
+                header = true;
+                // A primeira coluna é o tempo, com qualquer nome
+                for field in line.split(',').skip(1) {
+                    let field = field.trim();
+                    let (name, unit) = match field.strip_suffix(']').and_then(|f| f.split_once('['))
+                    {
+                        Some((name, unit)) => (name.trim(), Some(unit.trim().to_string())),
+                        None => (field, None),
+                    };
+                    recording.columns.push(Column {
+                        name: name.to_string(),
+                        unit,
+                        values: Vec::new(),
+                    });
+                }
+                continue;
+            }
+
+            let values = line
+                .split(',')
+                .map(|field| field.trim().parse::<f32>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| error(e.to_string()))?;
+            if values.len() != recording.columns.len() + 1 {
+                return Err(error(format!(
+                    "expected {} values, found {}",
+                    recording.columns.len() + 1,
+                    values.len()
+                )));
+            }
+
+            recording.time.push(values[0]);
+            for (column, value) in recording.columns.iter_mut().zip(&values[1..]) {
+                column.values.push(*value);
+            }
+        }
+
+        Ok(recording)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("recording is always serializable")
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, RecordError> {
+        serde_json::from_str::<Self>(json)?.validate()
+    }
+
+    /// Salva em CSV ou JSON, pela extensão de `path`.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), RecordError> {
+        let path = path.as_ref();
+        let mut writer = BufWriter::new(File::create(path)?);
+
+        match extension(path)?.as_str() {
+            "csv" => self.write_csv(&mut writer)?,
+            _ => writer.write_all(self.to_json().as_bytes())?,
+        }
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    /// Lê um arquivo salvo com [`Recording::save`], ou um CSV no mesmo formato gerado por
+    /// outra ferramenta.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, RecordError> {
+        let path = path.as_ref();
+
+        match extension(path)?.as_str() {
+            "csv" => Self::read_csv(BufReader::new(File::open(path)?)),
+            _ => Self::from_json(&std::fs::read_to_string(path)?),
+        }
+    }
+}
+
+/// `csv` ou `json`.
+fn extension(path: &Path) -> Result<String, RecordError> {
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_ascii_lowercase);
+
+    match extension {
+        Some(extension) if extension == "csv" || extension == "json" => Ok(extension),
+        _ => Err(RecordError::Format(path.display().to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physical::controller::{Pid, P};
+    use crate::physical::simulation::ClosedLoop;
+    use crate::physical::System;
+    use crate::signals::sine::Sine;
+    use crate::signals::step::Step;
+
+    fn recording() -> Recording {
+        Recording::from_wave("sine", &Sine::new(0.04, 2.0, 0.0), 100.0, 0.5)
+            .with_unit("sine", "V")
+            .with_column("offset", vec![1.5; 51])
+            .with_parameter("amplitude", 2.0)
+            .with_parameter("period", 0.04)
+    }
+
+    #[test]
+    fn test_from_wave() {
+        let recording = recording();
+
+        assert_eq!(recording.len(), 51);
+        assert_eq!(recording.time[50], 0.5);
+        assert_eq!(recording.metadata.sample_rate, Some(100.0));
+        assert!((recording.column("sine").unwrap().values[1] - 2.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_csv_round_trip() {
+        let recording = recording();
+        let mut csv = Vec::new();
+        recording.write_csv(&mut csv).unwrap();
+
+        let text = String::from_utf8(csv.clone()).unwrap();
+        assert!(text.starts_with("# name: sine\n# sample_rate: 100\n# amplitude: 2\n"));
+        assert!(text.contains("t,sine [V],offset\n0,0,1.5\n"));
+
+        assert_eq!(Recording::read_csv(&csv[..]).unwrap(), recording);
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let recording = recording();
+        let json = recording.to_json();
+
+        assert!(json.contains("\"unit\": \"V\""));
+        assert_eq!(Recording::from_json(&json).unwrap(), recording);
+
+        let truncated = json.replacen("1.5,", "", 1);
+        assert!(matches!(
+            Recording::from_json(&truncated),
+            Err(RecordError::Length { column }) if column == "offset"
+        ));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |csv: &str| match Recording::read_csv(csv.as_bytes()) {
+            Err(RecordError::Parse { line, .. }) => line,
+            other => panic!("{other:?}"),
+        };
+
+        assert_eq!(error("# sample_rate: fast\nt,x\n"), 1);
+        assert_eq!(error("t,x\n0,1\n1,a\n"), 3);
+        assert_eq!(error("t,x\n0,1,2\n"), 2);
+
+        // Sem metadados também funciona, como um CSV exportado de outra ferramenta
+        let recording = Recording::read_csv("time,x [g]\n0,1\n0.5,2\n".as_bytes()).unwrap();
+        assert_eq!(recording.time, [0.0, 0.5]);
+        assert_eq!(recording.column("x").unwrap().unit.as_deref(), Some("g"));
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = std::env::temp_dir();
+        let recording = recording();
+
+        for file in ["complemento3_record.csv", "complemento3_record.json"] {
+            let path = dir.join(file);
+            recording.save(&path).unwrap();
+            assert_eq!(Recording::load(&path).unwrap(), recording);
+        }
+
+        assert!(matches!(
+            recording.save(dir.join("complemento3_record.txt")),
+            Err(RecordError::Format(_))
+        ));
+    }
+
+    /// A saída gravada de uma simulação, reproduzida como sinal, é igual nos instantes das
+    /// amostras e pode ser a referência de outra simulação.
+    #[test]
+    fn test_replay_run() {
+        let simulation = ClosedLoop::new(0.001, 0.5);
+        let run = simulation.run(&mut System::default(), &mut Pid::p(P), &Step::unit());
+        let recording = Recording::from_run("p", &run).with_parameter("kp", f64::from(P));
+
+        assert_eq!(recording.metadata.sample_rate.map(f32::round), Some(1000.0));
+        let output = recording.replay("output", Interpolation::Linear).unwrap();
+        for (t, y) in run.time.iter().zip(&run.output) {
+            assert_eq!(output.compute(*t), *y);
+        }
+
+        let follower = simulation.run(&mut System::default(), &mut Pid::p(P), &output);
+        assert!(follower.final_output().unwrap() < run.final_output().unwrap());
+        assert!(recording.replay("missing", Interpolation::Hold).is_none());
+    }
+}
@@ -0,0 +1,133 @@
+use crate::signals::Wave;
+use std::f32::consts::PI;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sweep {
+    /// A frequência varia linearmente com o tempo.
+    Linear,
+    /// A frequência é multiplicada pelo mesmo fator a cada intervalo de tempo, passando o mesmo
+    /// tempo em cada oitava. Bom para levantar resposta em frequência.
+    Exponential,
+}
+
+/// Senoide com frequência variando de `start` a `end` Hz ao longo de `duration` segundos.
+///
+/// Depois de `duration` a varredura recomeça.
+pub struct Chirp {
+    start: f32,
+    end: f32,
+    duration: f32,
+    amplitude: f32,
+    offset: f32,
+    sweep: Sweep,
+    phase: f32,
+}
+
+impl Chirp {
+    /// !!! Na varredura exponencial as duas frequências têm que ser maiores que zero.
+    pub fn new(start: f32, end: f32, duration: f32, amplitude: f32, sweep: Sweep) -> Self {
+        Self {
+            start,
+            end,
+            duration,
+            amplitude,
+            offset: 0.0,
+            sweep,
+            phase: 0.0,
+        }
+    }
+
+    pub fn with_offset(self, offset: f32) -> Self {
+        Self { offset, ..self }
+    }
+
+    /// Fase inicial, em radianos.
+    pub fn with_phase(self, phase: f32) -> Self {
+        Self { phase, ..self }
+    }
+
+    /// Frequência instantânea em `t`, em Hz.
+    pub fn frequency(&self, t: f32) -> f32 {
+        let t = t.rem_euclid(self.duration);
+        match self.sweep {
+            Sweep::Linear => self.start + (self.end - self.start) * t / self.duration,
+            Sweep::Exponential => self.start * (self.end / self.start).powf(t / self.duration),
+        }
+    }
+
+    /// Integral da frequência de 0 a `t`, em ciclos.
+    fn cycles(&self, t: f32) -> f32 {
+        match self.sweep {
+            Sweep::Linear => {
+                self.start * t + (self.end - self.start) * t * t / (2.0 * self.duration)
+            }
+            Sweep::Exponential => {
+                let ratio = self.end / self.start;
+                if ratio == 1.0 {
+                    return self.start * t;
+                }
+                self.start * self.duration * (ratio.powf(t / self.duration) - 1.0) / ratio.ln()
+            }
+        }
+    }
+}
+
+impl Wave for Chirp {
+    fn compute(&self, t: f32) -> f32 {
+        let t = t.rem_euclid(self.duration);
+
+        self.amplitude * f32::sin(2.0 * PI * self.cycles(t) + self.phase) + self.offset
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Conta as passagens por zero subindo entre `from` e `to`.
+    fn rising_crossings(wave: &Chirp, from: f32, to: f32) -> usize {
+        let dt = 1e-4;
+        let steps = ((to - from) / dt) as usize;
+        (0..steps)
+            .filter(|&i| {
+                let t = from + i as f32 * dt;
+                wave.compute(t) < 0.0 && wave.compute(t + dt) >= 0.0
+            })
+            .count()
+    }
+
+    #[test]
+    fn test_linear_frequency() {
+        let chirp = Chirp::new(1.0, 21.0, 2.0, 1.0, Sweep::Linear);
+
+        assert_eq!(chirp.frequency(0.0), 1.0);
+        assert_eq!(chirp.frequency(1.0), 11.0);
+        // Recomeça depois de `duration`
+        assert_eq!(chirp.frequency(2.5), 6.0);
+
+        // Em 2 s a frequência média é 11 Hz: 22 ciclos
+        let cycles = rising_crossings(&chirp, 0.0, 1.999);
+        assert!((21..=22).contains(&cycles), "{cycles} cycles");
+    }
+
+    #[test]
+    fn test_exponential_frequency() {
+        let chirp = Chirp::new(1.0, 100.0, 2.0, 1.0, Sweep::Exponential);
+
+        assert!((chirp.frequency(1.0) - 10.0).abs() < 1e-3);
+        assert!((chirp.frequency(1.999) - 100.0).abs() < 0.5);
+
+        // Cada segundo cobre uma década: ∫ 10^t dt de 0 a 1 = 9 / ln 10 ≈ 3,9 ciclos
+        let cycles = rising_crossings(&chirp, 0.0, 1.0);
+        assert!((3..=4).contains(&cycles), "{cycles} cycles");
+    }
+
+    #[test]
+    fn test_phase_and_offset() {
+        let chirp = Chirp::new(1.0, 2.0, 1.0, 2.0, Sweep::Linear)
+            .with_phase(PI / 2.0)
+            .with_offset(1.0);
+
+        assert!((chirp.compute(0.0) - 3.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,284 @@
+//! Combinações de sinais.
+//!
+//! Cada combinação é um `Wave` que guarda os sinais de entrada e calcula o resultado em
+//! `compute`, então elas podem ser encadeadas à vontade:
+//!
+//! ```ignore
+//! let stimulus = Sine::new(1.0, 1.0, 0.0)
+//!     .add(SquareWave::new(0.2, 0.1, 0.0))
+//!     .scale(0.5)
+//!     .clamp(-0.4, 0.4);
+//! ```
+//!
+//! Os métodos que criam as combinações ficam no próprio trait [`Wave`].
+
+use crate::numeric::Number;
+use crate::signals::Wave;
+
+/// `a(t) + b(t)`
+pub struct Sum<A, B> {
+    pub(crate) a: A,
+    pub(crate) b: B,
+}
+
+impl<T: Number, A: Wave<T>, B: Wave<T>> Wave<T> for Sum<A, B> {
+    fn compute(&self, t: T) -> T {
+        self.a.compute(t) + self.b.compute(t)
+    }
+}
+
+/// `a(t) * b(t)`, por exemplo para modular a amplitude de um sinal.
+pub struct Product<A, B> {
+    pub(crate) a: A,
+    pub(crate) b: B,
+}
+
+impl<T: Number, A: Wave<T>, B: Wave<T>> Wave<T> for Product<A, B> {
+    fn compute(&self, t: T) -> T {
+        self.a.compute(t) * self.b.compute(t)
+    }
+}
+
+/// `gain * w(t)`
+pub struct Scale<W, T = f32> {
+    pub(crate) wave: W,
+    pub(crate) gain: T,
+}
+
+impl<T: Number, W: Wave<T>> Wave<T> for Scale<W, T> {
+    fn compute(&self, t: T) -> T {
+        self.gain * self.wave.compute(t)
+    }
+}
+
+/// `w(t) + offset`
+pub struct Offset<W, T = f32> {
+    pub(crate) wave: W,
+    pub(crate) offset: T,
+}
+
+impl<T: Number, W: Wave<T>> Wave<T> for Offset<W, T> {
+    fn compute(&self, t: T) -> T {
+        self.wave.compute(t) + self.offset
+    }
+}
+
+/// `w(t - delay)`: com `delay` positivo o sinal fica atrasado.
+pub struct Shift<W, T = f32> {
+    pub(crate) wave: W,
+    pub(crate) delay: T,
+}
+
+impl<T: Number, W: Wave<T>> Wave<T> for Shift<W, T> {
+    fn compute(&self, t: T) -> T {
+        self.wave.compute(t - self.delay)
+    }
+}
+
+/// `w(t)` limitado a `[min, max]`, como a saturação de um atuador.
+pub struct Clamp<W, T = f32> {
+    pub(crate) wave: W,
+    pub(crate) min: T,
+    pub(crate) max: T,
+}
+
+impl<T: Number, W: Wave<T>> Wave<T> for Clamp<W, T> {
+    fn compute(&self, t: T) -> T {
+        self.wave.compute(t).clamp(self.min, self.max)
+    }
+}
+
+/// Um sinal até `at`, depois outro.
+///
+/// O segundo sinal começa do seu próprio `t = 0` no instante `at`, ou seja, o resultado em `t`
+/// é `second(t - at)`.
+pub struct Then<A, B, T = f32> {
+    pub(crate) first: A,
+    pub(crate) second: B,
+    pub(crate) at: T,
+}
+
+impl<T: Number, A: Wave<T>, B: Wave<T>> Wave<T> for Then<A, B, T> {
+    fn compute(&self, t: T) -> T {
+        if t < self.at {
+            self.first.compute(t)
+        } else {
+            self.second.compute(t - self.at)
+        }
+    }
+}
+
+/// Sequência de trechos com duração definida, cada um com o seu sinal.
+///
+/// Como em [`Then`], cada trecho começa do `t = 0` do seu sinal. Antes do primeiro trecho vale
+/// o primeiro sinal e depois do último o último sinal continua.
+///
+/// ```ignore
+/// let profile = Piecewise::new()
+///     .segment(1.0, Constant(0.0))
+///     .segment(0.5, SawTooth::new(0.5, 1.0, 0.0))
+///     .segment(1.0, Constant(1.0));
+/// ```
+#[cfg(feature = "std")]
+#[derive(Default)]
+pub struct Piecewise {
+    segments: Vec<(f32, Box<dyn Wave>)>,
+}
+
+#[cfg(feature = "std")]
+impl Piecewise {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn segment(mut self, duration: f32, wave: impl Wave + 'static) -> Self {
+        self.segments.push((duration, Box::new(wave)));
+        self
+    }
+
+    /// Soma das durações de todos os trechos.
+    pub fn duration(&self) -> f32 {
+        self.segments.iter().map(|(duration, _)| duration).sum()
+    }
+}
+
+#[cfg(feature = "std")]
+impl Wave for Piecewise {
+    fn compute(&self, t: f32) -> f32 {
+        let Some(((_, last), init)) = self.segments.split_last() else {
+            return 0.0;
+        };
+
+        let mut start = 0.0;
+        for (duration, wave) in init {
+            if t < start + duration {
+                return wave.compute(t - start);
+            }
+            start += duration;
+        }
+
+        last.compute(t - start)
+    }
+}
+
+/// Valor fixo, útil como referência ou como trecho de um [`Piecewise`].
+pub struct Constant<T = f32>(pub T);
+
+impl<T: Number> Wave<T> for Constant<T> {
+    fn compute(&self, _t: T) -> T {
+        self.0
+    }
+}
+
+impl<T: Number, W: Wave<T> + ?Sized> Wave<T> for &W {
+    fn compute(&self, t: T) -> T {
+        (**self).compute(t)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Number, W: Wave<T> + ?Sized> Wave<T> for Box<W> {
+    fn compute(&self, t: T) -> T {
+        (**self).compute(t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signals::sine::Sine;
+    use crate::signals::square::SquareWave;
+    use crate::signals::SawTooth;
+
+    const PERIOD: f32 = 1.0;
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!((actual - expected).abs() < 1e-5, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn test_sum_and_product() {
+        let sine = Sine::new(PERIOD, 1.0, 0.0);
+        let square = SquareWave::new(PERIOD, 1.0, 0.0);
+
+        let sum = (&sine).add(&square);
+        let product = (&sine).mul(&square);
+
+        for t in [0.1, 0.25, 0.6, 0.75] {
+            assert_close(sum.compute(t), sine.compute(t) + square.compute(t));
+            assert_close(product.compute(t), sine.compute(t) * square.compute(t));
+        }
+        // A onda quadrada vale 0 na primeira metade do período
+        assert_close(product.compute(0.25), 0.0);
+        assert_close(product.compute(0.75), -1.0);
+    }
+
+    #[test]
+    fn test_scale_offset_chain() {
+        let wave = SawTooth::new(PERIOD, 1.0, 0.0).scale(2.0).offset(-1.0);
+
+        assert_close(wave.compute(0.0), -1.0);
+        assert_close(wave.compute(0.5), 0.0);
+        assert_close(wave.compute(0.75), 0.5);
+    }
+
+    #[test]
+    fn test_shift() {
+        let sine = Sine::new(PERIOD, 1.0, 0.0);
+        let delayed = (&sine).shift(0.25);
+
+        assert_close(delayed.compute(0.25), 0.0);
+        assert_close(delayed.compute(0.5), 1.0);
+        assert_close(delayed.compute(0.3), sine.compute(0.05));
+    }
+
+    #[test]
+    fn test_clamp() {
+        let wave = Sine::new(PERIOD, 2.0, 0.0).clamp(-0.5, 1.0);
+
+        assert_eq!(wave.compute(0.25), 1.0);
+        assert_eq!(wave.compute(0.75), -0.5);
+        assert_close(wave.compute(0.0), 0.0);
+    }
+
+    #[test]
+    fn test_then() {
+        let wave = Constant(0.0).then(1.0, SawTooth::new(PERIOD, 1.0, 0.0));
+
+        assert_eq!(wave.compute(0.5), 0.0);
+        assert_close(wave.compute(1.25), 0.25);
+    }
+
+    #[test]
+    fn test_piecewise() {
+        let wave = Piecewise::new()
+            .segment(1.0, Constant(0.0))
+            .segment(0.5, SawTooth::new(PERIOD, 1.0, 0.0))
+            .segment(1.0, Constant(1.0));
+
+        assert_eq!(wave.duration(), 2.5);
+        assert_eq!(wave.compute(-1.0), 0.0);
+        assert_eq!(wave.compute(0.99), 0.0);
+        assert_close(wave.compute(1.25), 0.25);
+        assert_eq!(wave.compute(1.5), 1.0);
+        // O último trecho continua depois do fim
+        assert_eq!(wave.compute(10.0), 1.0);
+        assert_eq!(Piecewise::new().compute(1.0), 0.0);
+    }
+
+    #[test]
+    fn test_boxed_waves() {
+        let waves: Vec<Box<dyn Wave>> = vec![
+            Box::new(Sine::new(PERIOD, 1.0, 0.0)),
+            Box::new(Constant(2.0)),
+        ];
+
+        let total = waves
+            .into_iter()
+            .fold(Box::new(Constant(0.0f32)) as Box<dyn Wave>, |acc, wave| {
+                Box::new(acc.add(wave))
+            });
+
+        assert_close(total.compute(0.25), 3.0);
+    }
+}
@@ -1,13 +1,190 @@
-pub trait Wave {
-    fn compute(&self, t: f32) -> f32;
+pub mod combinators;
+
+use crate::numeric::Number;
+use combinators::*;
+
+/// Sinal em função do tempo. `T` é o tipo do tempo e dos valores, `f32` por padrão; os sinais
+/// periódicos, o degrau, a rampa e as combinações também funcionam em ponto fixo.
+pub trait Wave<T: Number = f32> {
+    fn compute(&self, t: T) -> T;
+
+    /// Soma com outro sinal.
+    fn add<W: Wave<T>>(self, other: W) -> Sum<Self, W>
+    where
+        Self: Sized,
+    {
+        Sum { a: self, b: other }
+    }
+
+    /// Multiplica por outro sinal.
+    fn mul<W: Wave<T>>(self, other: W) -> Product<Self, W>
+    where
+        Self: Sized,
+    {
+        Product { a: self, b: other }
+    }
+
+    /// Multiplica por uma constante.
+    fn scale(self, gain: T) -> Scale<Self, T>
+    where
+        Self: Sized,
+    {
+        Scale { wave: self, gain }
+    }
+
+    /// Soma uma constante.
+    fn offset(self, offset: T) -> Offset<Self, T>
+    where
+        Self: Sized,
+    {
+        Offset { wave: self, offset }
+    }
+
+    /// Atrasa o sinal em `delay` segundos (adianta se for negativo).
+    fn shift(self, delay: T) -> Shift<Self, T>
+    where
+        Self: Sized,
+    {
+        Shift { wave: self, delay }
+    }
+
+    /// Limita o sinal a `[min, max]`.
+    fn clamp(self, min: T, max: T) -> Clamp<Self, T>
+    where
+        Self: Sized,
+    {
+        Clamp {
+            wave: self,
+            min,
+            max,
+        }
+    }
+
+    /// Este sinal até `at` e `next` a partir dali, começando do `t = 0` de `next`.
+    fn then<W: Wave<T>>(self, at: T, next: W) -> Then<Self, W, T>
+    where
+        Self: Sized,
+    {
+        Then {
+            first: self,
+            second: next,
+            at,
+        }
+    }
 }
 
+/// Posição de `t` dentro do período, em `[0, period)`, com a fase em radianos.
+pub(crate) fn cycle_position<T: Number>(t: T, period: T, phase: T) -> T {
+    let shift = phase / T::TAU * period;
+
+    (t + shift).rem_euclid(period)
+}
+
+#[cfg(feature = "std")]
+pub mod chirp;
+#[cfg(feature = "std")]
+pub mod noise;
+pub mod pwm;
+#[cfg(feature = "std")]
+pub mod replay;
+#[cfg(feature = "std")]
+pub mod sampler;
 pub mod sawtooth;
 pub mod sine;
 pub mod square;
+pub mod step;
+#[cfg(feature = "std")]
+pub mod table;
+pub mod triangle;
 
 mod other {
     use super::*;
 }
 
+pub use crate::dsp::Window;
+#[cfg(feature = "std")]
+pub use chirp::{Chirp, Sweep};
+#[cfg(feature = "std")]
+pub use noise::{Noise, NoiseKind, Rng};
+pub use pwm::Pwm;
+#[cfg(feature = "std")]
+pub use replay::Replay;
+#[cfg(feature = "std")]
+pub use sampler::{Sampler, Samples};
 pub use sawtooth::SawTooth;
+pub use step::{Impulse, Ramp, Step};
+#[cfg(feature = "std")]
+pub use table::{Interpolation, Table};
+pub use triangle::Triangle;
+
+#[cfg(test)]
+mod tests {
+    use super::sine::Sine;
+    use super::square::SquareWave;
+    use super::*;
+    use std::f32::consts::PI;
+
+    #[test]
+    fn test_phase() {
+        let sine = Sine::new(1.0, 1.0, 0.0).with_phase(PI / 2.0);
+        assert!((sine.compute(0.0) - 1.0).abs() < 1e-6);
+
+        // Sem fase a onda quadrada começa em nível baixo; com meio período, em nível alto
+        let square = SquareWave::new(1.0, 1.0, 0.0);
+        assert_eq!(square.compute(0.1), 0.0);
+        assert_eq!(square.with_phase(PI).compute(0.1), 1.0);
+
+        let saw = SawTooth::new(1.0, 1.0, 0.0).with_phase(PI);
+        assert!((saw.compute(0.0) - 0.5).abs() < 1e-6);
+        // Tempo negativo continua no mesmo ciclo
+        assert!((SawTooth::new(1.0, 1.0, 0.0).compute(-0.25) - 0.75).abs() < 1e-6);
+    }
+
+    /// Os mesmos sinais em Q16 ficam perto dos em `f32`. O maior erro é o do seno, que passa
+    /// a fração do período em 16 bits para o ângulo. Os períodos cabem exatos em Q16; senão a
+    /// diferença do período arredondado se acumula a cada ciclo.
+    #[test]
+    fn test_fixed_point() {
+        use crate::numeric::Q16;
+
+        let q = Q16::from_f32;
+        let waves: [(&dyn Wave, &dyn Wave<Q16>); 5] = [
+            (
+                &Sine::new(0.25, 2.0, 0.5).with_phase(1.0),
+                &Sine::new(q(0.25), q(2.0), q(0.5)).with_phase(q(1.0)),
+            ),
+            (
+                &Triangle::new(0.5, 1.5, -1.0),
+                &Triangle::new(q(0.5), q(1.5), q(-1.0)),
+            ),
+            (
+                &SawTooth::new(0.75, 3.0, 0.0),
+                &SawTooth::new(q(0.75), q(3.0), q(0.0)),
+            ),
+            (
+                &Pwm::new(0.25, 0.25, 1.0, 0.0)
+                    .add(Ramp::new(1.0, 0.5))
+                    .clamp(0.0, 1.2),
+                &Pwm::new(q(0.25), q(0.25), q(1.0), q(0.0))
+                    .add(Ramp::new(q(1.0), q(0.5)))
+                    .clamp(q(0.0), q(1.2)),
+            ),
+            (
+                &SquareWave::new(0.25, 1.0, 0.0).shift(0.01).scale(-2.0),
+                &SquareWave::new(q(0.25), q(1.0), q(0.0))
+                    .shift(q(0.01))
+                    .scale(q(-2.0)),
+            ),
+        ];
+
+        for (k, (float, fixed)) in waves.iter().enumerate() {
+            let error = (0..3000)
+                // Fora das bordas das ondas retangulares, onde o arredondamento de `t` troca o nível
+                .map(|i| q(i as f32 / 1000.0 + 0.0005))
+                // O mesmo instante nos dois, para medir só o erro das contas
+                .map(|t| (float.compute(t.to_f32()) - fixed.compute(t).to_f32()).abs())
+                .fold(0.0, f32::max);
+            assert!(error < 5e-4, "wave {k}: {error}");
+        }
+    }
+}
@@ -0,0 +1,221 @@
+//! Ruído pseudoaleatório reprodutível.
+//!
+//! `Wave::compute` recebe `&self`, então o ruído não pode guardar o estado de um gerador que
+//! avança a cada chamada. Em vez disso, o valor de cada amostra é calculado a partir da semente
+//! e do índice da amostra (`t * sample_rate`). Com isso o mesmo `t` sempre dá o mesmo valor, o
+//! sinal pode ser calculado fora de ordem e dois testes com a mesma semente veem o mesmo ruído.
+
+use crate::signals::Wave;
+use std::f32::consts::PI;
+
+/// Gerador SplitMix64: pequeno, rápido e suficiente para gerar estímulos (não para criptografia).
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        mix(self.state)
+    }
+
+    /// Uniforme em `[0, 1)`.
+    pub fn next_f32(&mut self) -> f32 {
+        unit(self.next_u64())
+    }
+
+    /// Normal com média 0 e desvio padrão 1 (Box-Muller).
+    pub fn gaussian(&mut self) -> f32 {
+        box_muller(self.next_u64(), self.next_u64())
+    }
+}
+
+/// Finalizador do SplitMix64: espalha os bits da entrada por toda a saída.
+fn mix(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+fn unit(bits: u64) -> f32 {
+    // 24 bits cabem exatamente na mantissa do f32
+    (bits >> 40) as f32 / (1u64 << 24) as f32
+}
+
+fn box_muller(a: u64, b: u64) -> f32 {
+    // `1 - u` fica em (0, 1], evitando ln(0)
+    let u1 = 1.0 - unit(a);
+    let u2 = unit(b);
+    (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NoiseKind {
+    /// Uniforme em `[-amplitude, amplitude]`.
+    Uniform,
+    /// Gaussiano com desvio padrão `amplitude`.
+    Gaussian,
+    /// Ruído rosa (densidade espectral proporcional a 1/f), com desvio padrão aproximado
+    /// `amplitude`. Gerado pelo algoritmo de Voss-McCartney com [`PINK_ROWS`] geradores.
+    Pink,
+}
+
+/// Quantidade de geradores somados no ruído rosa. Cada um muda com metade da frequência do
+/// anterior, então o espectro 1/f vale por umas 16 oitavas abaixo de `sample_rate`.
+pub const PINK_ROWS: u32 = 16;
+
+/// Ruído que muda de valor `sample_rate` vezes por segundo e fica constante entre as amostras.
+pub struct Noise {
+    kind: NoiseKind,
+    amplitude: f32,
+    sample_rate: f32,
+    seed: u64,
+}
+
+impl Noise {
+    pub fn new(kind: NoiseKind, amplitude: f32, sample_rate: f32, seed: u64) -> Self {
+        Self {
+            kind,
+            amplitude,
+            sample_rate,
+            seed,
+        }
+    }
+
+    pub fn uniform(amplitude: f32, sample_rate: f32, seed: u64) -> Self {
+        Self::new(NoiseKind::Uniform, amplitude, sample_rate, seed)
+    }
+
+    pub fn gaussian(std_dev: f32, sample_rate: f32, seed: u64) -> Self {
+        Self::new(NoiseKind::Gaussian, std_dev, sample_rate, seed)
+    }
+
+    pub fn pink(std_dev: f32, sample_rate: f32, seed: u64) -> Self {
+        Self::new(NoiseKind::Pink, std_dev, sample_rate, seed)
+    }
+
+    /// Valor pseudoaleatório para o par (`stream`, `index`).
+    fn random(&self, stream: u64, index: i64) -> u64 {
+        mix(self.seed ^ mix(stream.wrapping_add(0x9E37_79B9_7F4A_7C15) ^ index as u64))
+    }
+}
+
+impl Wave for Noise {
+    fn compute(&self, t: f32) -> f32 {
+        let index = (t * self.sample_rate).floor() as i64;
+
+        let value = match self.kind {
+            NoiseKind::Uniform => 2.0 * unit(self.random(0, index)) - 1.0,
+            NoiseKind::Gaussian => box_muller(self.random(0, index), self.random(1, index)),
+            NoiseKind::Pink => {
+                // Cada gerador `row` sorteia um novo valor a cada 2^row amostras. A soma de
+                // geradores uniformes independentes tem variância PINK_ROWS / 3.
+                let sum: f32 = (0..PINK_ROWS)
+                    .map(|row| {
+                        let held = index >> row;
+                        2.0 * unit(self.random(u64::from(row), held)) - 1.0
+                    })
+                    .sum();
+                sum / (PINK_ROWS as f32 / 3.0).sqrt()
+            }
+        };
+
+        self.amplitude * value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: f32 = 1000.0;
+
+    fn samples(noise: &Noise, count: usize) -> Vec<f32> {
+        (0..count).map(|i| noise.compute(i as f32 / RATE)).collect()
+    }
+
+    fn mean_and_std(values: &[f32]) -> (f32, f32) {
+        let mean = values.iter().sum::<f32>() / values.len() as f32;
+        let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f32>() / values.len() as f32;
+        (mean, variance.sqrt())
+    }
+
+    #[test]
+    fn test_rng_is_seedable() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        let mut c = Rng::new(43);
+
+        let a: Vec<_> = (0..10).map(|_| a.next_u64()).collect();
+        let b: Vec<_> = (0..10).map(|_| b.next_u64()).collect();
+        let c: Vec<_> = (0..10).map(|_| c.next_u64()).collect();
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+
+        let mut rng = Rng::new(1);
+        let values: Vec<f32> = (0..10_000).map(|_| rng.gaussian()).collect();
+        let (mean, std) = mean_and_std(&values);
+        assert!(mean.abs() < 0.05 && (std - 1.0).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_uniform() {
+        let noise = Noise::uniform(2.0, RATE, 7);
+        let values = samples(&noise, 10_000);
+        let (mean, std) = mean_and_std(&values);
+
+        assert!(values.iter().all(|v| (-2.0..2.0).contains(v)));
+        assert!(mean.abs() < 0.05);
+        // Desvio padrão da uniforme em [-a, a]: a / √3
+        assert!((std - 2.0 / 3f32.sqrt()).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_gaussian() {
+        let noise = Noise::gaussian(0.5, RATE, 7);
+        let (mean, std) = mean_and_std(&samples(&noise, 10_000));
+
+        assert!(mean.abs() < 0.02);
+        assert!((std - 0.5).abs() < 0.02);
+    }
+
+    #[test]
+    fn test_reproducible_and_held() {
+        let noise = Noise::gaussian(1.0, 10.0, 3);
+
+        // Mesmo `t`, mesmo valor; constante dentro do intervalo de uma amostra
+        assert_eq!(noise.compute(0.53), noise.compute(0.53));
+        assert_eq!(noise.compute(0.51), noise.compute(0.59));
+        assert_ne!(noise.compute(0.59), noise.compute(0.61));
+        assert_ne!(
+            noise.compute(0.5),
+            Noise::gaussian(1.0, 10.0, 4).compute(0.5)
+        );
+    }
+
+    /// No ruído rosa as amostras vizinhas são correlacionadas; no branco não.
+    #[test]
+    fn test_pink() {
+        fn lag_correlation(values: &[f32]) -> f32 {
+            let (mean, std) = mean_and_std(values);
+            let sum: f32 = values
+                .windows(2)
+                .map(|pair| (pair[0] - mean) * (pair[1] - mean))
+                .sum();
+            sum / ((values.len() - 1) as f32 * std * std)
+        }
+
+        let pink = samples(&Noise::pink(1.0, RATE, 9), 20_000);
+        let white = samples(&Noise::gaussian(1.0, RATE, 9), 20_000);
+
+        let (_, std) = mean_and_std(&pink);
+        assert!((std - 1.0).abs() < 0.3, "std {std}");
+        assert!(lag_correlation(&pink) > 0.5);
+        assert!(lag_correlation(&white).abs() < 0.05);
+    }
+}
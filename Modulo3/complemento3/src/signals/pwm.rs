@@ -0,0 +1,91 @@
+use crate::numeric::Number;
+use crate::signals::{cycle_position, Wave};
+
+/// Onda retangular com ciclo ativo configurável. Começa em nível alto e fica nele durante a
+/// fração `duty` do período.
+pub struct Pwm<T = f32> {
+    period: T,
+    duty: T,
+    amplitude: T,
+    offset: T,
+    phase: T,
+}
+
+impl<T: Number> Pwm<T> {
+    /// `duty` é a fração do período em nível alto, limitada a `[0, 1]`.
+    pub fn new(period: T, duty: T, amplitude: T, offset: T) -> Self {
+        Self {
+            period,
+            duty: duty.clamp(T::ZERO, T::ONE),
+            amplitude,
+            offset,
+            phase: T::ZERO,
+        }
+    }
+
+    /// Fase inicial, em radianos.
+    pub fn with_phase(self, phase: T) -> Self {
+        Self { phase, ..self }
+    }
+
+    pub fn duty(&self) -> T {
+        self.duty
+    }
+
+    /// Valor médio do sinal em um período.
+    pub fn mean(&self) -> T {
+        self.amplitude * self.duty + self.offset
+    }
+}
+
+impl<T: Number> Wave<T> for Pwm<T> {
+    fn compute(&self, t: T) -> T {
+        let now = cycle_position(t, self.period, self.phase);
+        let state = if now < self.duty * self.period {
+            T::ONE
+        } else {
+            T::ZERO
+        };
+
+        (self.amplitude * state) + self.offset
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::PI;
+
+    #[test]
+    fn test_duty() {
+        let wave = Pwm::new(1.0, 0.25, 3.3, 0.0);
+
+        assert_eq!(wave.compute(0.0), 3.3);
+        assert_eq!(wave.compute(0.24), 3.3);
+        assert_eq!(wave.compute(0.25), 0.0);
+        assert_eq!(wave.compute(0.99), 0.0);
+        assert_eq!(wave.compute(1.1), 3.3);
+
+        let samples = 1000;
+        let mean = (0..samples)
+            .map(|i| wave.compute(i as f32 / samples as f32))
+            .sum::<f32>()
+            / samples as f32;
+        assert!((mean - wave.mean()).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_limits() {
+        assert_eq!(Pwm::new(1.0, 1.5, 1.0, 0.0).duty(), 1.0);
+        assert_eq!(Pwm::new(1.0, 0.0, 1.0, 0.0).compute(0.0), 0.0);
+    }
+
+    #[test]
+    fn test_phase() {
+        // Adiantada de meio período: começa em nível baixo, como a `SquareWave`
+        let wave = Pwm::new(1.0, 0.5, 1.0, 0.0).with_phase(PI);
+
+        assert_eq!(wave.compute(0.1), 0.0);
+        assert_eq!(wave.compute(0.6), 1.0);
+    }
+}
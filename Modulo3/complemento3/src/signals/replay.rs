@@ -0,0 +1,89 @@
+//! Reprodução de amostras gravadas (de um sensor ou de outra simulação) como sinal.
+
+use crate::signals::table::Interpolation;
+use crate::signals::Wave;
+
+/// Sinal definido por pontos `(t, valor)`, que não precisam ser igualmente espaçados.
+///
+/// Diferente de [`Table`](crate::signals::table::Table), não repete: antes do primeiro ponto
+/// vale o primeiro valor e depois do último, o último.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Replay {
+    time: Vec<f32>,
+    values: Vec<f32>,
+    interpolation: Interpolation,
+}
+
+impl Replay {
+    /// !!! `time` precisa estar em ordem crescente e ter o mesmo tamanho de `values`, sem ser
+    /// vazio.
+    pub fn new(time: Vec<f32>, values: Vec<f32>, interpolation: Interpolation) -> Self {
+        assert!(!time.is_empty(), "replay without samples");
+        assert_eq!(
+            time.len(),
+            values.len(),
+            "time and values with different sizes"
+        );
+        assert!(
+            time.windows(2).all(|pair| pair[0] <= pair[1]),
+            "time out of order"
+        );
+
+        Self {
+            time,
+            values,
+            interpolation,
+        }
+    }
+
+    /// Instante do primeiro e do último ponto.
+    pub fn span(&self) -> (f32, f32) {
+        (self.time[0], self.time[self.time.len() - 1])
+    }
+}
+
+impl Wave for Replay {
+    fn compute(&self, t: f32) -> f32 {
+        // Quantidade de pontos com tempo <= t
+        let after = self.time.partition_point(|&time| time <= t);
+        if after == 0 {
+            return self.values[0];
+        }
+        let index = after - 1;
+        if index + 1 == self.time.len() {
+            return self.values[index];
+        }
+
+        match self.interpolation {
+            Interpolation::Hold => self.values[index],
+            Interpolation::Linear => {
+                let (t0, t1) = (self.time[index], self.time[index + 1]);
+                let (y0, y1) = (self.values[index], self.values[index + 1]);
+                y0 + (y1 - y0) * (t - t0) / (t1 - t0)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replay() {
+        let time = vec![0.0, 1.0, 3.0];
+        let values = vec![0.0, 2.0, -2.0];
+
+        let linear = Replay::new(time.clone(), values.clone(), Interpolation::Linear);
+        assert_eq!(linear.compute(-1.0), 0.0);
+        assert_eq!(linear.compute(0.5), 1.0);
+        assert_eq!(linear.compute(2.0), 0.0);
+        assert_eq!(linear.compute(3.0), -2.0);
+        assert_eq!(linear.compute(10.0), -2.0);
+
+        let hold = Replay::new(time, values, Interpolation::Hold);
+        assert_eq!(hold.compute(0.99), 0.0);
+        assert_eq!(hold.compute(2.0), 2.0);
+        assert_eq!(hold.span(), (0.0, 3.0));
+    }
+}
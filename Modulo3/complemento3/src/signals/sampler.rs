@@ -0,0 +1,317 @@
+//! Amostragem de sinais em uma grade de tempo e operações sobre as amostras.
+//!
+//! ```ignore
+//! // 1 kHz durante 2 s, incluindo t = 2
+//! let samples = Sampler::new(1000.0, 2.0).samples(&Sine::new(0.1, 1.0, 0.0));
+//! let slow = samples.decimate(10);
+//! let windowed = slow.window(Window::Hann);
+//! ```
+
+use crate::dsp::filter::Filter;
+use crate::dsp::Window;
+use crate::signals::noise::Rng;
+use crate::signals::replay::Replay;
+use crate::signals::table::Interpolation;
+use crate::signals::Wave;
+
+/// Grade de tempo: `count` instantes `start + k / rate`, com ruído opcional (jitter).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sampler {
+    rate: f32,
+    start: f32,
+    count: usize,
+    jitter: f32,
+    seed: u64,
+}
+
+impl Sampler {
+    /// `rate` em Hz, de `0` até `duration` segundos, incluindo as duas pontas.
+    pub fn new(rate: f32, duration: f32) -> Self {
+        Self::with_count(rate, (duration * rate).round() as usize + 1)
+    }
+
+    /// `count` amostras a `rate` Hz, a partir de `t = 0`.
+    pub fn with_count(rate: f32, count: usize) -> Self {
+        Self {
+            rate,
+            start: 0.0,
+            count,
+            jitter: 0.0,
+            seed: 0,
+        }
+    }
+
+    /// `count` amostras igualmente espaçadas em `[start, end]`, incluindo as duas pontas.
+    pub fn over(start: f32, end: f32, count: usize) -> Self {
+        let rate = (count.max(2) - 1) as f32 / (end - start);
+        Self {
+            start,
+            ..Self::with_count(rate, count)
+        }
+    }
+
+    /// Desloca a grade para começar em `start`.
+    pub fn starting_at(self, start: f32) -> Self {
+        Self { start, ..self }
+    }
+
+    /// Soma a cada instante um erro uniforme em `±jitter` segundos, como o de um timer ou de
+    /// uma interrupção atrasada. É limitado a meio período para as amostras não trocarem de
+    /// ordem. O mesmo `seed` gera sempre os mesmos instantes.
+    pub fn with_jitter(self, jitter: f32, seed: u64) -> Self {
+        Self {
+            jitter: jitter.min(0.5 / self.rate),
+            seed,
+            ..self
+        }
+    }
+
+    pub fn rate(&self) -> f32 {
+        self.rate
+    }
+
+    pub fn period(&self) -> f32 {
+        1.0 / self.rate
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Do primeiro ao último instante nominal.
+    pub fn duration(&self) -> f32 {
+        self.count.saturating_sub(1) as f32 / self.rate
+    }
+
+    pub fn times(&self) -> impl Iterator<Item = f32> {
+        let Sampler {
+            rate,
+            start,
+            count,
+            jitter,
+            seed,
+        } = *self;
+        let mut rng = Rng::new(seed);
+
+        (0..count).map(move |k| {
+            let error = if jitter > 0.0 {
+                (2.0 * rng.next_f32() - 1.0) * jitter
+            } else {
+                0.0
+            };
+            start + k as f32 / rate + error
+        })
+    }
+
+    /// Pares `(t, wave(t))`.
+    pub fn sample<'a, W: Wave + ?Sized>(
+        &self,
+        wave: &'a W,
+    ) -> impl Iterator<Item = (f32, f32)> + 'a {
+        self.times().map(move |t| (t, wave.compute(t)))
+    }
+
+    pub fn samples<W: Wave + ?Sized>(&self, wave: &W) -> Samples {
+        self.sample(wave).collect()
+    }
+}
+
+/// Amostras `(t, valor)`, em ordem de tempo.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Samples {
+    pub time: Vec<f32>,
+    pub values: Vec<f32>,
+}
+
+impl Samples {
+    pub fn len(&self) -> usize {
+        self.time.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.time.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (f32, f32)> + '_ {
+        self.time.iter().copied().zip(self.values.iter().copied())
+    }
+
+    /// Fica com uma a cada `factor` amostras.
+    ///
+    /// !!! Não filtra antes: frequências acima da nova metade da taxa de amostragem voltam
+    /// como aliasing. Passe um passa-baixas antes se o sinal tiver conteúdo nessa faixa.
+    pub fn decimate(&self, factor: usize) -> Samples {
+        assert!(factor > 0, "decimation factor must be positive");
+        self.iter().step_by(factor).collect()
+    }
+
+    /// Reamostra nos instantes de `sampler`, interpolando entre as amostras. Fora do trecho
+    /// gravado repete o primeiro ou o último valor.
+    pub fn resample(&self, sampler: &Sampler, interpolation: Interpolation) -> Samples {
+        match self.replay(interpolation) {
+            Some(replay) => sampler.samples(&replay),
+            None => Samples::default(),
+        }
+    }
+
+    /// Multiplica as amostras pela janela.
+    pub fn window(&self, window: Window) -> Samples {
+        let len = self.len();
+        Samples {
+            time: self.time.clone(),
+            values: self
+                .values
+                .iter()
+                .enumerate()
+                .map(|(k, v)| v * window.weight(k, len))
+                .collect(),
+        }
+    }
+
+    /// Passa os valores por `filter`, na ordem, mantendo os instantes. O filtro continua do
+    /// estado em que estava; chame [`Filter::reset`] antes para começar do zero.
+    pub fn filter<F: Filter + ?Sized>(&self, filter: &mut F) -> Samples {
+        Samples {
+            time: self.time.clone(),
+            values: self.values.iter().map(|&v| filter.update(v)).collect(),
+        }
+    }
+
+    /// Trechos de `size` amostras, começando a cada `hop` amostras. Trechos incompletos no
+    /// fim são descartados.
+    pub fn frames(&self, size: usize, hop: usize) -> impl Iterator<Item = Samples> + '_ {
+        assert!(size > 0 && hop > 0, "frame size and hop must be positive");
+        let count = if self.len() >= size {
+            (self.len() - size) / hop + 1
+        } else {
+            0
+        };
+
+        (0..count).map(move |i| {
+            let range = i * hop..i * hop + size;
+            Samples {
+                time: self.time[range.clone()].to_vec(),
+                values: self.values[range].to_vec(),
+            }
+        })
+    }
+
+    /// As amostras como sinal contínuo. `None` se não houver amostras.
+    pub fn replay(&self, interpolation: Interpolation) -> Option<Replay> {
+        (!self.is_empty())
+            .then(|| Replay::new(self.time.clone(), self.values.clone(), interpolation))
+    }
+}
+
+impl FromIterator<(f32, f32)> for Samples {
+    fn from_iter<I: IntoIterator<Item = (f32, f32)>>(iter: I) -> Self {
+        let (time, values) = iter.into_iter().unzip();
+        Samples { time, values }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signals::sine::Sine;
+    use crate::signals::step::Ramp;
+
+    #[test]
+    fn test_grid() {
+        let sampler = Sampler::new(10.0, 1.0);
+        let times: Vec<f32> = sampler.times().collect();
+
+        assert_eq!(sampler.count(), 11);
+        assert_eq!(times[0], 0.0);
+        assert!((times[10] - 1.0).abs() < 1e-6);
+        assert!((sampler.duration() - 1.0).abs() < 1e-6);
+
+        // A grade `t * (x_max / (total - 1))` que os testes montavam à mão
+        let (x_max, total) = (1.5, 1000);
+        let over = Sampler::over(0.0, x_max, total);
+        for (k, t) in over.times().enumerate() {
+            assert!((t - k as f32 * (x_max / (total as f32 - 1.0))).abs() < 1e-5);
+        }
+
+        let shifted: Vec<f32> = Sampler::with_count(2.0, 3)
+            .starting_at(1.0)
+            .times()
+            .collect();
+        assert_eq!(shifted, [1.0, 1.5, 2.0]);
+    }
+
+    #[test]
+    fn test_jitter() {
+        let sampler = Sampler::new(100.0, 1.0).with_jitter(0.002, 7);
+        let nominal: Vec<f32> = Sampler::new(100.0, 1.0).times().collect();
+        let jittered: Vec<f32> = sampler.times().collect();
+
+        assert!(jittered
+            .iter()
+            .zip(&nominal)
+            .all(|(t, n)| (t - n).abs() <= 0.002 + 1e-6));
+        assert!(jittered.iter().zip(&nominal).any(|(t, n)| t != n));
+        assert_eq!(jittered, sampler.times().collect::<Vec<_>>());
+
+        // Limitado a meio período
+        let wide: Vec<f32> = Sampler::new(100.0, 1.0)
+            .with_jitter(1.0, 7)
+            .times()
+            .collect();
+        assert!(wide.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+
+    #[test]
+    fn test_decimate_and_resample() {
+        let ramp = Ramp::new(0.0, 2.0);
+        let samples = Sampler::new(100.0, 1.0).samples(&ramp);
+
+        let decimated = samples.decimate(10);
+        assert_eq!(decimated.len(), 11);
+        assert_eq!(decimated.time[1], samples.time[10]);
+
+        // Linear reconstrói a rampa; o segurador fica no valor da amostra anterior
+        let fine = Sampler::new(1000.0, 1.0);
+        let linear = decimated.resample(&fine, Interpolation::Linear);
+        let hold = decimated.resample(&fine, Interpolation::Hold);
+        assert_eq!(linear.len(), 1001);
+        assert!((linear.values[155] - 0.31).abs() < 1e-4);
+        assert!((hold.values[155] - 0.2).abs() < 1e-4);
+
+        assert!(Samples::default()
+            .resample(&fine, Interpolation::Linear)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_window() {
+        let samples = Sampler::with_count(1.0, 5).samples(&Sine::new(4.0, 1.0, 0.0).offset(1.0));
+        let windowed = samples.window(Window::Hann);
+        assert_eq!(windowed.time, samples.time);
+        assert_eq!(windowed.values[0], 0.0);
+        assert_eq!(windowed.values[2], samples.values[2]);
+    }
+
+    #[test]
+    fn test_frames() {
+        let samples = Sampler::with_count(1.0, 10).samples(&Ramp::new(0.0, 1.0));
+        let frames: Vec<Samples> = samples.frames(4, 3).collect();
+
+        assert_eq!(frames.len(), 3);
+        assert_eq!(frames[1].values, [3.0, 4.0, 5.0, 6.0]);
+        assert_eq!(frames[2].time[3], 9.0);
+        assert_eq!(samples.frames(20, 1).count(), 0);
+    }
+
+    #[test]
+    fn test_filter() {
+        use crate::dsp::filter::MovingAverage;
+
+        let samples = Sampler::with_count(1.0, 4).samples(&Ramp::new(0.0, 2.0));
+        let mut average = MovingAverage::<2>::new();
+        let filtered = samples.filter(&mut average);
+
+        assert_eq!(filtered.time, samples.time);
+        assert_eq!(filtered.values, [0.0, 1.0, 3.0, 5.0]);
+    }
+}
@@ -1,24 +1,32 @@
-use crate::signals::Wave;
+use crate::numeric::Number;
+use crate::signals::{cycle_position, Wave};
 
-pub struct SawTooth {
-    period: f32,
-    amplitude: f32,
-    offset: f32,
+pub struct SawTooth<T = f32> {
+    period: T,
+    amplitude: T,
+    offset: T,
+    phase: T,
 }
 
-impl SawTooth {
-    pub fn new(period: f32, amplitude: f32, offset: f32) -> Self {
+impl<T: Number> SawTooth<T> {
+    pub fn new(period: T, amplitude: T, offset: T) -> Self {
         Self {
             period,
             amplitude,
             offset,
+            phase: T::ZERO,
         }
     }
+
+    /// Fase inicial, em radianos.
+    pub fn with_phase(self, phase: T) -> Self {
+        Self { phase, ..self }
+    }
 }
 
-impl Wave for SawTooth {
-    fn compute(&self, t: f32) -> f32 {
-        let now = t % self.period;
+impl<T: Number> Wave<T> for SawTooth<T> {
+    fn compute(&self, t: T) -> T {
+        let now = cycle_position(t, self.period, self.phase);
 
         (self.amplitude * (now / self.period)) + self.offset
     }
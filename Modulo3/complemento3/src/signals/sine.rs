@@ -1,25 +1,34 @@
-use crate::signals::Wave;
+use crate::numeric::Number;
+use crate::signals::{cycle_position, Wave};
 
-pub struct Sine {
-    period: f32,
-    amplitude: f32,
-    offset: f32,
+pub struct Sine<T = f32> {
+    period: T,
+    amplitude: T,
+    offset: T,
+    phase: T,
 }
 
-impl Sine {
-    pub fn new(period: f32, amplitude: f32, offset: f32) -> Self {
+impl<T: Number> Sine<T> {
+    pub fn new(period: T, amplitude: T, offset: T) -> Self {
         Self {
             period,
             amplitude,
             offset,
+            phase: T::ZERO,
         }
     }
+
+    /// Fase inicial, em radianos.
+    pub fn with_phase(self, phase: T) -> Self {
+        Self { phase, ..self }
+    }
 }
 
-impl Wave for Sine {
-    fn compute(&self, t: f32) -> f32 {
-        let pi = std::f32::consts::PI;
+impl<T: Number> Wave<T> for Sine<T> {
+    fn compute(&self, t: T) -> T {
+        // Reduzir ao período antes evita estourar o ponto fixo com `t` grande
+        let now = cycle_position(t, self.period, self.phase) / self.period;
 
-        (self.amplitude * f32::sin(2.0 * pi * (1.0 / self.period) * t)) + self.offset
+        (self.amplitude * (T::TAU * now).sin()) + self.offset
     }
 }
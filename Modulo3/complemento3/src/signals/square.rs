@@ -1,25 +1,39 @@
-use crate::signals::Wave;
+use crate::numeric::Number;
+use crate::signals::{cycle_position, Wave};
 
-pub struct SquareWave {
-    period: f32,
-    amplitude: f32,
-    offset: f32,
+/// Onda quadrada com 50% de ciclo ativo, começando em nível baixo. Para outro ciclo ativo use
+/// [`Pwm`](crate::signals::pwm::Pwm).
+pub struct SquareWave<T = f32> {
+    period: T,
+    amplitude: T,
+    offset: T,
+    phase: T,
 }
 
-impl SquareWave {
-    pub fn new(period: f32, amplitude: f32, offset: f32) -> Self {
+impl<T: Number> SquareWave<T> {
+    pub fn new(period: T, amplitude: T, offset: T) -> Self {
         Self {
             period,
             amplitude,
             offset,
+            phase: T::ZERO,
         }
     }
+
+    /// Fase inicial, em radianos. Com `PI` a onda começa em nível alto.
+    pub fn with_phase(self, phase: T) -> Self {
+        Self { phase, ..self }
+    }
 }
 
-impl Wave for SquareWave {
-    fn compute(&self, t: f32) -> f32 {
-        let now = t % self.period;
-        let state = if now >= (self.period / 2.0) { 1.0 } else { 0.0 };
+impl<T: Number> Wave<T> for SquareWave<T> {
+    fn compute(&self, t: T) -> T {
+        let now = cycle_position(t, self.period, self.phase);
+        let state = if now >= (self.period / T::from_int(2)) {
+            T::ONE
+        } else {
+            T::ZERO
+        };
 
         (self.amplitude * state) + self.offset
     }
@@ -0,0 +1,119 @@
+//! Sinais de teste clássicos de controle: degrau, rampa e impulso.
+//!
+//! Não são periódicos, então em vez de fase recebem o instante `at` em que começam.
+
+use crate::numeric::Number;
+use crate::signals::Wave;
+
+/// Degrau: `0` antes de `at` e `amplitude` a partir de `at`.
+pub struct Step<T = f32> {
+    at: T,
+    amplitude: T,
+}
+
+impl<T: Number> Step<T> {
+    pub fn new(at: T, amplitude: T) -> Self {
+        Self { at, amplitude }
+    }
+
+    /// Degrau unitário em `t = 0`.
+    pub fn unit() -> Self {
+        Self::new(T::ZERO, T::ONE)
+    }
+}
+
+impl<T: Number> Wave<T> for Step<T> {
+    fn compute(&self, t: T) -> T {
+        if t >= self.at {
+            self.amplitude
+        } else {
+            T::ZERO
+        }
+    }
+}
+
+/// Rampa: `0` antes de `at` e `slope * (t - at)` depois.
+pub struct Ramp<T = f32> {
+    at: T,
+    slope: T,
+}
+
+impl<T: Number> Ramp<T> {
+    pub fn new(at: T, slope: T) -> Self {
+        Self { at, slope }
+    }
+}
+
+impl<T: Number> Wave<T> for Ramp<T> {
+    fn compute(&self, t: T) -> T {
+        if t >= self.at {
+            self.slope * (t - self.at)
+        } else {
+            T::ZERO
+        }
+    }
+}
+
+/// Impulso com área `area` em `at`.
+///
+/// Um impulso ideal não tem como ser amostrado, então ele é um pulso retangular de largura
+/// `width` e altura `area / width`. Use como `width` o passo da simulação, para que exatamente
+/// uma amostra caia dentro do pulso.
+pub struct Impulse {
+    at: f32,
+    area: f32,
+    width: f32,
+}
+
+impl Impulse {
+    pub fn new(at: f32, area: f32, width: f32) -> Self {
+        Self { at, area, width }
+    }
+}
+
+impl Wave for Impulse {
+    fn compute(&self, t: f32) -> f32 {
+        if t >= self.at && t < self.at + self.width {
+            self.area / self.width
+        } else {
+            0.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_step() {
+        let step = Step::new(1.0, 2.5);
+
+        assert_eq!(step.compute(0.99), 0.0);
+        assert_eq!(step.compute(1.0), 2.5);
+        assert_eq!(step.compute(100.0), 2.5);
+        assert_eq!(Step::unit().compute(0.0), 1.0);
+        assert_eq!(Step::unit().compute(-0.1), 0.0);
+    }
+
+    #[test]
+    fn test_ramp() {
+        let ramp = Ramp::new(1.0, 2.0);
+
+        assert_eq!(ramp.compute(0.5), 0.0);
+        assert_eq!(ramp.compute(1.0), 0.0);
+        assert_eq!(ramp.compute(3.0), 4.0);
+    }
+
+    #[test]
+    fn test_impulse_area() {
+        let dt = 0.01;
+        let impulse = Impulse::new(0.5, 3.0, dt);
+
+        let samples: Vec<f32> = (0..100).map(|i| impulse.compute(i as f32 * dt)).collect();
+        let area: f32 = samples.iter().map(|v| v * dt).sum();
+
+        assert_eq!(samples.iter().filter(|&&v| v != 0.0).count(), 1);
+        assert!((area - 3.0).abs() < 1e-4);
+    }
+}
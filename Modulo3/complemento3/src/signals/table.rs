@@ -0,0 +1,94 @@
+use crate::signals::{cycle_position, Wave};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interpolation {
+    /// Mantém o valor da amostra até a próxima (segurador de ordem zero).
+    Hold,
+    /// Liga as amostras com retas, voltando da última para a primeira no fim do período.
+    Linear,
+}
+
+/// Forma de onda arbitrária definida por uma tabela de amostras igualmente espaçadas em um
+/// período, repetida periodicamente.
+pub struct Table {
+    samples: Vec<f32>,
+    period: f32,
+    interpolation: Interpolation,
+    phase: f32,
+}
+
+impl Table {
+    /// !!! `samples` não pode ser vazio.
+    pub fn new(samples: Vec<f32>, period: f32, interpolation: Interpolation) -> Self {
+        assert!(!samples.is_empty(), "table without samples");
+
+        Self {
+            samples,
+            period,
+            interpolation,
+            phase: 0.0,
+        }
+    }
+
+    /// Fase inicial, em radianos.
+    pub fn with_phase(self, phase: f32) -> Self {
+        Self { phase, ..self }
+    }
+}
+
+impl Wave for Table {
+    fn compute(&self, t: f32) -> f32 {
+        let len = self.samples.len();
+        let position = cycle_position(t, self.period, self.phase) / self.period * len as f32;
+        // `min` protege do arredondamento quando `position` fica muito perto de `len`
+        let index = (position as usize).min(len - 1);
+
+        match self.interpolation {
+            Interpolation::Hold => self.samples[index],
+            Interpolation::Linear => {
+                let next = self.samples[(index + 1) % len];
+                let fraction = position - index as f32;
+                self.samples[index] + (next - self.samples[index]) * fraction
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::PI;
+
+    fn table(interpolation: Interpolation) -> Table {
+        Table::new(vec![0.0, 1.0, 4.0, 2.0], 4.0, interpolation)
+    }
+
+    #[test]
+    fn test_hold() {
+        let wave = table(Interpolation::Hold);
+
+        assert_eq!(wave.compute(0.0), 0.0);
+        assert_eq!(wave.compute(1.5), 1.0);
+        assert_eq!(wave.compute(2.0), 4.0);
+        assert_eq!(wave.compute(3.9), 2.0);
+        assert_eq!(wave.compute(5.0), 1.0);
+    }
+
+    #[test]
+    fn test_linear() {
+        let wave = table(Interpolation::Linear);
+
+        assert_eq!(wave.compute(1.5), 2.5);
+        assert_eq!(wave.compute(2.5), 3.0);
+        // Do último valor volta para o primeiro
+        assert_eq!(wave.compute(3.5), 1.0);
+    }
+
+    #[test]
+    fn test_phase() {
+        // Um quarto de período adiantado: começa na segunda amostra
+        let wave = table(Interpolation::Hold).with_phase(PI / 2.0);
+
+        assert_eq!(wave.compute(0.0), 1.0);
+    }
+}
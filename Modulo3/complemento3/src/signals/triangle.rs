@@ -0,0 +1,68 @@
+use crate::numeric::Number;
+use crate::signals::{cycle_position, Wave};
+
+/// Onda triangular que sobe de `offset` até `offset + amplitude` na primeira metade do período e
+/// desce na segunda.
+pub struct Triangle<T = f32> {
+    period: T,
+    amplitude: T,
+    offset: T,
+    phase: T,
+}
+
+impl<T: Number> Triangle<T> {
+    pub fn new(period: T, amplitude: T, offset: T) -> Self {
+        Self {
+            period,
+            amplitude,
+            offset,
+            phase: T::ZERO,
+        }
+    }
+
+    /// Fase inicial, em radianos.
+    pub fn with_phase(self, phase: T) -> Self {
+        Self { phase, ..self }
+    }
+}
+
+impl<T: Number> Wave<T> for Triangle<T> {
+    fn compute(&self, t: T) -> T {
+        let now = cycle_position(t, self.period, self.phase) / self.period;
+        let two = T::from_int(2);
+        let level = if now * two < T::ONE {
+            two * now
+        } else {
+            two - two * now
+        };
+
+        (self.amplitude * level) + self.offset
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::PI;
+
+    #[test]
+    fn test_triangle() {
+        let wave = Triangle::new(2.0, 4.0, -1.0);
+
+        assert_eq!(wave.compute(0.0), -1.0);
+        assert_eq!(wave.compute(0.5), 1.0);
+        assert_eq!(wave.compute(1.0), 3.0);
+        assert_eq!(wave.compute(1.5), 1.0);
+        assert_eq!(wave.compute(2.0), -1.0);
+        assert_eq!(wave.compute(-0.5), 1.0);
+    }
+
+    #[test]
+    fn test_phase() {
+        // Meio período adiantado: começa no pico
+        let wave = Triangle::new(2.0, 1.0, 0.0).with_phase(PI);
+
+        assert_eq!(wave.compute(0.0), 1.0);
+        assert_eq!(wave.compute(1.0), 0.0);
+    }
+}
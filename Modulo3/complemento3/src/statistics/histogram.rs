@@ -0,0 +1,142 @@
+//! Histograma com faixas de mesmo tamanho, em um array de tamanho fixo.
+
+use super::Statistic;
+
+/// Conta as amostras em `N` faixas iguais de `[min, max]`. O próprio `max` cai na última
+/// faixa; o que fica fora do intervalo é contado à parte, em [`below`](Histogram::below) e
+/// [`above`](Histogram::above).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Histogram<const N: usize> {
+    min: f32,
+    max: f32,
+    bins: [u32; N],
+    below: u32,
+    above: u32,
+}
+
+impl<const N: usize> Histogram<N> {
+    /// !!! Precisa de `min < max` e pelo menos uma faixa.
+    pub fn new(min: f32, max: f32) -> Self {
+        assert!(N > 0, "histogram without bins");
+        assert!(min < max, "histogram range must be increasing");
+        Self {
+            min,
+            max,
+            bins: [0; N],
+            below: 0,
+            above: 0,
+        }
+    }
+
+    pub fn bins(&self) -> &[u32; N] {
+        &self.bins
+    }
+
+    /// Início e fim da faixa `k`.
+    pub fn bin_range(&self, k: usize) -> (f32, f32) {
+        let width = (self.max - self.min) / N as f32;
+        (
+            self.min + k as f32 * width,
+            self.min + (k + 1) as f32 * width,
+        )
+    }
+
+    /// Amostras menores que `min`.
+    pub fn below(&self) -> u32 {
+        self.below
+    }
+
+    /// Amostras maiores que `max`.
+    pub fn above(&self) -> u32 {
+        self.above
+    }
+
+    /// Todas as amostras contadas, dentro e fora do intervalo.
+    pub fn count(&self) -> u32 {
+        self.bins
+            .iter()
+            .fold(self.below.saturating_add(self.above), |n, &bin| {
+                n.saturating_add(bin)
+            })
+    }
+
+    /// Índice da faixa com mais amostras (a primeira, em caso de empate).
+    pub fn mode(&self) -> usize {
+        let max = self.bins.iter().max().copied().unwrap_or(0);
+        self.bins.iter().position(|&n| n == max).unwrap_or(0)
+    }
+}
+
+impl<const N: usize> Statistic for Histogram<N> {
+    /// `NaN` é ignorado.
+    fn update(&mut self, x: f32) {
+        if x.is_nan() {
+            return;
+        }
+        if x < self.min {
+            self.below = self.below.saturating_add(1);
+        } else if x > self.max {
+            self.above = self.above.saturating_add(1);
+        } else {
+            let k = ((x - self.min) / (self.max - self.min) * N as f32) as usize;
+            let bin = &mut self.bins[k.min(N - 1)];
+            *bin = bin.saturating_add(1);
+        }
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new(self.min, self.max);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signals::noise::Rng;
+
+    #[test]
+    fn test_edges() {
+        let mut histogram = Histogram::<4>::new(0.0, 2.0);
+        histogram.extend([0.0, 0.49, 0.5, 1.99, 2.0, -0.1, 2.1, f32::NAN]);
+
+        assert_eq!(histogram.bins(), &[2, 1, 0, 2]);
+        assert_eq!(histogram.below(), 1);
+        assert_eq!(histogram.above(), 1);
+        assert_eq!(histogram.count(), 7);
+        assert_eq!(histogram.bin_range(1), (0.5, 1.0));
+
+        histogram.below = u32::MAX;
+        histogram.update(-1.0);
+        assert_eq!(histogram.below(), u32::MAX);
+        assert_eq!(histogram.count(), u32::MAX);
+
+        histogram.reset();
+        assert_eq!(histogram.count(), 0);
+        assert_eq!(histogram.bin_range(3), (1.5, 2.0));
+    }
+
+    #[test]
+    fn test_against_batch_counting() {
+        let mut rng = Rng::new(13);
+        let data: Vec<f32> = (0..10_000).map(|_| rng.gaussian()).collect();
+        let mut histogram = Histogram::<16>::new(-3.0, 3.0);
+        histogram.extend(data.iter().copied());
+
+        for k in 0..16 {
+            let (start, end) = histogram.bin_range(k);
+            let expected = data
+                .iter()
+                .filter(|&&x| start <= x && (x < end || (k == 15 && x <= end)))
+                .count();
+            assert_eq!(histogram.bins()[k] as usize, expected, "bin {k}");
+        }
+        assert_eq!(
+            histogram.below() as usize,
+            data.iter().filter(|&&x| x < -3.0).count()
+        );
+        assert_eq!(histogram.count(), 10_000);
+
+        // A gaussiana concentra em volta de 0, nas faixas do meio
+        assert!([7, 8].contains(&histogram.mode()));
+    }
+}
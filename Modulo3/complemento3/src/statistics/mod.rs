@@ -0,0 +1,442 @@
+//! Estatísticas que se atualizam a cada amostra, sem guardar a série: média e variância,
+//! mínimo e máximo, RMS, quantis e histograma.
+//!
+//! O `micromath::statistics` usado em `aula5_stm32` precisa do array inteiro na memória. Aqui
+//! cada estatística ocupa alguns bytes e pode ficar rodando no dispositivo, para ele relatar a
+//! saúde do sensor (ruído, picos, deriva) a cada tantas leituras:
+//!
+//! ```ignore
+//! let mut health = Summary::new();
+//! for reading in sensor {
+//!     health.update(reading);
+//! }
+//! rprintln!("{}", health);
+//! health.reset();
+//! ```
+//!
+//! !!! Como os filtros, só usa `core` e o `libm`, sem alocação.
+//!
+//! Uma leitura `NaN` (sensor desconectado, divisão por zero na conversão) é ignorada por todas
+//! as estatísticas: senão a média e o RMS ficariam `NaN` até o próximo `reset`. As contagens de
+//! [`Moments`], [`Rms`] e [`Histogram`] param em `u32::MAX` em vez de dar a volta.
+
+pub mod histogram;
+pub mod quantile;
+
+pub use histogram::Histogram;
+pub use quantile::Quantile;
+
+use core::fmt::{Display, Formatter};
+
+/// Estatística calculada uma amostra por vez.
+pub trait Statistic {
+    fn update(&mut self, x: f32);
+
+    /// Esquece as amostras anteriores.
+    fn reset(&mut self);
+
+    /// Atualiza com todas as amostras, em ordem.
+    fn extend<I: IntoIterator<Item = f32>>(&mut self, samples: I)
+    where
+        Self: Sized,
+    {
+        samples.into_iter().for_each(|x| self.update(x));
+    }
+}
+
+/// Média e variância pelo algoritmo de Welford, que não perde precisão quando a média é
+/// grande perto do desvio (somar `x²` e depois subtrair `média²` perderia).
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Moments {
+    count: u32,
+    mean: f32,
+    /// Soma dos quadrados das diferenças para a média.
+    m2: f32,
+}
+
+impl Moments {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    pub fn mean(&self) -> Option<f32> {
+        (self.count > 0).then_some(self.mean)
+    }
+
+    /// Variância amostral (divide por `n - 1`), como a do `micromath`. Precisa de pelo menos
+    /// duas amostras.
+    pub fn variance(&self) -> Option<f32> {
+        (self.count > 1).then(|| self.m2 / (self.count - 1) as f32)
+    }
+
+    /// Variância da população (divide por `n`).
+    pub fn population_variance(&self) -> Option<f32> {
+        (self.count > 0).then(|| self.m2 / self.count as f32)
+    }
+
+    /// Desvio padrão amostral.
+    pub fn std_dev(&self) -> Option<f32> {
+        self.variance().map(libm::sqrtf)
+    }
+}
+
+impl Statistic for Moments {
+    /// `NaN` é ignorado.
+    fn update(&mut self, x: f32) {
+        if x.is_nan() {
+            return;
+        }
+        self.count = self.count.saturating_add(1);
+        let delta = x - self.mean;
+        self.mean += delta / self.count as f32;
+        self.m2 += delta * (x - self.mean);
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+/// Menor e maior valor.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MinMax {
+    extremes: Option<(f32, f32)>,
+}
+
+impl MinMax {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn min(&self) -> Option<f32> {
+        self.extremes.map(|(min, _)| min)
+    }
+
+    pub fn max(&self) -> Option<f32> {
+        self.extremes.map(|(_, max)| max)
+    }
+
+    /// `max - min`, a amplitude pico a pico.
+    pub fn range(&self) -> Option<f32> {
+        self.extremes.map(|(min, max)| max - min)
+    }
+}
+
+impl Statistic for MinMax {
+    /// `NaN` é ignorado.
+    fn update(&mut self, x: f32) {
+        if x.is_nan() {
+            return;
+        }
+        self.extremes = Some(match self.extremes {
+            Some((min, max)) => (min.min(x), max.max(x)),
+            None => (x, x),
+        });
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+/// Valor eficaz, `√(Σx² / n)`.
+///
+/// Guarda a média dos quadrados e não a soma, para não perder as amostras novas quando a
+/// soma fica grande.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Rms {
+    count: u32,
+    mean_square: f32,
+}
+
+impl Rms {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn rms(&self) -> Option<f32> {
+        (self.count > 0).then(|| libm::sqrtf(self.mean_square))
+    }
+}
+
+impl Statistic for Rms {
+    /// `NaN` é ignorado.
+    fn update(&mut self, x: f32) {
+        if x.is_nan() {
+            return;
+        }
+        self.count = self.count.saturating_add(1);
+        self.mean_square += (x * x - self.mean_square) / self.count as f32;
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+/// Média, desvio, extremos e RMS juntos, para um relatório de saúde do sensor.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Summary {
+    pub moments: Moments,
+    pub extremes: MinMax,
+    pub rms: Rms,
+}
+
+impl Summary {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn count(&self) -> u32 {
+        self.moments.count()
+    }
+}
+
+impl Statistic for Summary {
+    fn update(&mut self, x: f32) {
+        self.moments.update(x);
+        self.extremes.update(x);
+        self.rms.update(x);
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+impl Display for Summary {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "n = {}", self.count())?;
+        let values = [
+            ("média", self.moments.mean()),
+            ("desvio", self.moments.std_dev()),
+            ("mín", self.extremes.min()),
+            ("máx", self.extremes.max()),
+            ("rms", self.rms.rms()),
+        ];
+        for (name, value) in values {
+            if let Some(value) = value {
+                write!(f, ", {name} = {value:.6}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signals::noise::Rng;
+
+    /// O array de `embed_math` em `aula5_stm32`: uma gaussiana amostrada, copiada como está.
+    #[allow(clippy::excessive_precision)]
+    const DATA: [f32; 61] = [
+        0.001752830049,
+        0.002239453029,
+        0.002832703774,
+        0.003547459285,
+        0.004398359598,
+        0.005399096651,
+        0.006561581477,
+        0.00789501583,
+        0.009404907738,
+        0.01109208347,
+        0.01295175957,
+        0.01497274656,
+        0.01713685920,
+        0.01941860550,
+        0.02178521770,
+        0.02419707245,
+        0.02660852499,
+        0.02896915528,
+        0.03122539334,
+        0.03332246029,
+        0.03520653268,
+        0.03682701403,
+        0.03813878155,
+        0.03910426940,
+        0.03969525475,
+        0.03989422804,
+        0.03969525475,
+        0.03910426940,
+        0.03813878155,
+        0.03682701403,
+        0.03520653268,
+        0.03332246029,
+        0.03122539334,
+        0.02896915528,
+        0.02660852499,
+        0.02419707245,
+        0.02178521770,
+        0.01941860550,
+        0.01713685920,
+        0.01497274656,
+        0.01295175957,
+        0.01109208347,
+        0.009404907738,
+        0.00789501583,
+        0.006561581477,
+        0.005399096651,
+        0.004398359598,
+        0.003547459285,
+        0.002832703774,
+        0.002239453029,
+        0.001752830049,
+        0.001358296923,
+        0.001042093481,
+        0.0007915451583,
+        0.000595253242,
+        0.0004431848412,
+        0.0003266819056,
+        0.0002384088201,
+        0.0001722568939,
+        0.0001232219168,
+        0.0000872682695,
+    ];
+
+    /// Média e variância amostral calculadas com a série inteira, em `f64`.
+    fn batch(data: &[f32]) -> (f64, f64) {
+        let n = data.len() as f64;
+        let mean = data.iter().map(|&x| x as f64).sum::<f64>() / n;
+        let variance = data.iter().map(|&x| (x as f64 - mean).powi(2)).sum::<f64>() / (n - 1.0);
+        (mean, variance)
+    }
+
+    fn assert_relative(actual: f32, expected: f64, tolerance: f64) {
+        let error = ((actual as f64 - expected) / expected).abs();
+        assert!(error < tolerance, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn test_moments_against_batch() {
+        let mut moments = Moments::new();
+        moments.extend(DATA);
+        let (mean, variance) = batch(&DATA);
+
+        assert_eq!(moments.count(), 61);
+        assert_relative(moments.mean().unwrap(), mean, 1e-6);
+        assert_relative(moments.variance().unwrap(), variance, 1e-5);
+        assert_relative(moments.std_dev().unwrap(), variance.sqrt(), 1e-5);
+        assert_relative(
+            moments.population_variance().unwrap(),
+            variance * 60.0 / 61.0,
+            1e-5,
+        );
+
+        // As constantes que `embed_math` compara com o `micromath`
+        assert!((moments.mean().unwrap() - 0.0163).abs() < 1e-4);
+        assert_relative(moments.std_dev().unwrap(), 0.0141383216, 1e-4);
+        assert_relative(moments.variance().unwrap(), 0.0001998921375, 1e-4);
+    }
+
+    #[test]
+    fn test_moments_edge_cases() {
+        let mut moments = Moments::new();
+        assert_eq!(moments.mean(), None);
+        assert_eq!(moments.population_variance(), None);
+
+        moments.update(3.0);
+        assert_eq!(moments.mean(), Some(3.0));
+        assert_eq!(moments.variance(), None);
+        assert_eq!(moments.population_variance(), Some(0.0));
+
+        // O exemplo da documentação do `micromath`
+        moments.reset();
+        moments.extend([1.0, 3.0, 5.0]);
+        assert_eq!(moments.variance(), Some(4.0));
+    }
+
+    /// Leituras de 1000 com ruído de 0,01: em `f32`, Σx²/n - média² perderia tudo.
+    #[test]
+    fn test_moments_with_large_offset() {
+        let mut rng = Rng::new(11);
+        let data: Vec<f32> = (0..100_000)
+            .map(|_| 1000.0 + 0.01 * rng.gaussian())
+            .collect();
+        let mut moments = Moments::new();
+        moments.extend(data.iter().copied());
+        let (mean, variance) = batch(&data);
+
+        assert_relative(moments.mean().unwrap(), mean, 1e-6);
+        assert_relative(moments.variance().unwrap(), variance, 0.01);
+    }
+
+    #[test]
+    fn test_min_max_and_rms() {
+        let mut extremes = MinMax::new();
+        assert_eq!(extremes.range(), None);
+        extremes.extend(DATA);
+        extremes.update(f32::NAN);
+
+        let min = DATA.iter().copied().fold(f32::INFINITY, f32::min);
+        let max = DATA.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+        assert_eq!(extremes.min(), Some(min));
+        assert_eq!(extremes.max(), Some(max));
+        assert_eq!(extremes.range(), Some(max - min));
+
+        let mut rms = Rms::new();
+        rms.extend(DATA);
+        let exact = (DATA.iter().map(|&x| (x as f64).powi(2)).sum::<f64>() / 61.0).sqrt();
+        assert_relative(rms.rms().unwrap(), exact, 1e-5);
+
+        // Senoide de amplitude 1: RMS 1/√2
+        let mut rms = Rms::new();
+        rms.extend((0..1000).map(|k| (std::f32::consts::TAU * k as f32 / 100.0).sin()));
+        assert!((rms.rms().unwrap() - std::f32::consts::FRAC_1_SQRT_2).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_summary() {
+        assert_eq!(Summary::new().to_string(), "n = 0");
+
+        let mut summary = Summary::new();
+        summary.extend([1.0, 3.0, 5.0]);
+        assert_eq!(summary.count(), 3);
+        assert_eq!(summary.moments.mean(), Some(3.0));
+        assert_eq!(summary.extremes.range(), Some(4.0));
+        assert_eq!(
+            summary.to_string(),
+            "n = 3, média = 3.000000, desvio = 2.000000, mín = 1.000000, máx = 5.000000, \
+             rms = 3.415650"
+        );
+
+        summary.reset();
+        assert_eq!(summary, Summary::new());
+    }
+
+    /// Um `NaN` no meio das leituras não contamina o resto nem entra na contagem.
+    #[test]
+    fn test_nan_is_skipped() {
+        let mut summary = Summary::new();
+        summary.extend([1.0, f32::NAN, 3.0, 5.0]);
+
+        assert_eq!(summary.count(), 3);
+        assert_eq!(summary.moments.mean(), Some(3.0));
+        assert_eq!(summary.moments.variance(), Some(4.0));
+        assert_eq!(summary.extremes.range(), Some(4.0));
+        assert_relative(summary.rms.rms().unwrap(), (35.0f64 / 3.0).sqrt(), 1e-6);
+    }
+
+    #[test]
+    fn test_count_saturates() {
+        let mut moments = Moments {
+            count: u32::MAX - 1,
+            mean: 2.0,
+            m2: 0.0,
+        };
+        moments.extend([2.0, 2.0]);
+        assert_eq!(moments.count(), u32::MAX);
+        assert_eq!(moments.mean(), Some(2.0));
+
+        let mut rms = Rms {
+            count: u32::MAX,
+            mean_square: 4.0,
+        };
+        rms.update(2.0);
+        assert_eq!(rms.rms(), Some(2.0));
+    }
+}
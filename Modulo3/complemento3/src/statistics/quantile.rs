@@ -0,0 +1,227 @@
+//! Quantis estimados pelo algoritmo P² (Jain e Chlamtac, 1985), com cinco marcadores e sem
+//! guardar as amostras.
+
+use super::Statistic;
+
+/// Estimativa do quantil `p` (0,5 é a mediana, 0,99 o percentil 99).
+///
+/// Guarda cinco marcadores: o mínimo, `p/2`, `p`, `(1+p)/2` e o máximo. A cada amostra as
+/// posições desejadas andam e os marcadores do meio são ajustados por uma parábola pelos
+/// vizinhos, então a estimativa converge sem ordenar nada.
+///
+/// !!! As posições desejadas são `f32`: depois de uns 16 milhões de amostras o incremento se
+/// perde no arredondamento. Para janelas longas, chame [`Statistic::reset`] de tempos em
+/// tempos.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quantile {
+    p: f32,
+    count: u32,
+    /// Alturas dos marcadores. Antes de 5 amostras, as próprias amostras.
+    heights: [f32; 5],
+    /// Posições dos marcadores, contando de 1.
+    positions: [u32; 5],
+    desired: [f32; 5],
+}
+
+impl Quantile {
+    /// !!! `p` precisa estar em `[0, 1]`.
+    pub fn new(p: f32) -> Self {
+        assert!((0.0..=1.0).contains(&p), "quantile must be in [0, 1]");
+        Self {
+            p,
+            count: 0,
+            heights: [0.0; 5],
+            positions: [1, 2, 3, 4, 5],
+            desired: [1.0, 1.0 + 2.0 * p, 1.0 + 4.0 * p, 3.0 + 2.0 * p, 5.0],
+        }
+    }
+
+    pub fn median() -> Self {
+        Self::new(0.5)
+    }
+
+    pub fn p(&self) -> f32 {
+        self.p
+    }
+
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// O quantil estimado. Com menos de cinco amostras é o exato, interpolado entre as
+    /// amostras ordenadas.
+    pub fn estimate(&self) -> Option<f32> {
+        match self.count {
+            0 => None,
+            n if n < 5 => {
+                let mut sorted = self.heights;
+                let sorted = &mut sorted[..n as usize];
+                sort(sorted);
+                let rank = self.p * (n - 1) as f32;
+                let below = rank as usize;
+                let above = (below + 1).min(sorted.len() - 1);
+                let fraction = rank - below as f32;
+                Some(sorted[below] + (sorted[above] - sorted[below]) * fraction)
+            }
+            _ => Some(self.heights[2]),
+        }
+    }
+
+    /// Move o marcador `i` uma posição para `direction` (±1).
+    fn adjust(&mut self, i: usize, direction: i32) {
+        let q = &self.heights;
+        let n = self.positions.map(|n| n as f32);
+        let d = direction as f32;
+
+        let parabolic = q[i]
+            + d / (n[i + 1] - n[i - 1])
+                * ((n[i] - n[i - 1] + d) * (q[i + 1] - q[i]) / (n[i + 1] - n[i])
+                    + (n[i + 1] - n[i] - d) * (q[i] - q[i - 1]) / (n[i] - n[i - 1]));
+
+        self.heights[i] = if q[i - 1] < parabolic && parabolic < q[i + 1] {
+            parabolic
+        } else {
+            // A parábola sairia de ordem; usa a reta até o vizinho
+            let j = (i as i32 + direction) as usize;
+            q[i] + d * (q[j] - q[i]) / (n[j] - n[i])
+        };
+        self.positions[i] = self.positions[i].wrapping_add_signed(direction);
+    }
+}
+
+impl Statistic for Quantile {
+    /// `NaN` é ignorado.
+    fn update(&mut self, x: f32) {
+        if x.is_nan() {
+            return;
+        }
+        if self.count < 5 {
+            self.heights[self.count as usize] = x;
+            self.count += 1;
+            if self.count == 5 {
+                sort(&mut self.heights);
+            }
+            return;
+        }
+        self.count += 1;
+
+        // Célula `k` em que a amostra caiu, estendendo os extremos se preciso
+        let q = &mut self.heights;
+        let k = if x < q[0] {
+            q[0] = x;
+            0
+        } else if x >= q[4] {
+            q[4] = x;
+            3
+        } else {
+            (1..4).take_while(|&i| x >= q[i]).count()
+        };
+
+        self.positions[k + 1..].iter_mut().for_each(|n| *n += 1);
+        let increments = [0.0, self.p / 2.0, self.p, (1.0 + self.p) / 2.0, 1.0];
+        for (desired, increment) in self.desired.iter_mut().zip(increments) {
+            *desired += increment;
+        }
+
+        for i in 1..4 {
+            let offset = self.desired[i] - self.positions[i] as f32;
+            let room_after = self.positions[i + 1] - self.positions[i] > 1;
+            let room_before = self.positions[i] - self.positions[i - 1] > 1;
+            if offset >= 1.0 && room_after {
+                self.adjust(i, 1);
+            } else if offset <= -1.0 && room_before {
+                self.adjust(i, -1);
+            }
+        }
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new(self.p);
+    }
+}
+
+/// Ordenação por inserção, para os no máximo cinco valores dos marcadores sem depender de
+/// `std`.
+fn sort(values: &mut [f32]) {
+    for i in 1..values.len() {
+        let mut j = i;
+        while j > 0 && values[j - 1] > values[j] {
+            values.swap(j - 1, j);
+            j -= 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signals::noise::Rng;
+
+    /// Quantil exato da série inteira, com a mesma interpolação de [`Quantile::estimate`].
+    fn exact(data: &[f32], p: f32) -> f32 {
+        let mut sorted = data.to_vec();
+        sorted.sort_by(f32::total_cmp);
+        let rank = p * (sorted.len() - 1) as f32;
+        let below = rank as usize;
+        let above = (below + 1).min(sorted.len() - 1);
+        sorted[below] + (sorted[above] - sorted[below]) * (rank - below as f32)
+    }
+
+    #[test]
+    fn test_few_samples_are_exact() {
+        let mut median = Quantile::median();
+        assert_eq!(median.estimate(), None);
+
+        median.extend([5.0, 1.0]);
+        assert_eq!(median.estimate(), Some(3.0));
+        median.update(4.0);
+        assert_eq!(median.estimate(), Some(4.0));
+
+        let mut max = Quantile::new(1.0);
+        max.extend([2.0, 7.0, -1.0, 3.0]);
+        assert_eq!(max.estimate(), Some(7.0));
+    }
+
+    #[test]
+    fn test_against_sorted_gaussian() {
+        let mut rng = Rng::new(5);
+        let data: Vec<f32> = (0..10_000).map(|_| rng.gaussian()).collect();
+
+        // Tolerância em desvios padrão; a cauda tem menos amostras e erra mais
+        for (p, tolerance) in [(0.5, 0.03), (0.9, 0.05), (0.99, 0.15), (0.1, 0.05)] {
+            let mut quantile = Quantile::new(p);
+            quantile.extend(data.iter().copied());
+            let estimate = quantile.estimate().unwrap();
+            let expected = exact(&data, p);
+            assert!(
+                (estimate - expected).abs() < tolerance,
+                "p = {p}: {estimate} != {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_against_sorted_uniform() {
+        let mut rng = Rng::new(9);
+        let data: Vec<f32> = (0..5_000).map(|_| 10.0 * rng.next_f32()).collect();
+
+        for p in [0.25, 0.5, 0.75] {
+            let mut quantile = Quantile::new(p);
+            quantile.extend(data.iter().copied());
+            assert!((quantile.estimate().unwrap() - exact(&data, p)).abs() < 0.1);
+        }
+    }
+
+    /// Mesmo com a série em ordem, pior caso para marcadores que só andam para um lado.
+    #[test]
+    fn test_sorted_input() {
+        let mut median = Quantile::median();
+        median.extend((0..1001).map(|k| k as f32));
+        assert!((median.estimate().unwrap() - 500.0).abs() < 5.0);
+        assert_eq!(median.count(), 1001);
+
+        median.reset();
+        assert_eq!(median.estimate(), None);
+        assert_eq!(median.p(), 0.5);
+    }
+}
@@ -14,6 +14,9 @@ use my_proc_macros_lib::{make_func, show_streams, Greetings};
 
 use greetings::Greetings;
 
+extern crate state_machine;
+use state_machine::state_machine;
+
 #[show_streams]
 fn ordinary_function() -> u32 {
     30
@@ -57,6 +60,47 @@ struct Rectangle {
 // #[derive(Getters)]
 // enum ScreenType {}
 
+/// Mesmo player do `struct_state_with_traits` (Módulo 2, aula 7), verificado em tempo de execução
+mod player {
+    use super::*;
+
+    state_machine! {
+        pub enum Player {
+            Stopped --play--> Playing,
+            Playing --play--> Paused,
+            Playing --stop--> Stopped,
+            Paused --play--> Playing,
+            Paused --stop--> Stopped,
+        }
+    }
+}
+
+/// Mesmo aquecedor do `phantom_types` (Módulo 2, aula 5), verificado em tempo de compilação
+mod heater {
+    use super::*;
+
+    state_machine! {
+        pub typestate Heater {
+            Low --increase--> Medium,
+            Medium --increase--> High,
+            High --decrease--> Medium,
+            Medium --decrease--> Low,
+        }
+    }
+}
+
+/// Tabela completa: todo evento é aceito em todo estado
+mod switch {
+    use super::*;
+
+    state_machine! {
+        pub enum Switch {
+            Off --toggle--> On,
+            On --toggle--> Off,
+        }
+    }
+}
+
 fn main() {
     ordinary_function();
     ordinary_function2();
@@ -72,4 +116,20 @@ fn main() {
         rect.height(),
         rect.color(),
     );
+
+    let mut player = player::Player::default();
+    for event in [player::PlayerEvent::Play, player::PlayerEvent::Stop] {
+        match player.handle(event) {
+            Ok(state) => println!("{:?} -> {:?}", event, state),
+            Err(err) => println!("{}", err),
+        }
+    }
+    println!("{:?}", player.handle(player::PlayerEvent::Stop));
+
+    let heater = heater::Heater::new().increase().increase().decrease();
+    // heater.decrease().decrease(); // Não compila: não existe `decrease` em `Heater<Low>`
+    println!("Heater: {}", heater.state_name());
+
+    let mut switch = switch::Switch::default();
+    println!("{:?}", switch.handle(switch::SwitchEvent::Toggle));
 }
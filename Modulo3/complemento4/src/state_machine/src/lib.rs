@@ -0,0 +1,381 @@
+//! `state_machine!` gera uma máquina de estados a partir de uma tabela de transições no formato
+//! `Origem --evento--> Destino`. O primeiro estado da tabela é o estado inicial.
+//!
+//! Existem dois modos de geração:
+//!
+//! - `enum Nome { ... }`: um `enum` com os estados e outro (`NomeEvent`) com os eventos. As
+//!   transições são verificadas em tempo de execução com `handle(event)`.
+//! - `typestate Nome { ... }`: um marcador por estado e um `Nome<S>`, no mesmo estilo do
+//!   `Heater<S>` com `PhantomData`. Cada evento vira um método que consome `self`, então uma
+//!   transição inválida nem compila.
+//!
+//! Nos dois casos também é gerado um módulo de testes que percorre todas as transições.
+extern crate proc_macro;
+#[macro_use]
+extern crate quote;
+extern crate syn;
+use proc_macro::TokenStream;
+use quote::ToTokens;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{braced, Ident, Token, Visibility};
+
+enum Mode {
+    Enum,
+    TypeState,
+}
+
+struct Transition {
+    from: Ident,
+    event: Ident,
+    to: Ident,
+}
+
+struct Machine {
+    vis: Visibility,
+    mode: Mode,
+    name: Ident,
+    transitions: Vec<Transition>,
+}
+
+impl Parse for Transition {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let from = input.parse()?;
+        input.parse::<Token![-]>()?;
+        input.parse::<Token![-]>()?;
+        let event = input.parse()?;
+        input.parse::<Token![-]>()?;
+        input.parse::<Token![->]>()?;
+        let to = input.parse()?;
+
+        Ok(Self { from, event, to })
+    }
+}
+
+impl Parse for Machine {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let vis = input.parse()?;
+        let mode = if input.peek(Token![enum]) {
+            input.parse::<Token![enum]>()?;
+            Mode::Enum
+        } else {
+            let kind: Ident = input.parse()?;
+            if kind != "typestate" {
+                return Err(syn::Error::new(
+                    kind.span(),
+                    "expected `enum` or `typestate`",
+                ));
+            }
+            Mode::TypeState
+        };
+        let name = input.parse()?;
+
+        let content;
+        braced!(content in input);
+        let transitions = Punctuated::<Transition, Token![,]>::parse_terminated(&content)?;
+
+        Ok(Self {
+            vis,
+            mode,
+            name,
+            transitions: transitions.into_iter().collect(),
+        })
+    }
+}
+
+#[proc_macro]
+pub fn state_machine(input: TokenStream) -> TokenStream {
+    let machine = syn::parse_macro_input!(input as Machine);
+
+    if machine.transitions.is_empty() {
+        return error(&machine.name, "state machine needs at least one transition");
+    }
+
+    // Compara os eventos já em CamelCase: `press` e `Press` viram a mesma variante do enum
+    for (i, t) in machine.transitions.iter().enumerate() {
+        let event = camel_case(&t.event);
+        let duplicated = machine.transitions[..i]
+            .iter()
+            .any(|x| x.from == t.from && camel_case(&x.event) == event);
+        if duplicated {
+            return error(
+                &t.event,
+                &format!("duplicated transition from `{}` on `{}`", t.from, event),
+            );
+        }
+    }
+
+    match machine.mode {
+        Mode::Enum => impl_enum(&machine),
+        Mode::TypeState => impl_typestate(&machine),
+    }
+}
+
+fn error(tokens: impl ToTokens, message: &str) -> TokenStream {
+    syn::Error::new_spanned(tokens, message)
+        .to_compile_error()
+        .into()
+}
+
+/// Estados na ordem em que aparecem na tabela, sem repetições.
+fn states(machine: &Machine) -> Vec<&Ident> {
+    let mut states: Vec<&Ident> = vec![];
+    for t in machine.transitions.iter() {
+        for state in [&t.from, &t.to] {
+            if !states.contains(&state) {
+                states.push(state);
+            }
+        }
+    }
+    states
+}
+
+/// `button_press` -> `ButtonPress`
+fn camel_case(ident: &Ident) -> Ident {
+    let name: String = ident
+        .to_string()
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect();
+
+    Ident::new(&name, ident.span())
+}
+
+/// `PlayerState` -> `player_state`
+fn snake_case(ident: &Ident) -> String {
+    let mut name = String::new();
+    for (i, c) in ident.to_string().chars().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            name.push('_');
+        }
+        name.extend(c.to_lowercase());
+    }
+    name
+}
+
+fn impl_enum(machine: &Machine) -> TokenStream {
+    let vis = &machine.vis;
+    let name = &machine.name;
+    let event_name = format_ident!("{}Event", name);
+    let error_name = format_ident!("{}Error", name);
+    let tests_name = format_ident!("{}_transitions", snake_case(name));
+
+    let states = states(machine);
+    let initial = &machine.transitions[0].from;
+
+    let mut events: Vec<Ident> = vec![];
+    for t in machine.transitions.iter() {
+        let event = camel_case(&t.event);
+        if !events.contains(&event) {
+            events.push(event);
+        }
+    }
+
+    // Com a tabela completa um `_ =>` seria inalcançável e geraria warning no código do usuário
+    let mut pairs: Vec<(&Ident, Ident)> = vec![];
+    for t in machine.transitions.iter() {
+        let pair = (&t.from, camel_case(&t.event));
+        if !pairs.contains(&pair) {
+            pairs.push(pair);
+        }
+    }
+    let complete = pairs.len() == states.len() * events.len();
+    let fallback = (!complete).then(|| {
+        quote! {
+            (state, event) => return Err(#error_name { state, event }),
+        }
+    });
+
+    let arms = machine.transitions.iter().map(|t| {
+        let (from, event, to) = (&t.from, camel_case(&t.event), &t.to);
+        quote! {
+            (#name::#from, #event_name::#event) => #name::#to
+        }
+    });
+    let table = machine.transitions.iter().map(|t| {
+        let (from, event, to) = (&t.from, camel_case(&t.event), &t.to);
+        quote! {
+            (#name::#from, #event_name::#event, #name::#to)
+        }
+    });
+
+    let code = quote! {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        #vis enum #name {
+            #(#states),*
+        }
+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        #vis enum #event_name {
+            #(#events),*
+        }
+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        #vis struct #error_name {
+            pub state: #name,
+            pub event: #event_name,
+        }
+
+        impl ::std::fmt::Display for #error_name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                write!(f, "No transition from {:?} on {:?}", self.state, self.event)
+            }
+        }
+
+        impl ::std::error::Error for #error_name {}
+
+        impl Default for #name {
+            fn default() -> Self {
+                #name::#initial
+            }
+        }
+
+        #[allow(unused)]
+        impl #name {
+            pub const STATES: &'static [#name] = &[#(#name::#states),*];
+            pub const EVENTS: &'static [#event_name] = &[#(#event_name::#events),*];
+            pub const TRANSITIONS: &'static [(#name, #event_name, #name)] = &[#(#table),*];
+
+            /// Aplica `event` e retorna o novo estado. Em caso de erro o estado não muda (se a tabela
+            /// cobre todos os pares estado/evento, nunca há erro).
+            pub fn handle(&mut self, event: #event_name) -> Result<#name, #error_name> {
+                let next = match (*self, event) {
+                    #(#arms,)*
+                    #fallback
+                };
+                *self = next;
+                Ok(next)
+            }
+        }
+
+        #[cfg(test)]
+        mod #tests_name {
+            use super::*;
+
+            #[test]
+            fn exhaustive_transitions() {
+                for &state in #name::STATES {
+                    for &event in #name::EVENTS {
+                        let expected = #name::TRANSITIONS
+                            .iter()
+                            .find(|(from, on, _)| *from == state && *on == event)
+                            .map(|(_, _, to)| *to);
+                        let mut current = state;
+
+                        match (current.handle(event), expected) {
+                            (Ok(to), Some(expected)) => {
+                                assert_eq!(to, expected);
+                                assert_eq!(current, expected);
+                            }
+                            (Err(err), None) => {
+                                assert_eq!(err, #error_name { state, event });
+                                assert_eq!(current, state);
+                            }
+                            (result, expected) => panic!(
+                                "{:?} on {:?}: got {:?}, expected {:?}",
+                                state, event, result, expected
+                            ),
+                        }
+                    }
+                }
+            }
+
+            #[test]
+            fn starts_at_initial_state() {
+                assert_eq!(#name::default(), #name::#initial);
+            }
+        }
+    };
+    TokenStream::from(code)
+}
+
+fn impl_typestate(machine: &Machine) -> TokenStream {
+    let vis = &machine.vis;
+    let name = &machine.name;
+    let trait_name = format_ident!("{}State", name);
+    let tests_name = format_ident!("{}_transitions", snake_case(name));
+
+    let states = states(machine);
+    let initial = &machine.transitions[0].from;
+
+    let methods = machine.transitions.iter().map(|t| {
+        let (from, event, to) = (&t.from, &t.event, &t.to);
+        quote! {
+            impl #name<#from> {
+                pub fn #event(self) -> #name<#to> {
+                    #name { state: ::std::marker::PhantomData }
+                }
+            }
+        }
+    });
+    let checks = machine.transitions.iter().map(|t| {
+        let (from, event, to) = (&t.from, &t.event, &t.to);
+        quote! {
+            let next: #name<#to> = #name::<#from> { state: ::std::marker::PhantomData }.#event();
+            assert_eq!(next.state_name(), stringify!(#to));
+        }
+    });
+
+    let code = quote! {
+        #(
+            #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+            #vis struct #states;
+        )*
+
+        /// Somente os estados declarados na tabela implementam este trait.
+        #vis trait #trait_name {
+            const NAME: &'static str;
+        }
+
+        #(
+            impl #trait_name for #states {
+                const NAME: &'static str = stringify!(#states);
+            }
+        )*
+
+        #vis struct #name<S: #trait_name> {
+            state: ::std::marker::PhantomData<S>,
+        }
+
+        #[allow(unused)]
+        impl<S: #trait_name> #name<S> {
+            pub fn state_name(&self) -> &'static str {
+                S::NAME
+            }
+        }
+
+        #[allow(unused)]
+        impl #name<#initial> {
+            pub fn new() -> Self {
+                #name { state: ::std::marker::PhantomData }
+            }
+        }
+
+        #(
+            #[allow(unused)]
+            #methods
+        )*
+
+        #[cfg(test)]
+        mod #tests_name {
+            use super::*;
+
+            #[test]
+            fn exhaustive_transitions() {
+                #(#checks)*
+            }
+
+            #[test]
+            fn starts_at_initial_state() {
+                assert_eq!(#name::new().state_name(), stringify!(#initial));
+            }
+        }
+    };
+    TokenStream::from(code)
+}